@@ -0,0 +1,96 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Benchmark for formatted strings.
+//!
+//! Run with `cargo bench -p zrx-id --bench format`. This measures the size of
+//! identifiers, which store only the end offsets of spans, and the time it
+//! takes to parse, clone and access components of 1,000,000 identifiers.
+
+use std::error::Error;
+use std::hint::black_box;
+use std::mem::size_of;
+use std::time::{Duration, Instant};
+use zrx_id::Id;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Number of identifiers.
+const IDS: usize = 1_000_000;
+
+/// Number of rounds, of which the fastest is reported.
+const ROUNDS: usize = 5;
+
+// ----------------------------------------------------------------------------
+// Program
+// ----------------------------------------------------------------------------
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let values = (0..IDS)
+        .map(|n| format!("zri:file::docs:guide/{}/page-{n}.md:", n % 100))
+        .collect::<Vec<_>>();
+
+    // Parse identifiers, which computes all spans
+    let ids = values
+        .iter()
+        .map(|value| value.parse())
+        .collect::<Result<Vec<Id>, _>>()?;
+    println!("size    {:>6} B", size_of::<Id>());
+    report("parse", || {
+        for value in &values {
+            black_box(black_box(value).parse::<Id>().ok());
+        }
+    });
+
+    // Clone identifiers, which copies the span storage
+    report("clone", || {
+        black_box(black_box(&ids).clone());
+    });
+
+    // Access components, which derives spans from their end offsets
+    report("access", || {
+        for id in &ids {
+            black_box((id.scheme(), id.context(), id.path()));
+        }
+    });
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Reports the fastest of all rounds of the given function.
+fn report<F>(name: &str, mut f: F)
+where
+    F: FnMut(),
+{
+    let mut fastest = Duration::MAX;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        f();
+        fastest = fastest.min(start.elapsed());
+    }
+    println!("{name:<7} {fastest:>8.1?}");
+}
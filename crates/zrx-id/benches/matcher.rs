@@ -0,0 +1,110 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Benchmark for matching literal and glob selectors.
+//!
+//! Run with `cargo bench -p zrx-id --bench matcher`. This compares a matcher
+//! with 1,000 literal selectors and 50 glob selectors against one in which the
+//! literal selectors are wrapped in `{...}`, so they're compiled into the glob
+//! sets instead of being looked up.
+
+use std::error::Error;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use zrx_id::prelude::*;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Number of literal selectors.
+const LITERALS: usize = 1_000;
+
+/// Number of glob selectors.
+const GLOBS: usize = 50;
+
+/// Number of passes over all identifiers.
+const PASSES: usize = 20;
+
+// ----------------------------------------------------------------------------
+// Program
+// ----------------------------------------------------------------------------
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // Create identifiers matched by literal selectors, and twice as many that
+    // aren't, half of which are matched by glob selectors
+    let mut ids = Vec::with_capacity(LITERALS * 3);
+    for n in 0..LITERALS {
+        let section = n % (GLOBS * 2);
+        ids.push(Id::new("file", "docs", format!("page-{n}.md"))?);
+        ids.push(Id::new("file", "docs", format!("{section}/a-{n}.md"))?);
+        ids.push(Id::new("file", "docs", format!("{section}/b-{n}.txt"))?);
+    }
+
+    // Create matchers with literal selectors, and with the same selectors
+    // compiled into glob sets, and ensure both of them agree
+    let literal = matcher(str::to_owned)?;
+    let compiled = matcher(|path| format!("{{{path}}}"))?;
+    for id in &ids {
+        assert_eq!(literal.matches(id)?, compiled.matches(id)?, "{id}");
+    }
+
+    // Measure both matchers
+    let a = measure(&literal, &ids)?;
+    let b = measure(&compiled, &ids)?;
+    let n = u32::try_from(ids.len() * PASSES)?;
+    println!("literal:  {:>8.0?} ({:?}/id)", a, a / n);
+    println!("compiled: {:>8.0?} ({:?}/id)", b, b / n);
+    println!("speedup:  {:.1}x", b.as_secs_f64() / a.as_secs_f64());
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Creates a matcher, mapping the path of each literal selector.
+fn matcher<F>(f: F) -> Result<Matcher, Box<dyn Error>>
+where
+    F: Fn(&str) -> String,
+{
+    let mut builder = Matcher::builder();
+    for n in 0..LITERALS {
+        let path = f(&format!("page-{n}.md"));
+        builder.add(format!("zrs:file:-:docs:{path}:-").as_str())?;
+    }
+    for n in 0..GLOBS {
+        builder.add(format!("zrs:file::docs:{n}/**/*.md:").as_str())?;
+    }
+    Ok(builder.build()?)
+}
+
+/// Measures the time it takes to match all identifiers.
+fn measure(matcher: &Matcher, ids: &[Id]) -> Result<Duration, Box<dyn Error>> {
+    let start = Instant::now();
+    for _ in 0..PASSES {
+        for id in ids {
+            black_box(matcher.matches(black_box(id))?);
+        }
+    }
+    Ok(start.elapsed())
+}
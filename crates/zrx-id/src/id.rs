@@ -22,19 +22,55 @@
 
 //! Identifier.
 
-use std::borrow::Cow;
-use std::fmt;
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::ffi::{OsStr, OsString};
+use std::fmt::{self, Write};
+use std::io;
+use std::ops::Range;
+use std::path::Path;
 use std::str::FromStr;
 
+mod builder;
+mod change;
+mod component;
 mod error;
 pub mod format;
+pub mod fragment;
+mod graph;
+mod map;
 pub mod matcher;
+mod parts;
 pub mod path;
+mod pool;
+#[cfg(feature = "proptest")]
+mod proptest;
+mod scheme;
+#[cfg(feature = "serde")]
+mod serde;
+mod version;
+mod view;
 
+pub use builder::IdBuilder;
+pub use change::ChangeSet;
+pub use component::{Component, ComponentSet};
 pub use error::{Error, Result};
-use format::encoding::encode;
-use format::Format;
-use path::validate;
+use format::container::Container;
+use format::{check_len, Format};
+pub use graph::IdGraph;
+pub use map::IdMap;
+use matcher::Selector;
+pub use parts::IdParts;
+use path::{
+    canonicalize, file_name, join, normalize, parent, relative,
+    replace_extension, split_extension, split_segments, starts_with, to_url,
+    validate, validate_byte, Validation,
+};
+pub use pool::{IdPool, InternedId};
+pub use scheme::SchemeRegistry;
+pub use version::migrate;
+pub use view::IdView;
 
 // ----------------------------------------------------------------------------
 // Traits
@@ -47,9 +83,17 @@ use path::validate;
 pub trait ToId {
     /// Creates an identifier.
     #[allow(clippy::missing_errors_doc)]
-    fn to_id(&self) -> Result<Cow<Id>>;
+    fn to_id(&self) -> Result<Cow<'_, Id>>;
 }
 
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Labels of components for alternate display, omitting the prefix.
+const LABELS: [&str; 6] =
+    ["", "scheme", "binding", "context", "path", "fragment"];
+
 // ----------------------------------------------------------------------------
 // Structs
 // ----------------------------------------------------------------------------
@@ -72,7 +116,9 @@ pub trait ToId {
 /// to unify behavior among different operating system, ensuring that caches
 /// are portable. This is also why every method that takes a value will return
 /// [`Error::Backslash`][] if a backslash is found. Thus, the caller should
-/// use a library like [`path-slash`][] for consistent normalization.
+/// use a library like [`path-slash`][] for consistent normalization. NUL
+/// bytes are rejected with [`Error::Nul`][] for the same reason, as they'd
+/// truncate values when passed to C functions.
 ///
 /// Identifiers implement [`Eq`], [`PartialEq`] and [`Hash`], as well as [`Ord`]
 /// and [`PartialOrd`], as they are used in events that move through the system,
@@ -85,8 +131,14 @@ pub trait ToId {
 ///
 /// The decision to use a structured string representation as a data model was
 /// made to allow for blazing fast cloning and derivation of new identifiers.
+/// Since offsets are stored as `u16`, the string representation is limited
+/// to [`Format::MAX_LEN`][] bytes, including separators and percent-encoded
+/// characters. Longer identifiers are rejected with [`Error::TooLong`][].
 ///
+/// [`Error::TooLong`]: crate::format::Error::TooLong
+/// [`Format::MAX_LEN`]: crate::format::Format::MAX_LEN
 /// [`Error::Backslash`]: crate::path::Error::Backslash
+/// [`Error::Nul`]: crate::path::Error::Nul
 /// [`path-slash`]: https://crates.io/crates/path-slash
 ///
 /// # Examples
@@ -96,7 +148,7 @@ pub trait ToId {
 /// ```
 /// # use std::error::Error;
 /// # fn main() -> Result<(), Box<dyn Error>> {
-/// use zrx_id::Id;
+/// use zrx_id::prelude::*;
 ///
 /// // Create identifier
 /// let id = Id::new("file", "docs", "index.md")?;
@@ -109,7 +161,7 @@ pub trait ToId {
 /// ```
 /// # use std::error::Error;
 /// # fn main() -> Result<(), Box<dyn Error>> {
-/// use zrx_id::Id;
+/// use zrx_id::prelude::*;
 ///
 /// // Create identifier from string
 /// let id: Id = "zri:file::docs:index.md:".parse()?;
@@ -129,6 +181,9 @@ pub struct Id {
 impl Id {
     /// Creates an identifier.
     ///
+    /// The optional `binding` and `fragment` components are left empty. Use
+    /// [`Id::new_full`] to create an identifier with all five components.
+    ///
     /// # Errors
     ///
     /// This method returns [`Error::Path`], if a component value contains a
@@ -139,115 +194,212 @@ impl Id {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Id;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create identifier
     /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(id, "zri:file::docs:index.md:".parse::<Id>()?);
+    ///
+    /// // Create identifier with component that needs encoding
+    /// let id = Id::new("file", "docs", "a:b.md")?;
+    /// assert_eq!(id, "zri:file::docs:a%3Ab.md:".parse::<Id>()?);
+    /// assert_eq!(id.path(), "a:b.md");
+    ///
+    /// // Create identifier with component that consists of a separator
+    /// let id = Id::new("file", "docs", ":")?;
+    /// assert_eq!(id.path(), ":");
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// Identifiers are identical to those obtained by encoding and joining
+    /// the components, and parsing the resulting string:
+    ///
+    /// ```
+    /// # #[cfg(feature = "proptest")]
+    /// # fn main() {
+    /// use proptest::prelude::*;
+    /// use zrx_id::format::encoding::encode;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create strategy for components, including characters to encode
+    /// let value = "[a-z:%/ é\n]{1,8}";
+    ///
+    /// // Ensure identifiers are identical to parsed identifiers
+    /// proptest!(|(scheme in value, context in value, path in value)| {
+    ///     let id = Id::new(&scheme, &context, &path).unwrap();
+    ///     let value = format!(
+    ///         "zri:{}::{}:{}:",
+    ///         encode(scheme.as_bytes()),
+    ///         encode(context.as_bytes()),
+    ///         encode(path.as_bytes()),
+    ///     );
+    ///     let parsed: Id = value.parse().unwrap();
+    ///     prop_assert_eq!(id.as_str(), parsed.as_str());
+    ///     prop_assert_eq!(id.scheme(), scheme);
+    ///     prop_assert_eq!(id.context(), context);
+    ///     prop_assert_eq!(id.path(), path);
+    ///     prop_assert_eq!(id, parsed);
+    /// });
+    /// # }
+    /// # #[cfg(not(feature = "proptest"))]
+    /// # fn main() {}
+    /// ```
+    ///
+    /// Identifiers that exceed the length limit are rejected:
+    ///
+    /// ```
+    /// use zrx_id::{format, Error, Id};
+    ///
+    /// // Define long path, and expected error
+    /// let path = "a".repeat(70_000);
+    /// let check = |err: Error| {
+    ///     matches!(
+    ///         err,
+    ///         Error::Format(format::Error::TooLong { max: 65_535, .. })
+    ///     )
+    /// };
+    ///
+    /// // Ensure identifier is rejected when created, updated and parsed
+    /// assert!(check(Id::new("file", "docs", &path).unwrap_err()));
+    /// let mut id = Id::new("file", "docs", "index.md").unwrap();
+    /// assert!(check(id.set_path(&path).unwrap_err()));
+    /// let value = format!("zri:file::docs:{path}:");
+    /// assert!(check(value.parse::<Id>().unwrap_err()));
+    /// ```
     pub fn new<S, C, P>(scheme: S, context: C, path: P) -> Result<Self>
     where
         S: AsRef<[u8]>,
         C: AsRef<[u8]>,
         P: AsRef<[u8]>,
     {
-        // We must check if any of the values contains a `:` separator, so pass
-        // them through the encoder first, which will be a no-op in most cases
-        let scheme = encode(validate(scheme.as_ref())?);
-        let context = encode(validate(context.as_ref())?);
-        let path = encode(validate(path.as_ref())?);
-
-        // Compute required capacity
-        let capacity = 8 + scheme.len() + context.len() + path.len();
-
-        // Create identifier by appending each component with `:` separators
-        // to a string buffer instead of using the `format!` macro, and parse
-        // the string, instead of setting the components on the formatted
-        // string one after another, yielding a 5x performance increase
-        let mut buffer = String::with_capacity(capacity);
-        buffer.push_str("zri:");
-        buffer.push_str(scheme.as_ref());
-        buffer.push_str("::");
-        buffer.push_str(context.as_ref());
-        buffer.push(':');
-        buffer.push_str(path.as_ref());
-        buffer.push(':');
-
-        // Return identifier after parsing formatted string
-        buffer
-            .parse()
-            .map_err(Into::into)
-            .map(|format| Self { format })
+        let scheme = validate(scheme.as_ref())?;
+        let context = validate(context.as_ref())?;
+        let path = validate(path.as_ref())?;
+
+        // Ensure that the components fit before encoding, which never shrinks
+        // them, so identifiers that are too long are rejected without copies
+        check_len(8 + scheme.len() + context.len() + path.len())?;
+
+        // Create identifier by writing all components to the formatted string
+        // in a single pass, which encodes values containing `:` separators
+        let parts = [b"zri", scheme, b"", context, path, b""];
+        Ok(Self {
+            format: Format::from_parts(parts)?,
+        })
     }
 
-    /// Updates the `scheme` component.
+    /// Creates an identifier with the given validation policy.
+    ///
+    /// This is the variant of [`Id::new`] that applies the given [`Validation`]
+    /// policy to all components before the usual checks, e.g., to rewrite the
+    /// backslashes of Windows-style paths to forward slashes.
     ///
     /// # Errors
     ///
-    /// This method returns [`Error::Path`], if the component value contains a
-    /// backslash, or [`Error::Format`], if the format is invalid.
+    /// This method returns the same errors as [`Id::new`].
     ///
     /// # Examples
     ///
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Id;
+    /// use zrx_id::path::Validation;
+    /// use zrx_id::prelude::*;
     ///
-    /// // Create identifier and set scheme
-    /// let mut id = Id::new("file", "docs", "index.md")?;
-    /// id.set_scheme("git")?;
+    /// // Create identifier from Windows-style path
+    /// let policy = Validation::NormalizeBackslashes;
+    /// let id = Id::new_with(policy, "file", r"docs\api", r"guide\index.md")?;
+    /// assert_eq!(id, Id::new("file", "docs/api", "guide/index.md")?);
     /// # Ok(())
     /// # }
     /// ```
-    #[inline]
-    pub fn set_scheme<S>(&mut self, scheme: S) -> Result<&mut Self>
+    pub fn new_with<S, C, P>(
+        policy: Validation, scheme: S, context: C, path: P,
+    ) -> Result<Self>
     where
         S: AsRef<[u8]>,
+        C: AsRef<[u8]>,
+        P: AsRef<[u8]>,
     {
-        self.format
-            .set(1, validate(scheme)?)
-            .map_err(Into::into)
-            .map(|()| self)
+        Self::new(
+            policy.apply_bytes(scheme.as_ref()),
+            policy.apply_bytes(context.as_ref()),
+            policy.apply_bytes(path.as_ref()),
+        )
     }
 
-    /// Updates the `binding` component.
+    /// Creates an identifier with normalized `context` and `path` components.
+    ///
+    /// This is the variant of [`Id::new`] that normalizes the `context` and
+    /// `path` components before creating the identifier, so that identifiers
+    /// referring to the same resource compare equal and hash identically. In
+    /// both components, duplicate slashes are collapsed, and empty and `.`
+    /// segments are dropped, including trailing slashes.
     ///
     /// # Errors
     ///
-    /// This method returns [`Error::Path`], if the component value contains a
-    /// backslash, or [`Error::Format`], if the format is invalid.
+    /// In addition to the errors returned by [`Id::new`], this method returns
+    /// [`Error::Path`], if the `context` or `path` is absolute or contains
+    /// `..`, using the same rules as the conversion into a [`PathBuf`][].
+    ///
+    /// [`PathBuf`]: std::path::PathBuf
     ///
     /// # Examples
     ///
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Id;
+    /// use zrx_id::prelude::*;
     ///
-    /// // Create identifier and set binding
-    /// let mut id = Id::new("git", "docs", "index.md")?;
-    /// id.set_binding("master")?;
+    /// // Create identifiers from messy paths and ensure they are canonical
+    /// let id = Id::new("file", "docs", "a/b/c.md")?;
+    /// for path in
+    ///     ["./a//b/./c.md", "a/b/c.md/", "a///b/c.md", "./a/./b//c.md"]
+    /// {
+    ///     assert_eq!(Id::new_normalized("file", "docs", path)?, id);
+    /// }
+    ///
+    /// // Create identifier from messy context
+    /// let id = Id::new_normalized("file", "./docs//api/", "index.md")?;
+    /// assert_eq!(id.to_string(), "zri:file::docs/api:index.md:");
     /// # Ok(())
     /// # }
     /// ```
-    #[inline]
-    pub fn set_binding<S>(&mut self, binding: S) -> Result<&mut Self>
+    ///
+    /// Absolute paths and paths containing `..` are rejected:
+    ///
+    /// ```
+    /// use zrx_id::{path, Error, Id};
+    ///
+    /// // Create identifiers from invalid paths
+    /// let res = Id::new_normalized("file", "docs", "a/../b.md");
+    /// assert!(matches!(res, Err(Error::Path(path::Error::ParentDir))));
+    /// let res = Id::new_normalized("file", "/docs", "index.md");
+    /// assert!(matches!(res, Err(Error::Path(path::Error::RootDir))));
+    /// ```
+    pub fn new_normalized<S, C, P>(
+        scheme: S, context: C, path: P,
+    ) -> Result<Self>
     where
         S: AsRef<[u8]>,
+        C: AsRef<[u8]>,
+        P: AsRef<[u8]>,
     {
-        self.format
-            .set(2, validate(binding)?)
-            .map_err(Into::into)
-            .map(|()| self)
+        let context = canonicalize(context.as_ref())?;
+        let path = canonicalize(path.as_ref())?;
+        Self::new(scheme, context, path)
     }
 
-    /// Updates the `context` component.
+    /// Creates an identifier with all components.
+    ///
+    /// This is the variant of [`Id::new`] that also accepts the optional
+    /// `binding` and `fragment` components, which are absent if empty. All
+    /// components are written to the formatted string in a single pass.
     ///
     /// # Errors
     ///
-    /// This method returns [`Error::Path`], if the component value contains a
+    /// This method returns [`Error::Path`], if a component value contains a
     /// backslash, or [`Error::Format`], if the format is invalid.
     ///
     /// # Examples
@@ -255,238 +407,2785 @@ impl Id {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Id;
+    /// use zrx_id::prelude::*;
     ///
-    /// // Create identifier and set context
-    /// let mut id = Id::new("file", "docs", "index.md")?;
-    /// id.set_context("examples")?;
+    /// // Create identifier with binding and fragment
+    /// let id = Id::new_full("git", "master", "docs", "index.md", "top")?;
+    /// assert_eq!(id, "zri:git:master:docs:index.md:top".parse::<Id>()?);
+    ///
+    /// // Create identifier with empty binding and fragment
+    /// let id = Id::new_full("file", "", "docs", "index.md", "")?;
+    /// assert_eq!(id, Id::new("file", "docs", "index.md")?);
+    /// assert_eq!(id.binding(), None);
     /// # Ok(())
     /// # }
     /// ```
-    #[inline]
-    pub fn set_context<S>(&mut self, context: S) -> Result<&mut Self>
+    ///
+    /// NUL bytes are rejected in all components, as well as by all setters:
+    ///
+    /// ```
+    /// use zrx_id::{path, Error, Id};
+    ///
+    /// // Define components, each containing a NUL byte in one of them
+    /// let cases = [
+    ///     ["fi\0le", "", "docs", "index.md", ""],
+    ///     ["file", "ma\0ster", "docs", "index.md", ""],
+    ///     ["file", "", "do\0cs", "index.md", ""],
+    ///     ["file", "", "docs", "in\0dex.md", ""],
+    ///     ["file", "", "docs", "index.md", "to\0p"],
+    /// ];
+    ///
+    /// // Ensure each identifier is rejected with the offset of the NUL byte
+    /// for [scheme, binding, context, path, fragment] in cases {
+    ///     let err = Id::new_full(scheme, binding, context, path, fragment)
+    ///         .unwrap_err();
+    ///     assert!(
+    ///         matches!(err, Error::Path(path::Error::Nul { offset: 2 })),
+    ///         "{err:?}"
+    ///     );
+    /// }
+    ///
+    /// // Ensure setters reject NUL bytes as well
+    /// let mut id = Id::new("file", "docs", "index.md").unwrap();
+    /// assert!(id.set_scheme("fi\0le").is_err());
+    /// assert!(id.set_binding("ma\0ster").is_err());
+    /// assert!(id.set_context("do\0cs").is_err());
+    /// assert!(id.set_path("in\0dex.md").is_err());
+    /// assert!(id.set_fragment("to\0p").is_err());
+    ///
+    /// // Ensure percent-encoded control characters are retained
+    /// let id = Id::new("file", "docs", "a\nb.md").unwrap();
+    /// assert_eq!(id.to_string(), "zri:file::docs:a%0Ab.md:");
+    /// ```
+    pub fn new_full<S, B, C, P, F>(
+        scheme: S, binding: B, context: C, path: P, fragment: F,
+    ) -> Result<Self>
     where
         S: AsRef<[u8]>,
+        B: AsRef<[u8]>,
+        C: AsRef<[u8]>,
+        P: AsRef<[u8]>,
+        F: AsRef<[u8]>,
     {
-        self.format
-            .set(3, validate(context)?)
-            .map_err(Into::into)
-            .map(|()| self)
+        let scheme = validate(scheme.as_ref())?;
+        let binding = validate(binding.as_ref())?;
+        let context = validate(context.as_ref())?;
+        let path = validate(path.as_ref())?;
+        let fragment = validate(fragment.as_ref())?;
+
+        // Ensure that the components fit before encoding, which never shrinks
+        // them, so identifiers that are too long are rejected without copies
+        let len = binding.len() + context.len() + path.len() + fragment.len();
+        check_len(8 + scheme.len() + len)?;
+
+        // Create identifier by writing all components to the formatted string
+        // in a single pass, which encodes values containing `:` separators
+        let parts = [b"zri", scheme, binding, context, path, fragment];
+        Ok(Self {
+            format: Format::from_parts(parts)?,
+        })
     }
 
-    /// Updates the `path` component.
+    /// Creates an identifier from an OS string.
+    ///
+    /// OS strings aren't guaranteed to be valid Unicode, e.g., on Unix, where
+    /// they are arbitrary bytes, or on Windows, where they are potentially
+    /// ill-formed UTF-16. Other than silently replacing invalid sequences, the
+    /// conversion fails, carrying the lossy rendering for diagnostics.
     ///
     /// # Errors
     ///
-    /// This method returns [`Error::Path`], if the component value contains a
-    /// backslash, or [`Error::Format`], if the format is invalid.
+    /// This method returns [`Error::NonUtf8`], if the value isn't valid UTF-8,
+    /// and otherwise, all errors of [`Id::from_str`] apply.
     ///
     /// # Examples
     ///
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Id;
+    /// use std::ffi::OsStr;
+    /// use zrx_id::prelude::*;
     ///
-    /// // Create identifier and set path
-    /// let mut id = Id::new("file", "docs", "index.md")?;
-    /// id.set_path("README.md")?;
+    /// // Create identifier from OS string
+    /// let id = Id::from_os_str(OsStr::new("zri:file::docs:index.md:"))?;
+    /// assert_eq!(id.to_os_string(), "zri:file::docs:index.md:");
     /// # Ok(())
     /// # }
     /// ```
-    #[inline]
-    pub fn set_path<S>(&mut self, path: S) -> Result<&mut Self>
-    where
-        S: AsRef<[u8]>,
-    {
-        self.format
-            .set(4, validate(path)?)
-            .map_err(Into::into)
-            .map(|()| self)
+    ///
+    /// Values that aren't valid UTF-8 are rejected:
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::ffi::OsStr;
+    /// use std::os::unix::ffi::OsStrExt;
+    /// use zrx_id::{Error, Id};
+    ///
+    /// // Create identifier from OS string with invalid UTF-8
+    /// let value = OsStr::from_bytes(b"zri:file::docs:\xFF.md:");
+    /// match Id::from_os_str(value) {
+    ///     Err(Error::NonUtf8 { lossy }) => {
+    ///         assert_eq!(lossy, "zri:file::docs:\u{FFFD}.md:");
+    ///     }
+    ///     result => panic!("unexpected result: {result:?}"),
+    /// }
+    /// # }
+    /// ```
+    pub fn from_os_str(value: &OsStr) -> Result<Self> {
+        match value.to_str() {
+            Some(value) => value.parse(),
+            None => Err(Error::NonUtf8 {
+                lossy: value.to_string_lossy().into_owned(),
+            }),
+        }
     }
 
-    /// Updates the `fragment` component.
+    /// Creates an identifier from a file system path.
+    ///
+    /// This is intended for paths obtained from directory walkers, which are
+    /// normalized to use forward slashes, where backslashes are treated as
+    /// separators on all platforms. If the path starts with the context, it
+    /// is stripped, so paths can be given relative to the context, or to its
+    /// parent. Absolute paths and paths containing `..` are rejected, using
+    /// the same rules as the conversion into a [`PathBuf`][].
+    ///
+    /// [`PathBuf`]: std::path::PathBuf
     ///
     /// # Errors
     ///
-    /// This method returns [`Error::Path`], if the component value contains a
-    /// backslash, or [`Error::Format`], if the format is invalid.
+    /// This method returns [`Error::NonUtf8`], if the path is not valid UTF-8,
+    /// carrying the lossy rendering for diagnostics, [`Error::Path`], if the
+    /// path is absolute, starts with a Windows prefix, or contains `..`, as
+    /// well as [`Error::Component`], if the path is empty after normalization,
+    /// or [`Error::Format`], if the format is invalid. Windows prefixes can be
+    /// removed with [`path::strip_windows_prefix`] beforehand. Use
+    /// [`Id::from_path_lossy`] for display-only pipelines.
     ///
     /// # Examples
     ///
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Id;
+    /// use std::path::Path;
+    /// use zrx_id::prelude::*;
     ///
-    /// // Create identifier and set fragment
-    /// let mut id = Id::new("file", "docs", "index.md")?;
-    /// id.set_fragment("anchor")?;
+    /// // Create identifier from path, stripping the context
+    /// let path = Path::new("docs/guide/index.md");
+    /// let id = Id::from_path("file", "docs", path)?;
+    /// assert_eq!(id.to_string(), "zri:file::docs:guide/index.md:");
+    ///
+    /// // Create identifier from path relative to the context
+    /// let path = Path::new("./guide//index.md");
+    /// let id = Id::from_path("file", "docs", path)?;
+    /// assert_eq!(id.path(), "guide/index.md");
+    ///
+    /// // Create identifier from Windows-style path
+    /// let path = Path::new(r"docs\guide\index.md");
+    /// let id = Id::from_path("file", "docs", path)?;
+    /// assert_eq!(id.path(), "guide/index.md");
     /// # Ok(())
     /// # }
     /// ```
-    #[inline]
-    pub fn set_fragment<S>(&mut self, fragment: S) -> Result<&mut Self>
+    ///
+    /// Absolute paths and paths containing `..` are rejected:
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use zrx_id::{path, Error, Id};
+    ///
+    /// // Define invalid paths and expected errors
+    /// let cases: [(&str, fn(&Error) -> bool); 9] = [
+    ///     ("/docs/index.md", |e| {
+    ///         matches!(e, Error::Path(path::Error::RootDir))
+    ///     }),
+    ///     (r"\docs\index.md", |e| {
+    ///         matches!(e, Error::Path(path::Error::RootDir))
+    ///     }),
+    ///     (r"C:\docs\index.md", |e| {
+    ///         matches!(e, Error::Path(path::Error::WindowsPrefix(p))
+    ///             if p == "C:")
+    ///     }),
+    ///     ("c:docs/index.md", |e| {
+    ///         matches!(e, Error::Path(path::Error::WindowsPrefix(p))
+    ///             if p == "c:")
+    ///     }),
+    ///     (r"\\server\share", |e| {
+    ///         matches!(e, Error::Path(path::Error::WindowsPrefix(p))
+    ///             if p == r"\\server\share")
+    ///     }),
+    ///     (r"\\?\C:\docs", |e| {
+    ///         matches!(e, Error::Path(path::Error::WindowsPrefix(p))
+    ///             if p == r"\\?\C:")
+    ///     }),
+    ///     (r"\\.\COM1", |e| {
+    ///         matches!(e, Error::Path(path::Error::WindowsPrefix(p))
+    ///             if p == r"\\.\COM1")
+    ///     }),
+    ///     ("docs/../a.md", |e| {
+    ///         matches!(e, Error::Path(path::Error::ParentDir))
+    ///     }),
+    ///     (r"guide\..\a.md", |e| {
+    ///         matches!(e, Error::Path(path::Error::ParentDir))
+    ///     }),
+    /// ];
+    ///
+    /// // Ensure each path is rejected with the expected error
+    /// for (value, check) in cases {
+    ///     let path = Path::new(value);
+    ///     let err = Id::from_path("file", "docs", path).unwrap_err();
+    ///     assert!(check(&err), "{value}: {err:?}");
+    /// }
+    ///
+    /// // Ensure paths that are empty after normalization are rejected
+    /// let res = Id::from_path("file", "docs", Path::new("docs/"));
+    /// assert!(matches!(res, Err(Error::Component { name: "path", .. })));
+    ///
+    /// // Ensure paths that are not valid UTF-8 are rejected
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::ffi::OsStr;
+    /// use std::os::unix::ffi::OsStrExt;
+    /// let value = OsStr::from_bytes(b"docs/\xFF.md");
+    /// match Id::from_path("file", "docs", value) {
+    ///     Err(Error::NonUtf8 { lossy }) => {
+    ///         assert_eq!(lossy, "docs/\u{FFFD}.md");
+    ///     }
+    ///     result => panic!("unexpected result: {result:?}"),
+    /// }
+    /// # }
+    /// ```
+    pub fn from_path<P>(scheme: &str, context: &str, path: P) -> Result<Self>
     where
-        S: AsRef<[u8]>,
+        P: AsRef<Path>,
     {
-        self.format
-            .set(5, validate(fragment)?)
-            .map_err(Into::into)
-            .map(|()| self)
-    }
-}
-
-#[allow(clippy::must_use_candidate)]
-impl Id {
-    /// Returns the `scheme` component.
-    #[inline]
-    pub fn scheme(&self) -> Cow<str> {
-        self.format.get(1)
-    }
-
-    /// Returns the `binding` component, if any.
-    #[inline]
-    pub fn binding(&self) -> Option<Cow<str>> {
-        Some(self.format.get(2)).filter(|value| !value.is_empty())
-    }
-
-    /// Returns the `context` component.
-    #[inline]
-    pub fn context(&self) -> Cow<str> {
-        self.format.get(3)
-    }
-
-    /// Returns the `path` component.
-    #[inline]
-    pub fn path(&self) -> Cow<str> {
-        self.format.get(4)
-    }
-
-    /// Returns the `fragment` component, if any.
-    #[inline]
-    pub fn fragment(&self) -> Option<Cow<str>> {
-        Some(self.format.get(5)).filter(|value| !value.is_empty())
+        let path = to_str(path.as_ref())?;
+        from_path_str(scheme, context, path)
     }
-}
-
-// ----------------------------------------------------------------------------
-// Trait implementations
-// ----------------------------------------------------------------------------
 
-impl ToId for &Id {
-    /// Creates an identifier from a reference.
-    #[inline]
-    fn to_id(&self) -> Result<Cow<Id>> {
-        Ok(Cow::Borrowed(self))
+    /// Creates an identifier from a file system path, replacing invalid UTF-8.
+    ///
+    /// This is the variant of [`Id::from_path`] that replaces invalid UTF-8
+    /// sequences with [`U+FFFD`][char::REPLACEMENT_CHARACTER], instead of
+    /// returning an error. Note that this is lossy, and thus can't be reversed,
+    /// as distinct paths might map to the same identifier, which is why it must
+    /// only be used in display-only pipelines, and never for caching.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Id::from_path`], except for
+    /// [`Error::NonUtf8`], which is never returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::path::Path;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier from path
+    /// let path = Path::new("docs/guide/index.md");
+    /// let id = Id::from_path_lossy("file", "docs", path)?;
+    /// assert_eq!(id, Id::from_path("file", "docs", path)?);
+    ///
+    /// // Create identifier from path that isn't valid UTF-8
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::ffi::OsStr;
+    /// use std::os::unix::ffi::OsStrExt;
+    /// let value = OsStr::from_bytes(b"docs/\xFF.md");
+    /// let id = Id::from_path_lossy("file", "docs", value)?;
+    /// assert_eq!(id.path(), "\u{FFFD}.md");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_path_lossy<P>(
+        scheme: &str, context: &str, path: P,
+    ) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_string_lossy();
+        from_path_str(scheme, context, &path)
     }
-}
 
-impl ToId for &str {
-    /// Creates an identifier from a string.
+    /// Creates an identifier from a UTF-8 file system path.
+    ///
+    /// This is the equivalent of [`Id::from_path`] for [`Utf8Path`], which
+    /// applies the same normalization, including the treatment of backslashes
+    /// as separators, and the same checks.
+    ///
+    /// [`Utf8Path`]: camino::Utf8Path
     ///
     /// # Errors
     ///
-    /// This method returns [`Error::Path`], if a component value contains a
-    /// backslash, or [`Error::Format`], if the format is invalid.
+    /// This method returns [`Error::Path`], if the path is absolute or contains
+    /// `..`, [`Error::Component`], if the path is empty after normalization, or
+    /// [`Error::Format`], if the format is invalid.
     ///
     /// # Examples
     ///
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::{Id, ToId};
+    /// use camino::{Utf8Path, Utf8PathBuf};
+    /// use zrx_id::prelude::*;
     ///
-    /// // Create identifier from string
-    /// let id = "zri:file::docs:index.md:".to_id()?;
+    /// // Create identifiers from paths with both families of separators
+    /// for value in ["docs/guide/index.md", r"docs\guide\index.md"] {
+    ///     let id = Id::from_utf8_path("file", "docs", Utf8Path::new(value))?;
+    ///     assert_eq!(id.to_string(), "zri:file::docs:guide/index.md:");
+    ///
+    ///     // Convert identifier back into path and round-trip it
+    ///     let path = Utf8PathBuf::try_from(&id)?;
+    ///     assert_eq!(
+    ///         path,
+    ///         Utf8Path::new("docs").join("guide").join("index.md")
+    ///     );
+    ///     assert_eq!(Id::from_utf8_path("file", "docs", &path)?, id);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "camino")]
     #[inline]
-    fn to_id(&self) -> Result<Cow<Id>> {
-        self.parse().map(Cow::Owned)
+    pub fn from_utf8_path(
+        scheme: &str, context: &str, path: &camino::Utf8Path,
+    ) -> Result<Self> {
+        Self::from_path(scheme, context, path.as_std_path())
     }
-}
 
-// ----------------------------------------------------------------------------
-
-impl FromStr for Id {
-    type Err = Error;
-
-    /// Attempts to create an identifier from a string.
-    ///
-    /// The string must adhere to the following format and include exactly five
-    /// `:` separators, even if some components are omitted. Only the `binding`
-    /// and `fragment` components are optional and can be left empty, all other
-    /// components must be present:
+    /// Creates an identifier from a URL.
     ///
-    /// ``` text
-    /// zri:<scheme>:<binding>:<context>:<path>:<fragment>
-    /// ```
+    /// This is the inverse of [`Id::to_url`] for a base URL without a path,
+    /// i.e., the path of the URL must start with the segments of the given
+    /// `context`, which are stripped, and the remaining segments become the
+    /// `path` component. The URL fragment becomes the `fragment` component.
+    /// Segments and the fragment are percent-decoded, and empty segments are
+    /// dropped. Host, query and all other parts of the URL are ignored.
     ///
     /// # Errors
     ///
-    /// This method returns [`Error::Path`], if a component value contains a
-    /// backslash, or [`Error::Format`], if the format is invalid.
+    /// This method returns [`Error::Mismatch`], if the path of the URL doesn't
+    /// start with the `context`, [`Error::Component`], if the URL has no path
+    /// after the `context`, and [`Error::NonUtf8`], if a decoded segment or the
+    /// decoded fragment is not valid UTF-8.
     ///
     /// # Examples
     ///
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Id;
+    /// use url::Url;
+    /// use zrx_id::prelude::*;
     ///
-    /// // Create identifier from string
-    /// let id: Id = "zri:file::docs:index.md:".parse()?;
+    /// // Create identifier from URL
+    /// let url = Url::parse("https://example.com/docs/my%20guide/a.md#top")?;
+    /// let id = Id::from_url("file", "docs", &url)?;
+    /// assert_eq!(id.path(), "my guide/a.md");
+    /// assert_eq!(id.fragment().as_deref(), Some("top"));
+    ///
+    /// // Create identifier from URL outside of context
+    /// let url = Url::parse("https://example.com/blog/index.md")?;
+    /// assert!(Id::from_url("file", "docs", &url).is_err());
+    ///
+    /// // Create identifier from URL without path after context
+    /// let url = Url::parse("https://example.com/docs/")?;
+    /// assert!(Id::from_url("file", "docs", &url).is_err());
     /// # Ok(())
     /// # }
     /// ```
-    fn from_str(value: &str) -> Result<Self> {
-        let format = Format::from_str(validate(value)?)?;
+    #[cfg(feature = "url")]
+    pub fn from_url(
+        scheme: &str, context: &str, url: &url::Url,
+    ) -> Result<Self> {
+        let segments = url.path_segments().into_iter().flatten();
+        let mut segments = segments.filter(|segment| !segment.is_empty());
 
-        // Ensure prefix is valid
-        if format.get(0) != "zri" {
-            Err(Error::Prefix)?;
+        // Ensure the path of the URL starts with the context, comparing the
+        // decoded segments, since the context might contain encoded characters
+        for expected in context.split('/') {
+            if expected.is_empty() || expected == "." {
+                continue;
+            }
+            let segment = segments.next().map(decode_url).transpose()?;
+            if segment.as_deref() != Some(expected) {
+                return Err(Error::Mismatch { name: "context" });
+            }
         }
 
-        // Ensure scheme is set
-        if format.get(1).is_empty() {
-            Err(Error::Component("scheme"))?;
+        // Decode the remaining segments, and join them to obtain the path
+        let segments = segments.map(decode_url).collect::<Result<Vec<_>>>()?;
+        if segments.is_empty() {
+            let name = Component::Path.name();
+            return Err(Error::Component { name, offset: None });
         }
 
-        // Ensure context is set
-        if format.get(3).is_empty() {
-            Err(Error::Component("context"))?;
+        // Create identifier from path and fragment, if any
+        let id = Self::new(scheme, context, segments.join("/"))?;
+        match url.fragment() {
+            Some(fragment) => {
+                id.with_fragment(decode_url(fragment)?.as_bytes())
+            }
+            None => Ok(id),
         }
-
-        // Ensure path is set
-        if format.get(4).is_empty() {
-            Err(Error::Component("path"))?;
-        }
-
-        // No errors occurred
-        Ok(Self { format })
     }
-}
-
-// ----------------------------------------------------------------------------
 
-impl fmt::Display for Id {
-    /// Formats the identifier for display.
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.format.fmt(f)
+    /// Creates an identifier from bytes.
+    ///
+    /// This is the equivalent of [`FromStr`] for bytes, which validates the
+    /// bytes as UTF-8 in the same pass, and applies the same checks.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Format`] if the bytes are not valid UTF-8,
+    /// and otherwise, the same errors as [`Id::from_str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::{format, Error, Id};
+    ///
+    /// // Create identifier from bytes
+    /// let id = Id::from_bytes(b"zri:file::docs:index.md:")?;
+    /// assert_eq!(id.path(), "index.md");
+    ///
+    /// // Create identifier from bytes that are not valid UTF-8
+    /// let res = Id::from_bytes(b"zri:file::docs:\xFF.md:");
+    /// assert!(matches!(
+    ///     res,
+    ///     Err(Error::Format(format::Error::Utf8 { offset: 15 }))
+    /// ));
+    ///
+    /// // Create identifier from bytes without scheme
+    /// let res = Id::from_bytes(b"zri:::docs:index.md:");
+    /// assert!(matches!(res, Err(Error::Component { name: "scheme", .. })));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_bytes(value: &[u8]) -> Result<Self> {
+        let format = Format::from_bytes_with(value, 5, |offset, byte| {
+            validate_byte(offset, byte).map_err(Error::from)
+        })?;
+        from_format(format)
     }
-}
 
-impl fmt::Debug for Id {
-    /// Formats the identifier for debugging.
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Id")
-            .field("scheme", &self.scheme())
-            .field("binding", &self.binding())
-            .field("context", &self.context())
-            .field("path", &self.path())
-            .field("fragment", &self.fragment())
+    /// Creates an identifier from a string, tolerating missing components.
+    ///
+    /// This is a lenient variant of [`Id::from_str`] for hand-written input,
+    /// e.g., in configuration files, which accepts between three and five `:`
+    /// separators, filling missing trailing components with empty values. The
+    /// `scheme`, `context` and `path` components are still required, so their
+    /// absence is reported as [`Error::Component`] instead of an opaque
+    /// cardinality error, where possible.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Id::from_str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier from string without trailing separator
+    /// let id = Id::parse_lenient("zri:file::docs:index.md")?;
+    /// assert_eq!(id.to_string(), "zri:file::docs:index.md:");
+    ///
+    /// // Create identifier from string without path
+    /// let err = Id::parse_lenient("zri:file::docs").unwrap_err();
+    /// assert_eq!(err.to_string(), "missing component: path at offset 15");
+    ///
+    /// // Create identifier from string without scheme
+    /// let err = Id::parse_lenient("zri:::docs:index.md").unwrap_err();
+    /// assert_eq!(err.to_string(), "missing component: scheme at offset 4");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_lenient(value: &str) -> Result<Self> {
+        let format = Format::from_str_with(value, 4, |offset, byte| {
+            validate_byte(offset, byte).map_err(Error::from)
+        })?;
+        from_format(format)
+    }
+
+    /// Creates an identifier from a string with the given validation policy.
+    ///
+    /// This is the variant of [`Id::from_str`] that applies the given
+    /// [`Validation`] policy to the string before parsing it. The policy only
+    /// applies to literal characters, so percent-encoded sequences like `%5C`
+    /// are left untouched, and decoded as usual.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Id::from_str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::path::Validation;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier from string with backslashes
+    /// let policy = Validation::NormalizeBackslashes;
+    /// let id = Id::parse_with(policy, r"zri:file::docs:guide\index.md:")?;
+    /// assert_eq!(id.path(), "guide/index.md");
+    ///
+    /// // Percent-encoded backslashes are never normalized
+    /// let id = Id::parse_with(policy, "zri:file::docs:a%5Cb.md:")?;
+    /// assert_eq!(id.path(), r"a\b.md");
+    /// assert_eq!(id, "zri:file::docs:a%5Cb.md:".parse::<Id>()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_with(policy: Validation, value: &str) -> Result<Self> {
+        policy.apply(value).parse()
+    }
+
+    /// Updates the `scheme` component.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and set scheme
+    /// let mut id = Id::new("file", "docs", "index.md")?;
+    /// id.set_scheme("git")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_scheme<S>(&mut self, scheme: S) -> Result<&mut Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.format
+            .set(1, validate(scheme)?)
+            .map_err(Into::into)
+            .map(|()| self)
+    }
+
+    /// Updates the `binding` component.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and set binding
+    /// let mut id = Id::new("git", "docs", "index.md")?;
+    /// id.set_binding("master")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_binding<S>(&mut self, binding: S) -> Result<&mut Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.format
+            .set(2, validate(binding)?)
+            .map_err(Into::into)
+            .map(|()| self)
+    }
+
+    /// Updates the `context` component.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and set context
+    /// let mut id = Id::new("file", "docs", "index.md")?;
+    /// id.set_context("examples")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_context<S>(&mut self, context: S) -> Result<&mut Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.format
+            .set(3, validate(context)?)
+            .map_err(Into::into)
+            .map(|()| self)
+    }
+
+    /// Updates the `context` component with a normalized value.
+    ///
+    /// The value is normalized exactly like in [`Id::new_normalized`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, is absolute or contains `..`, or [`Error::Format`], if the
+    /// format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and set normalized context
+    /// let mut id = Id::new("file", "docs", "index.md")?;
+    /// id.set_context_normalized("./docs//api/")?;
+    /// assert_eq!(id.context(), "docs/api");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_context_normalized<S>(&mut self, context: S) -> Result<&mut Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_context(canonicalize(context.as_ref())?)
+    }
+
+    /// Updates the `path` component.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and set path
+    /// let mut id = Id::new("file", "docs", "index.md")?;
+    /// id.set_path("README.md")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_path<S>(&mut self, path: S) -> Result<&mut Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.format
+            .set(4, validate(path)?)
+            .map_err(Into::into)
+            .map(|()| self)
+    }
+
+    /// Updates the `path` component with a normalized value.
+    ///
+    /// The value is normalized exactly like in [`Id::new_normalized`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, is absolute or contains `..`, or [`Error::Format`], if the
+    /// format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and set normalized path
+    /// let mut id = Id::new("file", "docs", "index.md")?;
+    /// id.set_path_normalized("./a//b/./c.md")?;
+    /// assert_eq!(id, Id::new("file", "docs", "a/b/c.md")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_path_normalized<S>(&mut self, path: S) -> Result<&mut Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_path(canonicalize(path.as_ref())?)
+    }
+
+    /// Updates the `path` component from a file system path.
+    ///
+    /// The path is normalized relative to the `context` component, exactly
+    /// like in [`Id::from_path`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Id::from_path`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::path::Path;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and set path from Windows-style path
+    /// let mut id = Id::new("file", "docs", "index.md")?;
+    /// id.set_path_from(Path::new(r"docs\guide\README.md"))?;
+    /// assert_eq!(id.path(), "guide/README.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_path_from(&mut self, path: &Path) -> Result<&mut Self> {
+        let path = normalize(to_str(path)?, &self.context())?;
+        if path.is_empty() {
+            let name = Component::Path.name();
+            return Err(Error::Component { name, offset: None });
+        }
+
+        // Update path from normalized path
+        self.set_path(path)
+    }
+
+    /// Appends a segment to the `path` component.
+    ///
+    /// Exactly one `/` is inserted between the existing path and the segment,
+    /// and all other components are left untouched. If the path is empty, the
+    /// segment becomes the path, and if the segment is empty or `.`, the path
+    /// is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the segment contains a
+    /// backslash or is absolute, or [`Error::Format`], if the format is
+    /// invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and append path segments
+    /// let mut id = Id::new("file", "docs", "section/")?;
+    /// id.push_path_segment("guide/")?;
+    /// id.push_path_segment(".")?;
+    /// id.push_path_segment("index.md")?;
+    /// assert_eq!(id.path(), "section/guide/index.md");
+    ///
+    /// // Absolute segments are rejected
+    /// assert!(id.push_path_segment("/index.md").is_err());
+    /// assert!(id.push_path_segment("a\\b").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn push_path_segment<S>(&mut self, segment: S) -> Result<&mut Self>
+    where
+        S: AsRef<str>,
+    {
+        let path = join(&self.path(), segment.as_ref())?;
+        self.set_path(path)
+    }
+
+    /// Returns a derived identifier with a segment appended to the `path`.
+    ///
+    /// This is the non-mutating variant of [`Id::push_path_segment`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Id::push_path_segment`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and derive child identifier
+    /// let id = Id::new("file", "docs", "section")?;
+    /// let child = id.join("index.md")?;
+    /// assert_eq!(child.to_string(), "zri:file::docs:section/index.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn join<S>(&self, segment: S) -> Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        let mut id = self.clone();
+        id.push_path_segment(segment)?;
+        Ok(id)
+    }
+
+    /// Returns the identifier of the parent, if any.
+    ///
+    /// The last segment of the `path` component is removed, and trailing
+    /// slashes are ignored. Since the `fragment` component refers to the
+    /// original resource, it is dropped. If the path consists of a single
+    /// segment, [`None`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain parent
+    /// let id =
+    ///     Id::new("file", "docs", "guide/index.md")?.with_fragment("top")?;
+    /// let parent = id.parent().unwrap();
+    /// assert_eq!(parent.to_string(), "zri:file::docs:guide:");
+    ///
+    /// // Trailing slashes are ignored
+    /// let id = Id::new("file", "docs", "guide/section/")?;
+    /// assert_eq!(id.parent().unwrap().path(), "guide");
+    ///
+    /// // Single-segment paths have no parent
+    /// assert_eq!(parent.parent(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        let path = self.path();
+        let parent = parent(&path)?;
+
+        // Derive identifier, retaining all components but the fragment
+        let mut id = self.clone();
+        id.format
+            .set_many([(4, parent.as_bytes()), (5, &b""[..])])
+            .ok()?;
+        Some(id)
+    }
+
+    /// Returns the final segment of the `path` component, if any.
+    ///
+    /// This mirrors [`Path::file_name`], so trailing slashes are ignored, and
+    /// [`None`] is returned if the path is empty or ends in `..`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain file name
+    /// let id = Id::new("file", "docs", "guide/index.md")?;
+    /// assert_eq!(id.file_name().as_deref(), Some("index.md"));
+    ///
+    /// // Trailing slashes are ignored
+    /// let id = Id::new("file", "docs", "guide/")?;
+    /// assert_eq!(id.file_name().as_deref(), Some("guide"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn file_name(&self) -> Option<Cow<'_, str>> {
+        project(self.path(), file_name)
+    }
+
+    /// Returns the final segment of the `path` component without extension.
+    ///
+    /// This mirrors [`Path::file_stem`], so only the last extension is removed,
+    /// and dotfiles like `.gitignore` are returned as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain file stem
+    /// let id = Id::new("file", "docs", "archive.tar.gz")?;
+    /// assert_eq!(id.file_stem().as_deref(), Some("archive.tar"));
+    ///
+    /// // Dotfiles have no extension
+    /// let id = Id::new("file", "docs", ".gitignore")?;
+    /// assert_eq!(id.file_stem().as_deref(), Some(".gitignore"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn file_stem(&self) -> Option<Cow<'_, str>> {
+        project(self.path(), |path| {
+            file_name(path).map(|name| split_extension(name).0)
+        })
+    }
+
+    /// Returns the extension of the `path` component, if any.
+    ///
+    /// This mirrors [`Path::extension`], so only the last extension of the
+    /// final segment is returned, and dotfiles like `.gitignore` don't have an
+    /// extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain extension
+    /// let id = Id::new("file", "docs", "archive.tar.gz")?;
+    /// assert_eq!(id.extension().as_deref(), Some("gz"));
+    ///
+    /// // Dotfiles have no extension
+    /// let id = Id::new("file", "docs", ".gitignore")?;
+    /// assert_eq!(id.extension(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn extension(&self) -> Option<Cow<'_, str>> {
+        project(self.path(), |path| {
+            file_name(path).and_then(|name| split_extension(name).1)
+        })
+    }
+
+    /// Updates the extension of the `path` component.
+    ///
+    /// This mirrors [`PathBuf::set_extension`][], so only the last extension
+    /// is replaced, or appended, if absent, and an empty extension removes it.
+    /// All other components are left untouched. If the path has no final
+    /// segment, e.g., if it's empty, the identifier is left unchanged.
+    ///
+    /// [`PathBuf::set_extension`]: std::path::PathBuf::set_extension
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the extension contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and update extension
+    /// let mut id = Id::new("file", "docs", "archive.tar.gz")?;
+    /// id.set_extension("xz")?;
+    /// assert_eq!(id.path(), "archive.tar.xz");
+    ///
+    /// // Update extension of dotfile
+    /// let mut id = Id::new("file", "docs", ".gitignore")?;
+    /// id.set_extension("bak")?;
+    /// assert_eq!(id.path(), ".gitignore.bak");
+    ///
+    /// // Remove extension
+    /// id.set_extension("")?;
+    /// assert_eq!(id.path(), ".gitignore");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_extension(&mut self, extension: &str) -> Result<&mut Self> {
+        let extension = validate(extension)?;
+        match replace_extension(&self.path(), extension) {
+            Some(path) => self.set_path(path),
+            None => Ok(self),
+        }
+    }
+
+    /// Returns a derived identifier with the extension of the `path` updated.
+    ///
+    /// This is the non-mutating variant of [`Id::set_extension`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Id::set_extension`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and derive output identifier
+    /// let id =
+    ///     Id::new("file", "docs", "guide/index.md")?.with_fragment("top")?;
+    /// let html = id.with_extension("html")?;
+    /// assert_eq!(html.to_string(), "zri:file::docs:guide/index.html:top");
+    ///
+    /// // Append extension, if absent
+    /// let id = Id::new("file", "docs", "README")?;
+    /// assert_eq!(id.with_extension("md")?.path(), "README.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_extension(&self, extension: &str) -> Result<Self> {
+        let mut id = self.clone();
+        id.set_extension(extension)?;
+        Ok(id)
+    }
+
+    /// Returns the relative path from the given base identifier.
+    ///
+    /// The base is treated as a file, so the path is resolved relative to the
+    /// parent of the base, which is what links between documents require,
+    /// e.g., from `guide/install.md` to `reference/api.md`, the relative path
+    /// is `../reference/api.md`. Use [`Id::relative_to_dir`] to treat the base
+    /// as a directory. Paths always use forward slashes.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Mismatch`], if the `scheme`, `binding` or
+    /// `context` components of both identifiers differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers and compute relative path
+    /// let id = Id::new("file", "docs", "reference/api.md")?;
+    /// let base = Id::new("file", "docs", "guide/install.md")?;
+    /// assert_eq!(id.relative_to(&base)?, "../reference/api.md");
+    ///
+    /// // Identifiers with different contexts are unrelated
+    /// let base = Id::new("file", "site", "guide/install.md")?;
+    /// assert!(id.relative_to(&base).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Relative paths are computed over all nesting depths:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Define paths, bases, and expected relative paths for bases that are
+    /// // treated as files and directories, respectively
+    /// let cases = [
+    ///     ("index.md", "index.md", "index.md", "."),
+    ///     ("index.md", "about.md", "index.md", "../index.md"),
+    ///     ("a/b.md", "index.md", "a/b.md", "../a/b.md"),
+    ///     ("index.md", "a/b.md", "../index.md", "../../index.md"),
+    ///     ("a/b.md", "a/c.md", "b.md", "../b.md"),
+    ///     ("a/b.md", "a/b.md", "b.md", "."),
+    ///     ("a/b/c.md", "a/d/e.md", "../b/c.md", "../../b/c.md"),
+    ///     ("a/b/c/d.md", "a/e.md", "b/c/d.md", "../b/c/d.md"),
+    ///     ("a/e.md", "a/b/c/d.md", "../../e.md", "../../../e.md"),
+    ///     ("x/y/z.md", "a/b/c.md", "../../x/y/z.md", "../../../x/y/z.md"),
+    ///     ("a/b", "a", "a/b", "b"),
+    ///     ("a", "a/b", ".", ".."),
+    ///     ("a", "a", "a", "."),
+    ///     ("a/b/", "a/./c", "b", "../b"),
+    /// ];
+    ///
+    /// // Ensure that each relative path matches
+    /// for (path, base, file, dir) in cases {
+    ///     let id = Id::new("file", "docs", path)?;
+    ///     let base = Id::new("file", "docs", base)?;
+    ///     assert_eq!(id.relative_to(&base)?, file, "{path} from {base}");
+    ///     assert_eq!(id.relative_to_dir(&base)?, dir, "{path} from {base}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn relative_to(&self, base: &Id) -> Result<String> {
+        self.check_related(base)
+            .map(|()| relative(&self.path(), &base.path(), false))
+    }
+
+    /// Returns the relative path from the given base directory identifier.
+    ///
+    /// This is the variant of [`Id::relative_to`], which treats the `path`
+    /// component of the base as a directory.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Id::relative_to`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers and compute relative path
+    /// let id = Id::new("file", "docs", "reference/api.md")?;
+    /// let base = Id::new("file", "docs", "guide")?;
+    /// assert_eq!(id.relative_to_dir(&base)?, "../reference/api.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn relative_to_dir(&self, base: &Id) -> Result<String> {
+        self.check_related(base)
+            .map(|()| relative(&self.path(), &base.path(), true))
+    }
+
+    /// Returns the relative URL path.
+    ///
+    /// Other than the conversion into a [`PathBuf`][], which is platform
+    /// specific, this joins the `context` and `path` components with `/`, and
+    /// percent-encodes each segment as mandated by RFC 3986, including spaces,
+    /// `#`, `?`, `%`, `:` and non-ASCII characters. The `fragment` component
+    /// is percent-encoded and appended after a `#`, if present. Empty and `.`
+    /// segments are dropped. Use [`Id::to_url_path_without_context`] if the
+    /// context is mounted as a URL prefix.
+    ///
+    /// [`PathBuf`]: std::path::PathBuf
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the `context` or `path` is
+    /// absolute or contains `..`, using the same rules as the conversion into
+    /// a [`PathBuf`][].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain relative URL path
+    /// let id = Id::new("file", "docs", "guide/index.md")?;
+    /// assert_eq!(id.to_url_path()?, "docs/guide/index.md");
+    ///
+    /// // Create identifier with spaces, unicode, and `#` in file names
+    /// let id = Id::new("file", "docs", "my guide/über #1.md")?
+    ///     .with_fragment("section 2")?;
+    /// assert_eq!(
+    ///     id.to_url_path()?,
+    ///     "docs/my%20guide/%C3%BCber%20%231.md#section%202",
+    /// );
+    ///
+    /// // Create identifier with `:` in the first segment
+    /// let id = Id::new("file", "", "a:b.md")?;
+    /// assert_eq!(id.to_url_path()?, "a%3Ab.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Absolute paths and paths containing `..` are rejected:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::{path, Error, Id};
+    ///
+    /// // Create identifiers with invalid paths
+    /// let id = Id::new("file", "docs", "../index.md")?;
+    /// let res = id.to_url_path();
+    /// assert!(matches!(res, Err(Error::Path(path::Error::ParentDir))));
+    /// let id = Id::new("file", "/docs", "index.md")?;
+    /// let res = id.to_url_path();
+    /// assert!(matches!(res, Err(Error::Path(path::Error::RootDir))));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_url_path(&self) -> Result<String> {
+        let fragment = self.fragment();
+        to_url(Some(&self.context()), &self.path(), fragment.as_deref())
+            .map_err(Into::into)
+    }
+
+    /// Returns the relative URL path without the `context` component.
+    ///
+    /// This is the variant of [`Id::to_url_path`] for servers that mount the
+    /// context as a URL prefix, and otherwise behaves exactly the same.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Id::to_url_path`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain relative URL path
+    /// let id = Id::new("file", "docs", "my guide/index.md")?;
+    /// assert_eq!(id.to_url_path_without_context()?, "my%20guide/index.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_url_path_without_context(&self) -> Result<String> {
+        let fragment = self.fragment();
+        to_url(None, &self.path(), fragment.as_deref()).map_err(Into::into)
+    }
+
+    /// Checks whether the `path` component starts with the given prefix.
+    ///
+    /// Other than comparing strings, whole segments are compared, so `docs/tut`
+    /// is not a prefix of `docs/tutorials/index.md`. Empty and `.` segments are
+    /// ignored, so trailing slashes don't matter, and every path starts with
+    /// itself. No allocations are necessary, unless the path is encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and check path prefixes
+    /// let id = Id::new("file", "docs", "docs/tutorials/index.md")?;
+    /// assert!(id.path_starts_with("docs/tutorials"));
+    /// assert!(id.path_starts_with("docs/tutorials/"));
+    /// assert!(id.path_starts_with("docs/tutorials/index.md"));
+    /// assert!(id.path_starts_with(""));
+    ///
+    /// // Partial segments are no prefixes
+    /// assert!(!id.path_starts_with("docs/tut"));
+    /// assert!(!id.path_starts_with("docs/tutorials/index"));
+    /// assert!(!id.path_starts_with("docs/tutorials/index.md/a"));
+    ///
+    /// // Encoded paths are compared by their decoded segments
+    /// let id = Id::new("file", "docs", "a:b/c.md")?;
+    /// assert!(id.path_starts_with("a:b"));
+    /// assert!(!id.path_starts_with("a:"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn path_starts_with(&self, prefix: &str) -> bool {
+        starts_with(&self.path(), prefix)
+    }
+
+    /// Checks whether the identifier is located within the given identifier.
+    ///
+    /// This requires equal `scheme`, `binding` and `context` components, and
+    /// the `path` component of the given identifier to be a prefix of the
+    /// `path` component of this identifier, see [`Id::path_starts_with`]. The
+    /// `fragment` component is ignored, and every identifier is within itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers and check containment
+    /// let id = Id::new("file", "docs", "tutorials/index.md")?;
+    /// assert!(id.is_within(&Id::new("file", "docs", "tutorials")?));
+    /// assert!(!id.is_within(&Id::new("file", "docs", "tut")?));
+    /// assert!(!id.is_within(&Id::new("file", "site", "tutorials")?));
+    /// assert!(!id.is_within(&Id::new("git", "docs", "tutorials")?));
+    ///
+    /// // Identifiers with encoded components are compared by their decoded
+    /// // path segments, and their raw other components
+    /// let id = Id::new("file", "a:b", "c:d/index.md")?;
+    /// assert!(id.is_within(&Id::new("file", "a:b", "c:d")?));
+    /// assert!(!id.is_within(&Id::new("file", "a:b", "c")?));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_within(&self, other: &Id) -> bool {
+        self.check_related(other).is_ok()
+            && starts_with(&self.path(), &other.path())
+    }
+
+    /// Validates the identifier against the given scheme registry.
+    ///
+    /// This is a convenience for [`SchemeRegistry::validate`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`SchemeRegistry::validate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::SchemeRegistry;
+    ///
+    /// // Create scheme registry
+    /// let mut builder = SchemeRegistry::builder();
+    /// builder.add("file")?;
+    /// let registry = builder.build();
+    ///
+    /// // Validate identifiers against the registry
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert!(id.validate_against(&registry).is_ok());
+    /// let id = Id::new("gti", "docs", "index.md")?;
+    /// assert!(id.validate_against(&registry).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn validate_against(&self, registry: &SchemeRegistry) -> Result {
+        registry.validate(self)
+    }
+
+    /// Ensures that both identifiers share `scheme`, `binding` and `context`.
+    fn check_related(&self, other: &Id) -> Result {
+        for (component, index) in [
+            (Component::Scheme, 1),
+            (Component::Binding, 2),
+            (Component::Context, 3),
+        ] {
+            if self.format.raw(index) != other.format.raw(index) {
+                let name = component.name();
+                return Err(Error::Mismatch { name });
+            }
+        }
+
+        // No errors occurred
+        Ok(())
+    }
+
+    /// Updates the `fragment` component.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and set fragment
+    /// let mut id = Id::new("file", "docs", "index.md")?;
+    /// id.set_fragment("anchor")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_fragment<S>(&mut self, fragment: S) -> Result<&mut Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.format
+            .set(5, validate(fragment)?)
+            .map_err(Into::into)
+            .map(|()| self)
+    }
+
+    /// Updates the `fragment` component with a source location.
+    ///
+    /// The location is written in the canonical encoding, which is `L{line}`
+    /// or `L{line}C{column}`, and avoids `:`, so the fragment doesn't need to
+    /// be percent-encoded. See [`fragment::format_location`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and set fragment location
+    /// let mut id = Id::new("file", "docs", "index.md")?;
+    /// id.set_fragment_location(10, Some(5))?;
+    /// assert_eq!(id.to_string(), "zri:file::docs:index.md:L10C5");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_fragment_location(
+        &mut self, line: u32, column: Option<u32>,
+    ) -> Result<&mut Self> {
+        self.set_fragment(fragment::format_location(line, column))
+    }
+
+    /// Returns the identifier with the `scheme` component updated.
+    ///
+    /// This is the consuming variant of [`Id::set_scheme`], which allows to
+    /// derive identifiers in expression position.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier with scheme
+    /// let id = Id::new("file", "docs", "index.md")?.with_scheme("git")?;
+    /// assert_eq!(id.to_string(), "zri:git::docs:index.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_scheme<S>(mut self, scheme: S) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_scheme(scheme)?;
+        Ok(self)
+    }
+
+    /// Returns the identifier with the `binding` component updated.
+    ///
+    /// This is the consuming variant of [`Id::set_binding`], which allows to
+    /// derive identifiers in expression position.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier with binding
+    /// let id = Id::new("file", "docs", "index.md")?.with_binding("master")?;
+    /// assert_eq!(id.to_string(), "zri:file:master:docs:index.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_binding<S>(mut self, binding: S) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_binding(binding)?;
+        Ok(self)
+    }
+
+    /// Returns the identifier with the `context` component updated.
+    ///
+    /// This is the consuming variant of [`Id::set_context`], which allows to
+    /// derive identifiers in expression position.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier with context
+    /// let id = Id::new("file", "docs", "index.md")?.with_context("site")?;
+    /// assert_eq!(id.to_string(), "zri:file::site:index.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_context<S>(mut self, context: S) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_context(context)?;
+        Ok(self)
+    }
+
+    /// Returns the identifier with the `path` component updated.
+    ///
+    /// This is the consuming variant of [`Id::set_path`], which allows to
+    /// derive identifiers in expression position.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier with path
+    /// let id = Id::new("file", "docs", "index.md")?.with_path("about.md")?;
+    /// assert_eq!(id.to_string(), "zri:file::docs:about.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_path<S>(mut self, path: S) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_path(path)?;
+        Ok(self)
+    }
+
+    /// Returns the identifier with the `fragment` component updated.
+    ///
+    /// This is the consuming variant of [`Id::set_fragment`], which allows to
+    /// derive identifiers in expression position.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier with fragment
+    /// let id = Id::new("file", "docs", "index.md")?.with_fragment("anchor")?;
+    /// assert_eq!(id.to_string(), "zri:file::docs:index.md:anchor");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_fragment<S>(mut self, fragment: S) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_fragment(fragment)?;
+        Ok(self)
+    }
+
+    /// Copies the given components from another identifier.
+    ///
+    /// Components are copied as is, without decoding and re-encoding them,
+    /// which is faster than obtaining and setting them one after another.
+    ///
+    /// # Errors
+    ///
+    /// If the identifier would overflow, [`Error::Format`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers and copy context and path
+    /// let mut id = Id::new("file", "docs", "index.md")?;
+    /// let other = Id::new("git", "site", "a:b.md")?;
+    /// id.copy_components(&other, Component::Context | Component::Path)?;
+    /// assert_eq!(id.to_string(), "zri:file::site:a%3Ab.md:");
+    /// assert_eq!(id.path(), "a:b.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_components(
+        &mut self, other: &Id, components: ComponentSet,
+    ) -> Result<&mut Self> {
+        let mut indexes = [0; 5];
+        let mut len = 0;
+        for component in components.iter() {
+            indexes[len] = component.index();
+            len += 1;
+        }
+        self.format.copy_from(&other.format, &indexes[..len])?;
+        Ok(self)
+    }
+
+    /// Returns the components that differ from another identifier.
+    ///
+    /// Components are compared on their decoded values, so identifiers that
+    /// only differ in the percent-encoding of the same value are considered
+    /// equal, even though their string representations differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers and compute differing components
+    /// let a = Id::new("file", "docs", "index.md")?;
+    /// let b = Id::new("file", "site", "index.md")?;
+    /// assert_eq!(a.diff(&b), Component::Context.into());
+    ///
+    /// // Percent-encoding of the same value is not a difference
+    /// let a: Id = "zri:file::docs:%61.md:".parse()?;
+    /// let b: Id = "zri:file::docs:a.md:".parse()?;
+    /// assert_ne!(a, b);
+    /// assert!(a.diff(&b).is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Id) -> ComponentSet {
+        let mut components = ComponentSet::new();
+        for component in Component::ALL {
+            let index = component.index();
+            if self.format.get(index) != other.format.get(index) {
+                components.insert(component);
+            }
+        }
+        components
+    }
+
+    /// Returns a human-readable explanation of the differing components.
+    ///
+    /// Only components returned by [`Id::diff`] are rendered, one per line,
+    /// with the values of both identifiers in decoded form. If there are no
+    /// differences, the returned string is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers and explain differing components
+    /// let a = Id::new("file", "docs", "index.md")?;
+    /// let b = Id::new("file", "site", "a:b.md")?;
+    /// assert_eq!(a.explain_diff(&b), concat!(
+    ///     "context: \"docs\" != \"site\"\n",
+    ///     "path: \"index.md\" != \"a:b.md\"\n",
+    /// ));
+    /// assert_eq!(a.explain_diff(&a), "");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn explain_diff(&self, other: &Id) -> String {
+        let mut explanation = String::new();
+        for component in self.diff(other).iter() {
+            let index = component.index();
+            let _ = writeln!(
+                explanation,
+                "{component}: {:?} != {:?}",
+                self.format.get(index),
+                other.format.get(index),
+            );
+        }
+        explanation
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl Id {
+    /// Format version.
+    ///
+    /// Identifiers are always emitted with the unversioned `zri` prefix, but
+    /// the versioned `zri1` prefix is accepted as an alias when parsing, see
+    /// [`migrate`] for parsing identifiers from other versions.
+    pub const FORMAT_VERSION: u32 = version::FORMAT_VERSION;
+
+    /// Returns the `scheme` component.
+    #[inline]
+    pub fn scheme(&self) -> Cow<'_, str> {
+        self.format.get(1)
+    }
+
+    /// Returns the `binding` component, if any.
+    #[inline]
+    pub fn binding(&self) -> Option<Cow<'_, str>> {
+        (!self.format.is_component_empty(2)).then(|| self.format.get(2))
+    }
+
+    /// Returns the `context` component.
+    #[inline]
+    pub fn context(&self) -> Cow<'_, str> {
+        self.format.get(3)
+    }
+
+    /// Returns the `path` component.
+    #[inline]
+    pub fn path(&self) -> Cow<'_, str> {
+        self.format.get(4)
+    }
+
+    /// Returns the `fragment` component, if any.
+    #[inline]
+    pub fn fragment(&self) -> Option<Cow<'_, str>> {
+        (!self.format.is_component_empty(5)).then(|| self.format.get(5))
+    }
+
+    /// Returns the `fragment` component as a source location, if any.
+    ///
+    /// Besides the canonical encoding, legacy forms like `10:5` are accepted,
+    /// see [`fragment::parse_location`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain fragment location
+    /// let id: Id = "zri:file::docs:index.md:L10C5".parse()?;
+    /// assert_eq!(id.fragment_as_location(), Some((10, Some(5))));
+    ///
+    /// // Create identifier with legacy fragment location
+    /// let id: Id = "zri:file::docs:index.md:10%3A5".parse()?;
+    /// assert_eq!(id.fragment_as_location(), Some((10, Some(5))));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn fragment_as_location(&self) -> Option<(u32, Option<u32>)> {
+        self.fragment()
+            .and_then(|fragment| fragment::parse_location(&fragment))
+    }
+
+    /// Returns an iterator over the segments of the `path` component.
+    ///
+    /// Segments are decoded, and empty segments, e.g., caused by duplicate or
+    /// trailing slashes, are skipped. If the `path` component isn't encoded,
+    /// segments are borrowed from the identifier, and otherwise, the path is
+    /// decoded once, and segments are yielded as owned values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain path segments
+    /// let id = Id::new("file", "docs", "guide//index.md/")?;
+    /// let segments: Vec<_> = id.path_segments().collect();
+    /// assert_eq!(segments, ["guide", "index.md"]);
+    ///
+    /// // Create identifier with single segment
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// let segments: Vec<_> = id.path_segments().collect();
+    /// assert_eq!(segments, ["index.md"]);
+    ///
+    /// // Create identifier with encoded segment
+    /// let id = Id::new("file", "docs", "guide/a:b.md")?;
+    /// let segments: Vec<_> = id.path_segments().collect();
+    /// assert_eq!(segments, ["guide", "a:b.md"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn path_segments(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        split_segments(self.path())
+    }
+
+    /// Returns the number of segments of the `path` component.
+    ///
+    /// This counts the segments yielded by [`Id::path_segments`], so empty
+    /// segments are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain number of path segments
+    /// let id = Id::new("file", "docs", "guide//index.md/")?;
+    /// assert_eq!(id.path_segment_count(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn path_segment_count(&self) -> usize {
+        self.path_segments().count()
+    }
+
+    /// Returns the string representation.
+    ///
+    /// This is the canonical representation, which is also used by [`Display`].
+    ///
+    /// [`Display`]: fmt::Display
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.format.as_str()
+    }
+
+    /// Returns the byte representation.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.format.as_bytes()
+    }
+
+    /// Writes the string representation to the given writer.
+    ///
+    /// See [`Format::write_to`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the writer are propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and write it
+    /// let id = Id::new("file", "docs", "a:b.md")?;
+    /// let mut buffer = Vec::new();
+    /// id.write_to(&mut buffer)?;
+    /// assert_eq!(buffer, id.to_string().as_bytes());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn write_to<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.format.write_to(writer)
+    }
+
+    /// Writes the string representation to the given formatter.
+    ///
+    /// See [`Format::fmt_to`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the writer are propagated.
+    #[inline]
+    pub fn fmt_to<W>(&self, writer: &mut W) -> fmt::Result
+    where
+        W: fmt::Write,
+    {
+        self.format.fmt_to(writer)
+    }
+
+    /// Writes the decoded components to the given writer.
+    ///
+    /// See [`Format::write_decoded_to`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the writer are propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and write decoded components
+    /// let id = Id::new("file", "docs", "a:b.md")?;
+    /// let mut buffer = Vec::new();
+    /// id.write_decoded_to(&mut buffer)?;
+    /// assert_eq!(buffer, b"zri:file::docs:a\\:b.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn write_decoded_to<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.format.write_decoded_to(writer)
+    }
+
+    /// Converts the identifier into its string representation.
+    ///
+    /// This avoids a copy if the formatted string is stored on the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and convert into string
+    /// let id = Id::new("file", "docs", "a:b.md")?;
+    /// let value = id.clone().into_string();
+    /// assert_eq!(value, id.as_str());
+    /// assert_eq!(value.parse::<Id>()?, id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_string(self) -> String {
+        let bytes = Container::into_vec(self.format.into_inner());
+
+        // SAFETY: The formatted string is guaranteed to be valid UTF-8, as it
+        // was created from valid UTF-8 strings, or encoded values
+        unsafe { String::from_utf8_unchecked(bytes) }
+    }
+
+    /// Returns the byte range of the given component.
+    ///
+    /// The range refers to the string representation of the identifier, and
+    /// includes percent-encoded characters as is. It remains valid until the
+    /// next mutation of the identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain byte range of path
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// let range = id.component_span(Component::Path);
+    /// assert_eq!(&id.to_string()[range], "index.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn component_span(&self, component: Component) -> Range<usize> {
+        self.format.span(component.index())
+    }
+
+    /// Ensures that all components are valid UTF-8 after decoding.
+    ///
+    /// Components are obtained with lossy decoding, which replaces invalid
+    /// UTF-8 hidden behind percent-encoding with the Unicode replacement
+    /// character, so two distinct identifiers might yield the same components.
+    /// Consumers that treat identifiers as authoritative keys should call this
+    /// method after parsing. Only percent-encoded components are checked, as
+    /// all other components are valid by construction.
+    ///
+    /// # Errors
+    ///
+    /// If a component isn't valid UTF-8 after decoding, [`Error::Format`] is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers and ensure components are valid UTF-8
+    /// let id: Id = "zri:file::docs:a%3Ab.md:".parse()?;
+    /// assert!(id.validate_utf8().is_ok());
+    /// let id: Id = "zri:file::docs:a%FF.md:".parse()?;
+    /// assert!(id.validate_utf8().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate_utf8(&self) -> Result {
+        for component in Component::ALL {
+            self.format.get_strict(component.index())?;
+        }
+        Ok(())
+    }
+
+    /// Returns whether the given component is percent-encoded.
+    ///
+    /// Components are percent-encoded if they contain characters that can't
+    /// be represented as is, most notably `:`, and must be decoded when they
+    /// are obtained, which means that they can't be borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and check if components are encoded
+    /// let id = Id::new("file", "docs", "a:b.md")?;
+    /// assert!(!id.is_encoded(Component::Context));
+    /// assert!(id.is_encoded(Component::Path));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_encoded(&self, component: Component) -> bool {
+        self.format.is_encoded(component.index())
+    }
+
+    /// Orders two identifiers by their decoded components.
+    ///
+    /// Components are compared in order of appearance, i.e., `scheme`,
+    /// `binding`, `context`, `path` and `fragment`, after decoding, where an
+    /// absent binding or fragment orders before any present value. This is
+    /// the ordering to use for listings and stable output. The [`Ord`]
+    /// implementation compares the canonical strings, which is faster, but
+    /// lets separators and percent-encoded characters influence the ordering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::cmp::Ordering;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create and compare identifiers with encoded paths
+    /// let a = Id::new("file", "docs", "a:b.md")?;
+    /// let b = Id::new("file", "docs", "a-b.md")?;
+    /// assert_eq!(a.cmp(&b), Ordering::Less);
+    /// assert_eq!(a.cmp_components(&b), Ordering::Greater);
+    ///
+    /// // Create and compare identifiers with nested paths
+    /// let a = Id::new("file", "docs", "a/b.md")?;
+    /// let b = Id::new("file", "docs", "a")?;
+    /// assert_eq!(a.cmp(&b), Ordering::Less);
+    /// assert_eq!(a.cmp_components(&b), Ordering::Greater);
+    ///
+    /// // Sort identifiers for listing
+    /// let mut ids = vec![a.clone(), b.clone()];
+    /// ids.sort_by(Id::cmp_components);
+    /// assert_eq!(ids, [b, a]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn cmp_components(&self, other: &Self) -> Ordering {
+        self.format.cmp_decoded(&other.format)
+    }
+
+    /// Checks if the identifier matches a selector.
+    ///
+    /// This is a convenience for [`Selector::is_match`], see its documentation
+    /// for details on when to prefer a [`Matcher`][] instead.
+    ///
+    /// [`Matcher`]: crate::Matcher
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Selector::is_match`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and check if it matches a selector
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert!(id.matches(&"zrs:::docs:**/*.md:".parse()?)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "globset")]
+    #[inline]
+    pub fn matches(&self, selector: &Selector) -> matcher::Result<bool> {
+        selector.is_match(self)
+    }
+
+    /// Returns a selector that matches the identifier literally.
+    ///
+    /// This is a convenience for [`Selector::literal`], see its documentation
+    /// for details on escaping, and how absent components are matched.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Selector::literal`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain selector
+    /// let id = Id::new("file", "docs", "*.md")?;
+    /// let selector = id.to_selector()?;
+    /// assert_eq!(selector.to_string(), "zrs:file:-:docs:[*].md:-");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The selector doesn't match identifiers with a fragment:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # #[cfg(feature = "globset")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain selector
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// let selector = id.to_selector()?;
+    ///
+    /// // Ensure the identifier with a fragment is not matched
+    /// assert!(id.matches(&selector)?);
+    /// assert!(!id.with_fragment("top")?.matches(&selector)?);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "globset"))]
+    /// # fn main() {}
+    /// ```
+    #[inline]
+    pub fn to_selector(&self) -> matcher::Result<Selector> {
+        Selector::literal(self)
+    }
+
+    /// Returns the stable 64-bit hash of the identifier.
+    ///
+    /// This hash is computed with FNV-1a over the canonical string, and other
+    /// than [`Hash`], it is stable across processes, platforms and versions,
+    /// which makes it suitable for distributed cache keys. The algorithm is
+    /// guaranteed not to change without a major version bump.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and compute stable hash
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(id.stable_hash(), 0xd99c_968d_8294_4616);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn stable_hash(&self) -> u64 {
+        self.format.stable_hash()
+    }
+
+    /// Returns the stable 128-bit hash of the identifier.
+    ///
+    /// This is the collision-averse variant of [`Id::stable_hash`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and compute stable hash
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(
+    ///     id.stable_hash128(),
+    ///     0xd270_c3c4_21b6_909b_7dcb_fd52_c695_049e
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn stable_hash128(&self) -> u128 {
+        self.format.stable_hash128()
+    }
+
+    /// Returns the fingerprint of the identifier.
+    ///
+    /// Fingerprints are short, stable hashes for compact on-disk indexes and
+    /// for correlating identifiers in logs. They are computed with FNV-1a over
+    /// the canonical string with the standard 64-bit offset basis as the seed,
+    /// and are identical to [`Id::stable_hash`]. Changing the value of any
+    /// fingerprint is considered a breaking change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Define identifiers and expected fingerprints
+    /// let cases = [
+    ///     ("zri:file::docs:index.md:", 0xd99c_968d_8294_4616),
+    ///     ("zri:file::docs:a%3Ab.md:", 0x2b63_ac36_5e6c_fe82),
+    ///     ("zri:git::docs:guide/install.md:", 0x98ed_fa7d_c09b_03dc),
+    ///     ("zri:file:master:docs:index.md:top", 0x5036_faa2_1aea_94b3),
+    /// ];
+    ///
+    /// // Ensure that each fingerprint matches
+    /// for (value, fingerprint) in cases {
+    ///     let id: Id = value.parse()?;
+    ///     assert_eq!(id.fingerprint(), fingerprint, "{value}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn fingerprint(&self) -> u64 {
+        self.format.fingerprint()
+    }
+
+    /// Returns the OS string representation of the identifier.
+    ///
+    /// This conversion is infallible, since the canonical representation of an
+    /// identifier is always valid Unicode.
+    #[inline]
+    pub fn to_os_string(&self) -> OsString {
+        OsString::from(self.format.as_str())
+    }
+
+    /// Returns the URL relative to the given base URL.
+    ///
+    /// The `context` and `path` components are joined with `/`, and each of
+    /// their segments is percent-encoded as mandated by RFC 3986, including
+    /// spaces, `#`, `?`, `%`, `:` and non-ASCII characters. The result is then
+    /// resolved against the base URL, and the percent-encoded `fragment`
+    /// becomes the URL fragment. Note that the base URL must end with a `/`
+    /// for its last segment to be kept, as mandated by RFC 3986. Use
+    /// [`Id::from_url`] to convert it back.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the `context` or `path` is
+    /// absolute or contains `..`, and [`Error::Url`], if the URL can't be
+    /// resolved against the base URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use url::Url;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain URL
+    /// let base = Url::parse("https://example.com/")?;
+    /// let id = Id::new("file", "docs", "my guide/über?.md")?
+    ///     .with_fragment("#top")?;
+    /// assert_eq!(
+    ///     id.to_url(&base)?.as_str(),
+    ///     "https://example.com/docs/my%20guide/%C3%BCber%3F.md#%23top"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Identifiers round-trip through URLs, including unicode and anchors:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use url::Url;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers with special characters in paths and fragments
+    /// let base = Url::parse("https://example.com/")?;
+    /// for (context, path, fragment) in [
+    ///     ("docs", "index.md", None),
+    ///     ("docs", "guide/getting started.md", Some("step 1")),
+    ///     ("docs", "日本語/ページ.md", Some("見出し")),
+    ///     ("api docs", "a#b?c%d.md", Some("x#y?z")),
+    ///     ("docs", "über/naïve [draft] {1}.md", Some("é")),
+    /// ] {
+    ///     let mut id = Id::new("file", context, path)?;
+    ///     if let Some(fragment) = fragment {
+    ///         id.set_fragment(fragment)?;
+    ///     }
+    ///
+    ///     // Ensure identifier is converted into a URL and back
+    ///     let url = id.to_url(&base)?;
+    ///     assert_eq!(url.fragment().is_some(), fragment.is_some(), "{url}");
+    ///     assert_eq!(Id::from_url("file", context, &url)?, id, "{url}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn to_url(&self, base: &url::Url) -> Result<url::Url> {
+        let fragment = self.fragment();
+        let value = path::to_url(
+            Some(&self.context()),
+            &self.path(),
+            fragment.as_deref(),
+        )?;
+        base.join(&value).map_err(Into::into)
+    }
+
+    /// Returns the compact string representation of the identifier.
+    ///
+    /// Other than the canonical representation returned by [`Display`], the
+    /// compact representation omits the trailing separator if the `fragment`
+    /// is empty. Since the `binding` is always followed by the `context`, an
+    /// empty binding is rendered as a collapsed `::`. The compact form can be
+    /// parsed back into an identifier with [`FromStr`].
+    ///
+    /// [`Display`]: fmt::Display
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain compact string
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(id.to_compact_string(), "zri:file::docs:index.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Compact and canonical forms round-trip for all optional components:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Define canonical and compact forms
+    /// let cases = [
+    ///     ("zri:file::docs:index.md:", "zri:file::docs:index.md"),
+    ///     ("zri:file:git:docs:index.md:", "zri:file:git:docs:index.md"),
+    ///     ("zri:file::docs:index.md:a", "zri:file::docs:index.md:a"),
+    ///     ("zri:file:git:docs:index.md:a", "zri:file:git:docs:index.md:a"),
+    ///     ("zri:file::docs:c%3Ad.md:", "zri:file::docs:c%3Ad.md"),
+    /// ];
+    ///
+    /// // Ensure both forms parse to the same identifier
+    /// for (canonical, compact) in cases {
+    ///     let id: Id = canonical.parse()?;
+    ///     assert_eq!(id.to_compact_string(), compact);
+    ///     assert_eq!(compact.parse::<Id>()?, id);
+    ///     assert_eq!(compact.parse::<Id>()?.to_string(), canonical);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_compact_string(&self) -> String {
+        let value = self.format.as_str();
+        if self.format.is_component_empty(5) {
+            value[..value.len() - 1].to_string()
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl ToId for &Id {
+    /// Creates an identifier from a reference.
+    #[inline]
+    fn to_id(&self) -> Result<Cow<'_, Id>> {
+        Ok(Cow::Borrowed(self))
+    }
+}
+
+impl ToId for &str {
+    /// Creates an identifier from a string.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if a component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier from string
+    /// let id = "zri:file::docs:index.md:".to_id()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn to_id(&self) -> Result<Cow<'_, Id>> {
+        self.parse().map(Cow::Owned)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl FromStr for Id {
+    type Err = Error;
+
+    /// Attempts to create an identifier from a string.
+    ///
+    /// The string must adhere to the following format and include exactly five
+    /// `:` separators, even if some components are omitted. Only the `binding`
+    /// and `fragment` components are optional and can be left empty, all other
+    /// components must be present. The trailing separator may be omitted if the
+    /// `fragment` is empty, as returned by [`Id::to_compact_string`], and the
+    /// versioned `zri1` prefix is accepted as an alias for `zri`:
+    ///
+    /// ``` text
+    /// zri:<scheme>:<binding>:<context>:<path>:<fragment>
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if a component value contains a
+    /// backslash, [`Error::Version`], if the format version is unsupported, or
+    /// [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier from string
+    /// let id: Id = "zri:file::docs:index.md:".parse()?;
+    ///
+    /// // Create identifier from string with versioned prefix
+    /// let versioned: Id = "zri1:file::docs:index.md:".parse()?;
+    /// assert_eq!(versioned, id);
+    /// assert_eq!(versioned.to_string(), "zri:file::docs:index.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Malformed identifiers are rejected with the following errors:
+    ///
+    /// ```
+    /// use zrx_id::{format, path, Error, Id};
+    ///
+    /// // Define malformed identifiers and expected errors
+    /// let long = format!("zri:file::docs:{}:", "a".repeat(1 << 16));
+    /// let cases: [(&str, fn(&Error) -> bool); 18] = [
+    ///     ("zrx:file::docs:index.md:", |e| matches!(e, Error::Prefix)),
+    ///     ("zri9:file::docs:index.md:", |e| matches!(e, Error::Version(9))),
+    ///     ("zri01:file::docs:index.md:", |e| matches!(e, Error::Prefix)),
+    ///     ("zri1a:file::docs:index.md:", |e| matches!(e, Error::Prefix)),
+    ///     (":file::docs:index.md:", |e| matches!(e, Error::Prefix)),
+    ///     ("zri:::docs:index.md:", |e| {
+    ///         matches!(e, Error::Component { name: "scheme", .. })
+    ///     }),
+    ///     ("zri:file:::index.md:", |e| {
+    ///         matches!(e, Error::Component { name: "context", .. })
+    ///     }),
+    ///     ("zri:file::docs::", |e| {
+    ///         matches!(e, Error::Component { name: "path", .. })
+    ///     }),
+    ///     ("zri:file::docs", |e| {
+    ///         matches!(e, Error::Format(format::Error::Cardinality { .. }))
+    ///     }),
+    ///     ("zri:file::docs:index.md::", |e| {
+    ///         matches!(e, Error::Format(format::Error::Cardinality { .. }))
+    ///     }),
+    ///     ("zri:file::docs:index.md:a:b\\c", |e| {
+    ///         matches!(e, Error::Path(path::Error::Backslash))
+    ///     }),
+    ///     ("zri:file::docs:a\\b.md:", |e| {
+    ///         matches!(e, Error::Path(path::Error::Backslash))
+    ///     }),
+    ///     ("zrx:file::\\docs", |e| {
+    ///         matches!(e, Error::Path(path::Error::Backslash))
+    ///     }),
+    ///     ("zri:file::docs:in\0dex.md:", |e| {
+    ///         matches!(e, Error::Path(path::Error::Nul { offset: 17 }))
+    ///     }),
+    ///     ("zri:file::docs:index.md:\0", |e| {
+    ///         matches!(e, Error::Path(path::Error::Nul { offset: 24 }))
+    ///     }),
+    ///     ("zri:file::docs:index.md::\0", |e| {
+    ///         matches!(e, Error::Path(path::Error::Nul { offset: 25 }))
+    ///     }),
+    ///     ("", |e| {
+    ///         matches!(e, Error::Format(format::Error::Cardinality { .. }))
+    ///     }),
+    ///     (&long, |e| {
+    ///         matches!(e, Error::Format(format::Error::TooLong { .. }))
+    ///     }),
+    /// ];
+    ///
+    /// // Ensure each identifier is rejected with the expected error
+    /// for (value, check) in cases {
+    ///     let err = value.parse::<Id>().unwrap_err();
+    ///     assert!(check(&err), "{value}: {err:?}");
+    /// }
+    ///
+    /// // Obtain byte offset of missing component from error
+    /// let err = "zri:file:::index.md:".parse::<Id>().unwrap_err();
+    /// assert_eq!(err.to_string(), "missing component: context at offset 10");
+    ///
+    /// // Ensure percent-encoded prefixes are accepted
+    /// assert!("z%72i:file::docs:index.md:".parse::<Id>().is_ok());
+    ///
+    /// // Ensure percent-encoded characters are canonicalized, so identifiers
+    /// // can be looked up regardless of the casing of hexadecimal digits
+    /// let mut ids = std::collections::HashMap::new();
+    /// ids.insert(Id::new("file", "docs", "a:b.md").unwrap(), 1);
+    /// let id: Id = "zri:file::docs:a%3ab.md:".parse().unwrap();
+    /// assert_eq!(id.to_string(), "zri:file::docs:a%3Ab.md:");
+    /// assert_eq!(ids.get(&id), Some(&1));
+    ///
+    /// // Ensure literal `%` sequences in components round trip
+    /// let id = Id::new("file", "docs", "file%41.md").unwrap();
+    /// assert_eq!(id.to_string(), "zri:file::docs:file%2541.md:");
+    /// assert_eq!(id.to_string().parse::<Id>().unwrap().path(), "file%41.md");
+    /// ```
+    fn from_str(value: &str) -> Result<Self> {
+        let format = Format::from_str_with(value, 5, |offset, byte| {
+            validate_byte(offset, byte).map_err(Error::from)
+        })?;
+
+        from_format(format)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Borrow<str> for Id {
+    /// Borrows the string representation.
+    ///
+    /// Since identifiers hash and compare like their string representation,
+    /// maps and sets keyed by identifiers can be queried with strings, without
+    /// parsing them first. Note that the string must be in canonical form, as
+    /// returned by [`Id::as_str`], or the lookup will miss.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::collections::HashMap;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create map and query it with strings
+    /// let mut ids = HashMap::new();
+    /// ids.insert(Id::new("file", "docs", "a:b.md")?, 1);
+    /// assert_eq!(ids.get("zri:file::docs:a%3Ab.md:"), Some(&1));
+    ///
+    /// // Equivalent, but non-canonical strings don't match
+    /// assert_eq!(ids.get("zri:file::docs:a%3ab.md:"), None);
+    /// assert_eq!(ids.get("zri1:file::docs:a%3Ab.md:"), None);
+    /// assert_eq!(ids.get("zri:file::docs:a%3Ab.md"), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl PartialEq<str> for Id {
+    /// Compares the identifier with a string for equality.
+    ///
+    /// The string is compared with the canonical representation as is, so
+    /// equivalent, but non-canonical strings aren't considered equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and compare it with strings
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(id, *"zri:file::docs:index.md:");
+    /// assert_ne!(id, *"zri:file::docs:index.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Id {
+    /// Compares the identifier with a string for equality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and compare it with strings
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(id, "zri:file::docs:index.md:");
+    /// assert_ne!(id, "zri1:file::docs:index.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Id {
+    /// Formats the identifier for display.
+    ///
+    /// In alternate mode, i.e., with `{:#}`, the components are decoded and
+    /// labeled for human-readable output, omitting empty components, which is
+    /// not canonical, and thus should not be used for logs or serialization.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and format it for humans
+    /// let id = Id::new("file", "docs", "a:b.md")?.with_fragment("top")?;
+    /// assert_eq!(id.to_string(), "zri:file::docs:a%3Ab.md:top");
+    /// assert_eq!(
+    ///     format!("{id:#}"),
+    ///     "scheme=file context=docs path=a:b.md fragment=top"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            self.format.fmt_components(f, LABELS)
+        } else {
+            self.format.fmt(f)
+        }
+    }
+}
+
+impl fmt::Debug for Id {
+    /// Formats the identifier for debugging.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Id")
+            .field("scheme", &self.scheme())
+            .field("binding", &self.binding())
+            .field("context", &self.context())
+            .field("path", &self.path())
+            .field("fragment", &self.fragment())
             .finish()
     }
 }
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Creates an identifier from a parsed formatted string.
+///
+/// This function is shared by [`Id::from_str`] and [`Id::from_bytes`], and
+/// checks the prefix and the presence of all required components.
+fn from_format(mut format: Format<6>) -> Result<Id> {
+    // Ensure prefix is valid, comparing the raw bytes first, and only
+    // decoding the prefix if it doesn't match, as it might be encoded
+    let prefix = &format.as_str()[format.span(0)];
+    let version = version::parse(prefix, "zri")
+        .or_else(|_| version::parse(&format.get(0), "zri"))?;
+
+    // Ensure version is supported - older versions must be migrated
+    if version != Id::FORMAT_VERSION {
+        Err(Error::Version(version))?;
+    }
+
+    // Ensure scheme, context and path are set, which we can check from the
+    // span lengths, since encoded values can never be empty
+    for (index, name) in [(1, "scheme"), (3, "context"), (4, "path")] {
+        let span = format.span(index);
+        if span.is_empty() {
+            let offset = Some(span.start);
+            Err(Error::Component { name, offset })?;
+        }
+    }
+
+    // Normalize prefix if it's versioned or encoded, so identifiers are
+    // always emitted unversioned, and compare equal regardless of prefix
+    if format.span(0).len() != 3 {
+        format.set(0, b"zri")?;
+    }
+
+    // No errors occurred
+    Ok(Id { format })
+}
+
+/// Creates an identifier from a file system path that is valid UTF-8.
+///
+/// This function is shared by [`Id::from_path`] and [`Id::from_path_lossy`],
+/// and normalizes the path relative to the context.
+fn from_path_str(scheme: &str, context: &str, path: &str) -> Result<Id> {
+    let path = normalize(path, context)?;
+    if path.is_empty() {
+        let name = Component::Path.name();
+        return Err(Error::Component { name, offset: None });
+    }
+
+    // Create identifier from normalized path
+    Id::new(scheme, context, path)
+}
+
+/// Converts a file system path into a string, if it is valid UTF-8.
+fn to_str(path: &Path) -> Result<&str> {
+    path.to_str().ok_or_else(|| Error::NonUtf8 {
+        lossy: path.to_string_lossy().into_owned(),
+    })
+}
+
+/// Decodes a percent-encoded part of a URL, if it is valid UTF-8.
+#[cfg(feature = "url")]
+fn decode_url(value: &str) -> Result<Cow<'_, str>> {
+    let value = value.as_bytes();
+    format::encoding::decode_strict(value).map_err(|_| Error::NonUtf8 {
+        lossy: format::encoding::decode(value).into_owned(),
+    })
+}
+
+/// Projects a part of a value, retaining borrowed values.
+///
+/// This allows to return parts of decoded components without allocating, if
+/// the component didn't need to be decoded.
+fn project<F>(value: Cow<'_, str>, f: F) -> Option<Cow<'_, str>>
+where
+    F: FnOnce(&str) -> Option<&str>,
+{
+    match value {
+        Cow::Borrowed(value) => f(value).map(Cow::Borrowed),
+        Cow::Owned(value) => f(&value).map(|part| Cow::Owned(part.to_owned())),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Type aliases
+// ----------------------------------------------------------------------------
+
+/// Ordered set of identifiers.
+pub type IdSet = BTreeSet<Id>;
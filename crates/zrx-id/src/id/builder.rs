@@ -0,0 +1,298 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Identifier builder.
+
+use std::borrow::Cow;
+
+use super::format::Format;
+use super::path::validate;
+use super::{Component, Error, Id, Result};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Identifier builder.
+///
+/// Builders accumulate components, and defer validation to
+/// [`IdBuilder::build`], which checks for backslashes and the presence of the
+/// `scheme`, `context` and `path` components, and writes all components to the
+/// formatted string in a single pass. This reads better than chains of
+/// fallible setters when identifiers are assembled from optional values,
+/// e.g., from configuration.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::prelude::*;
+///
+/// // Create identifier from builder
+/// let branch = Some("master");
+/// let id = Id::builder()
+///     .scheme("git")
+///     .maybe_binding(branch)
+///     .context("docs")
+///     .path("index.md")
+///     .build()?;
+/// assert_eq!(id.to_string(), "zri:git:master:docs:index.md:");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IdBuilder<'a> {
+    /// Scheme component.
+    scheme: Cow<'a, [u8]>,
+    /// Binding component.
+    binding: Cow<'a, [u8]>,
+    /// Context component.
+    context: Cow<'a, [u8]>,
+    /// Path component.
+    path: Cow<'a, [u8]>,
+    /// Fragment component.
+    fragment: Cow<'a, [u8]>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Id {
+    /// Creates an identifier builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier builder
+    /// let builder = Id::builder();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn builder<'a>() -> IdBuilder<'a> {
+        IdBuilder::default()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<'a> IdBuilder<'a> {
+    /// Sets the `scheme` component.
+    #[inline]
+    #[must_use]
+    pub fn scheme<S>(mut self, scheme: &'a S) -> Self
+    where
+        S: AsRef<[u8]> + ?Sized,
+    {
+        self.scheme = Cow::Borrowed(scheme.as_ref());
+        self
+    }
+
+    /// Sets the `binding` component.
+    #[inline]
+    #[must_use]
+    pub fn binding<S>(mut self, binding: &'a S) -> Self
+    where
+        S: AsRef<[u8]> + ?Sized,
+    {
+        self.binding = Cow::Borrowed(binding.as_ref());
+        self
+    }
+
+    /// Sets the `binding` component, or clears it, if none is given.
+    #[inline]
+    #[must_use]
+    pub fn maybe_binding<S>(mut self, binding: Option<&'a S>) -> Self
+    where
+        S: AsRef<[u8]> + ?Sized,
+    {
+        self.binding = Cow::Borrowed(binding.map_or(b"", AsRef::as_ref));
+        self
+    }
+
+    /// Sets the `context` component.
+    #[inline]
+    #[must_use]
+    pub fn context<S>(mut self, context: &'a S) -> Self
+    where
+        S: AsRef<[u8]> + ?Sized,
+    {
+        self.context = Cow::Borrowed(context.as_ref());
+        self
+    }
+
+    /// Sets the `path` component.
+    #[inline]
+    #[must_use]
+    pub fn path<S>(mut self, path: &'a S) -> Self
+    where
+        S: AsRef<[u8]> + ?Sized,
+    {
+        self.path = Cow::Borrowed(path.as_ref());
+        self
+    }
+
+    /// Sets the `fragment` component.
+    #[inline]
+    #[must_use]
+    pub fn fragment<S>(mut self, fragment: &'a S) -> Self
+    where
+        S: AsRef<[u8]> + ?Sized,
+    {
+        self.fragment = Cow::Borrowed(fragment.as_ref());
+        self
+    }
+
+    /// Sets the `fragment` component, or clears it, if none is given.
+    #[inline]
+    #[must_use]
+    pub fn maybe_fragment<S>(mut self, fragment: Option<&'a S>) -> Self
+    where
+        S: AsRef<[u8]> + ?Sized,
+    {
+        self.fragment = Cow::Borrowed(fragment.map_or(b"", AsRef::as_ref));
+        self
+    }
+
+    /// Builds the identifier.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if a component value contains a
+    /// backslash, [`Error::Component`], if a required component is empty, or
+    /// [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier from builder
+    /// let id = Id::builder()
+    ///     .scheme("file")
+    ///     .context("docs")
+    ///     .path("a:b.md")
+    ///     .fragment("top")
+    ///     .build()?;
+    /// assert_eq!(id.to_string(), "zri:file::docs:a%3Ab.md:top");
+    ///
+    /// // Create identifier from builder without path
+    /// let res = Id::builder().scheme("file").context("docs").build();
+    /// assert!(matches!(
+    ///     res,
+    ///     Err(zrx_id::Error::Component { name: "path", .. })
+    /// ));
+    ///
+    /// // Create identifier from builder with backslash
+    /// let res = Id::builder()
+    ///     .scheme("file")
+    ///     .context("docs")
+    ///     .path("a\\b.md")
+    ///     .build();
+    /// assert!(res.is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build(self) -> Result<Id> {
+        let required = [
+            (Component::Scheme, &self.scheme),
+            (Component::Context, &self.context),
+            (Component::Path, &self.path),
+        ];
+        for (component, value) in required {
+            if value.is_empty() {
+                let name = component.name();
+                return Err(Error::Component { name, offset: None });
+            }
+        }
+
+        // Create identifier by writing all components to the formatted string
+        // in a single pass, which encodes values containing `:` separators
+        let parts = [
+            b"zri",
+            validate(self.scheme.as_ref())?,
+            validate(self.binding.as_ref())?,
+            validate(self.context.as_ref())?,
+            validate(self.path.as_ref())?,
+            validate(self.fragment.as_ref())?,
+        ];
+        Ok(Id {
+            format: Format::from_parts(parts)?,
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<'a> From<&'a Id> for IdBuilder<'a> {
+    /// Creates an identifier builder seeded from an identifier.
+    ///
+    /// Components are borrowed from the identifier, unless they need to be
+    /// decoded, which allows to derive identifiers from existing ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdBuilder;
+    ///
+    /// // Create identifier and derive identifier from builder
+    /// let id = Id::new("file", "docs", "a:b.md")?.with_fragment("top")?;
+    /// let derived = IdBuilder::from(&id).path("index.md").build()?;
+    /// assert_eq!(derived.to_string(), "zri:file::docs:index.md:top");
+    ///
+    /// // Ensure identifiers round trip, including encoded components
+    /// assert_eq!(IdBuilder::from(&id).build()?, id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from(id: &'a Id) -> Self {
+        Self {
+            scheme: into_bytes(id.scheme()),
+            binding: id.binding().map(into_bytes).unwrap_or_default(),
+            context: into_bytes(id.context()),
+            path: into_bytes(id.path()),
+            fragment: id.fragment().map(into_bytes).unwrap_or_default(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Converts a string into bytes, retaining borrowed values.
+fn into_bytes(value: Cow<'_, str>) -> Cow<'_, [u8]> {
+    match value {
+        Cow::Borrowed(value) => Cow::Borrowed(value.as_bytes()),
+        Cow::Owned(value) => Cow::Owned(value.into_bytes()),
+    }
+}
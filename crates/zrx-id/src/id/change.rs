@@ -0,0 +1,286 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Change set.
+
+use std::collections::BTreeSet;
+
+#[cfg(feature = "globset")]
+use crate::Matcher;
+
+use super::{Id, IdSet};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Change set.
+///
+/// Change sets are computed by diffing two snapshots of identifiers, and are
+/// divided into created, removed and retained identifiers. Optionally, moves
+/// can be detected, which pair removed and created identifiers that share the
+/// same file name. All iterators yield identifiers in a deterministic order.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::prelude::*;
+/// use zrx_id::ChangeSet;
+///
+/// // Create identifiers
+/// let a = Id::new("file", "docs", "a.md")?;
+/// let b = Id::new("file", "docs", "b.md")?;
+/// let c = Id::new("file", "docs", "c.md")?;
+///
+/// // Compute change set from snapshots
+/// let changes = ChangeSet::diff([&a, &b], [&b, &c]);
+/// assert_eq!(changes.created().collect::<Vec<_>>(), [&c]);
+/// assert_eq!(changes.removed().collect::<Vec<_>>(), [&a]);
+/// assert_eq!(changes.retained().collect::<Vec<_>>(), [&b]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChangeSet {
+    /// Created identifiers.
+    created: IdSet,
+    /// Removed identifiers.
+    removed: IdSet,
+    /// Retained identifiers.
+    retained: IdSet,
+    /// Moved identifiers.
+    moved: BTreeSet<(Id, Id)>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl ChangeSet {
+    /// Computes the change set between two snapshots of identifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::ChangeSet;
+    ///
+    /// // Create identifiers
+    /// let a = Id::new("file", "docs", "a.md")?;
+    /// let b = Id::new("file", "docs", "b.md")?;
+    ///
+    /// // Compute change set from snapshots
+    /// let changes = ChangeSet::diff([&a], [&a, &b]);
+    /// assert!(!changes.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff<'a, B, A>(before: B, after: A) -> Self
+    where
+        B: IntoIterator<Item = &'a Id>,
+        A: IntoIterator<Item = &'a Id>,
+    {
+        let mut removed: IdSet = before.into_iter().cloned().collect();
+        let mut created = IdSet::new();
+        let mut retained = IdSet::new();
+
+        // Partition identifiers of the second snapshot into retained ones, and
+        // created ones, and leave all remaining ones of the first as removed
+        for id in after {
+            if removed.remove(id) {
+                retained.insert(id.clone());
+            } else if !retained.contains(id) {
+                created.insert(id.clone());
+            }
+        }
+
+        // Return change set
+        Self {
+            created,
+            removed,
+            retained,
+            moved: BTreeSet::new(),
+        }
+    }
+
+    /// Detects moves among created and removed identifiers.
+    ///
+    /// Each removed identifier is paired with a created identifier that has
+    /// the same file name, i.e., the last segment of the path, preferring the
+    /// identifier with the longest common path suffix, and then the one that
+    /// sorts first. Paired identifiers are no longer reported as created or
+    /// removed, but as moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::ChangeSet;
+    ///
+    /// // Create identifiers
+    /// let a = Id::new("file", "docs", "guide/index.md")?;
+    /// let b = Id::new("file", "docs", "blog/index.md")?;
+    /// let c = Id::new("file", "docs", "manual/guide/index.md")?;
+    ///
+    /// // Compute change set from snapshots and detect moves
+    /// let mut changes = ChangeSet::diff([&a], [&b, &c]);
+    /// changes.detect_moves();
+    /// assert_eq!(changes.moved().collect::<Vec<_>>(), [(&a, &c)]);
+    /// assert_eq!(changes.created().collect::<Vec<_>>(), [&b]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn detect_moves(&mut self) -> &mut Self {
+        let mut moved = Vec::new();
+        let mut paired = BTreeSet::new();
+        for from in &self.removed {
+            let path = from.path();
+
+            // Find the best match among all created identifiers that haven't
+            // been paired yet, which must at least share the same file name
+            let mut best: Option<(&Id, usize)> = None;
+            for to in &self.created {
+                if paired.contains(to) {
+                    continue;
+                }
+                let len = common_suffix(&path, &to.path());
+                if len > 0 && best.map_or(true, |(_, best)| len > best) {
+                    best = Some((to, len));
+                }
+            }
+
+            // Pair identifiers, if a match was found
+            if let Some((to, _)) = best {
+                paired.insert(to);
+                moved.push((from.clone(), to.clone()));
+            }
+        }
+
+        // Move paired identifiers out of created and removed
+        for (from, to) in moved {
+            self.removed.remove(&from);
+            self.created.remove(&to);
+            self.moved.insert((from, to));
+        }
+
+        // Return self for chaining
+        self
+    }
+
+    /// Returns the change set scoped to identifiers matching a matcher.
+    ///
+    /// Moves are retained if either of the identifiers matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::ChangeSet;
+    ///
+    /// // Create identifiers
+    /// let a = Id::new("file", "docs", "a.md")?;
+    /// let b = Id::new("file", "docs", "b.png")?;
+    ///
+    /// // Compute change set from snapshots and scope to Markdown files
+    /// let changes = ChangeSet::diff([], [&a, &b]);
+    /// let changes = changes.filter(&"zrs::::**/*.md:".parse()?);
+    /// assert_eq!(changes.created().collect::<Vec<_>>(), [&a]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "globset")]
+    #[must_use]
+    pub fn filter(&self, matcher: &Matcher) -> Self {
+        let is_match = |id: &Id| matches!(matcher.is_match(id), Ok(true));
+        let filter = |ids: &IdSet| {
+            ids.iter().filter(|id| is_match(id)).cloned().collect()
+        };
+        Self {
+            created: filter(&self.created),
+            removed: filter(&self.removed),
+            retained: filter(&self.retained),
+            moved: self
+                .moved
+                .iter()
+                .filter(|(from, to)| is_match(from) || is_match(to))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Returns an iterator over created identifiers.
+    #[inline]
+    pub fn created(&self) -> impl Iterator<Item = &Id> {
+        self.created.iter()
+    }
+
+    /// Returns an iterator over removed identifiers.
+    #[inline]
+    pub fn removed(&self) -> impl Iterator<Item = &Id> {
+        self.removed.iter()
+    }
+
+    /// Returns an iterator over retained identifiers.
+    #[inline]
+    pub fn retained(&self) -> impl Iterator<Item = &Id> {
+        self.retained.iter()
+    }
+
+    /// Returns an iterator over moved identifiers, from old to new.
+    #[inline]
+    pub fn moved(&self) -> impl Iterator<Item = (&Id, &Id)> {
+        self.moved.iter().map(|(from, to)| (from, to))
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl ChangeSet {
+    /// Returns whether there are no changes.
+    ///
+    /// Retained identifiers are not considered changes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the number of common trailing segments of two paths.
+fn common_suffix(a: &str, b: &str) -> usize {
+    a.rsplit('/')
+        .zip(b.rsplit('/'))
+        .take_while(|(a, b)| a == b)
+        .count()
+}
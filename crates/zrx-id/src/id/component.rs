@@ -0,0 +1,115 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Component.
+
+use std::fmt;
+
+mod set;
+
+pub use set::ComponentSet;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Component.
+///
+/// Components name the five values that make up identifiers and selectors,
+/// and map to their positions in the underlying formatted string, which are
+/// offset by one, as the first position is occupied by the prefix.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::prelude::*;
+///
+/// // Obtain position and name of component
+/// assert_eq!(Component::Path.index(), 4);
+/// assert_eq!(Component::Path.name(), "path");
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Component {
+    /// Scheme of the resource.
+    Scheme,
+    /// Binding of the resource.
+    Binding,
+    /// Context of the resource.
+    Context,
+    /// Path to the resource.
+    Path,
+    /// Fragment of the resource.
+    Fragment,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Component {
+    /// All components in order of appearance.
+    pub const ALL: [Component; 5] = [
+        Component::Scheme,
+        Component::Binding,
+        Component::Context,
+        Component::Path,
+        Component::Fragment,
+    ];
+}
+
+#[allow(clippy::must_use_candidate)]
+impl Component {
+    /// Returns the position in the formatted string.
+    #[inline]
+    pub const fn index(self) -> usize {
+        self as usize + 1
+    }
+
+    /// Returns the name.
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Component::Scheme => "scheme",
+            Component::Binding => "binding",
+            Component::Context => "context",
+            Component::Path => "path",
+            Component::Fragment => "fragment",
+        }
+    }
+
+    /// Returns whether the component is optional in identifiers.
+    #[inline]
+    pub const fn is_optional(self) -> bool {
+        matches!(self, Component::Binding | Component::Fragment)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Component {
+    /// Formats the component for display.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Component set.
+
+use std::fmt;
+use std::ops::BitOr;
+
+use super::Component;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Component set.
+///
+/// Component sets are small bit sets over the five [`Component`] variants,
+/// which allow to name several components at once, e.g., to copy them from
+/// one identifier to another. They can be combined with the `|` operator.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::prelude::*;
+/// use zrx_id::ComponentSet;
+///
+/// // Create component set
+/// let set = Component::Context | Component::Path;
+/// assert!(set.contains(Component::Path));
+/// assert!(!set.contains(Component::Scheme));
+/// assert_eq!(set.len(), 2);
+///
+/// // Obtain components in order of appearance
+/// let components: Vec<_> = set.iter().collect();
+/// assert_eq!(components, [Component::Context, Component::Path]);
+/// ```
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct ComponentSet {
+    /// Bit set of components.
+    bits: u8,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl ComponentSet {
+    /// Set of all components.
+    pub const ALL: Self = Self { bits: 0b1_1111 };
+
+    /// Creates an empty component set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::ComponentSet;
+    ///
+    /// // Create component set
+    /// let set = ComponentSet::new();
+    /// assert!(set.is_empty());
+    /// ```
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { bits: 0 }
+    }
+
+    /// Returns the component set with the given component added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::ComponentSet;
+    ///
+    /// // Create component set in a const context
+    /// const SET: ComponentSet = ComponentSet::new().with(Component::Path);
+    /// assert!(SET.contains(Component::Path));
+    /// ```
+    #[must_use]
+    pub const fn with(self, component: Component) -> Self {
+        Self {
+            bits: self.bits | 1 << component as u8,
+        }
+    }
+
+    /// Adds the given component to the component set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::ComponentSet;
+    ///
+    /// // Create component set and add component
+    /// let mut set = ComponentSet::new();
+    /// set.insert(Component::Fragment);
+    /// assert!(set.contains(Component::Fragment));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, component: Component) {
+        *self = self.with(component);
+    }
+
+    /// Removes the given component from the component set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::ComponentSet;
+    ///
+    /// // Create component set and remove component
+    /// let mut set = ComponentSet::ALL;
+    /// set.remove(Component::Fragment);
+    /// assert!(!set.contains(Component::Fragment));
+    /// assert_eq!(set.len(), 4);
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, component: Component) {
+        self.bits &= !(1 << component as u8);
+    }
+
+    /// Returns an iterator over the components in order of appearance.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Component> {
+        let set = *self;
+        Component::ALL
+            .into_iter()
+            .filter(move |&component| set.contains(component))
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl ComponentSet {
+    /// Returns whether the component set contains the given component.
+    #[inline]
+    pub const fn contains(&self, component: Component) -> bool {
+        self.bits & 1 << component as u8 != 0
+    }
+
+    /// Returns the number of components.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Returns whether there are any components.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl From<Component> for ComponentSet {
+    /// Creates a component set from a component.
+    #[inline]
+    fn from(component: Component) -> Self {
+        Self::new().with(component)
+    }
+}
+
+impl FromIterator<Component> for ComponentSet {
+    /// Creates a component set from an iterator.
+    #[inline]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = Component>,
+    {
+        iter.into_iter().fold(Self::new(), Self::with)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl BitOr for ComponentSet {
+    type Output = Self;
+
+    /// Returns the union of both component sets.
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        Self { bits: self.bits | rhs.bits }
+    }
+}
+
+impl BitOr<Component> for ComponentSet {
+    type Output = Self;
+
+    /// Returns the component set with the given component added.
+    #[inline]
+    fn bitor(self, rhs: Component) -> Self {
+        self.with(rhs)
+    }
+}
+
+impl BitOr for Component {
+    type Output = ComponentSet;
+
+    /// Returns the component set of both components.
+    #[inline]
+    fn bitor(self, rhs: Self) -> ComponentSet {
+        ComponentSet::from(self).with(rhs)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl fmt::Debug for ComponentSet {
+    /// Formats the component set for debugging.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
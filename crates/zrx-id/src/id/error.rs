@@ -33,6 +33,7 @@ use crate::{format, path};
 
 /// Identifier error.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Format error.
     #[error(transparent)]
@@ -42,13 +43,60 @@ pub enum Error {
     #[error(transparent)]
     Path(#[from] path::Error),
 
+    /// URL error.
+    #[cfg(feature = "url")]
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+
     /// Invalid prefix.
     #[error("invalid prefix")]
     Prefix,
 
+    /// Invalid UTF-8.
+    #[error("invalid UTF-8: {lossy}")]
+    NonUtf8 {
+        /// Lossy rendering.
+        lossy: String,
+    },
+
+    /// Unsupported format version.
+    #[error("unsupported version: {0}")]
+    Version(u32),
+
     /// Missing component.
-    #[error("missing component: {0}")]
-    Component(&'static str),
+    #[error(
+        "missing component: {name}{}",
+        .offset.map(|offset| format!(" at offset {offset}")).unwrap_or_default()
+    )]
+    Component {
+        /// Component name.
+        name: &'static str,
+        /// Byte offset in the identifier, if parsed from a string.
+        offset: Option<usize>,
+    },
+
+    /// Mismatched component.
+    #[error("mismatched component: {name}")]
+    Mismatch {
+        /// Component name.
+        name: &'static str,
+    },
+
+    /// Unknown scheme.
+    #[error("unknown scheme: {scheme}")]
+    Scheme {
+        /// Scheme name.
+        scheme: String,
+    },
+
+    /// Identifier rejected by scheme validator.
+    #[error("rejected by scheme {scheme}: {reason}")]
+    Rejected {
+        /// Scheme name.
+        scheme: String,
+        /// Reason for rejection.
+        reason: String,
+    },
 }
 
 // ----------------------------------------------------------------------------
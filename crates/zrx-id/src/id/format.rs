@@ -26,18 +26,33 @@ use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::io;
 use std::ops::Range;
-use std::str::{from_utf8_unchecked, FromStr};
+use std::result;
+use std::str::{from_utf8, from_utf8_unchecked, FromStr};
 
 pub mod container;
 pub mod encoding;
 mod error;
+mod flex;
+pub mod hash;
+#[cfg(feature = "serde")]
+mod serde;
 pub mod span;
 
 use container::{Container, Recommended};
-use encoding::{decode, encode};
+use encoding::{decode, decode_strict, encode};
 pub use error::{Error, Result};
-use span::{init, Span};
+pub use flex::FlexFormat;
+use hash::{fnv1a_128, fnv1a_64};
+use span::Span;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Maximum length of a formatted string in bytes.
+const MAX_LEN: usize = u16::MAX as usize;
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -47,8 +62,8 @@ use span::{init, Span};
 ///
 /// This is a low-level construct which allows to create and manage strings that
 /// contain a predefined number of values, all of which are separated with `:`.
-/// If a value contains a `:` itself, it is percent-encoded, which is indicated
-/// by a flag. This is slower, but not expected to be common.
+/// If a value contains a `:` or `%` itself, it is percent-encoded, which is
+/// indicated by a flag. This is slower, but not expected to be common.
 ///
 /// Formatted strings are optimized for very fast conversion with [`FromStr`],
 /// as well as cloning, since we only expect a few lookups but much more clones
@@ -60,6 +75,16 @@ use span::{init, Span};
 /// would be more than enough, but since Rust will align the field to 64 bits
 /// anyway, there's no point in being cheap.
 ///
+/// Since span ends are stored as `u16`, formatted strings are limited to
+/// [`Format::MAX_LEN`] bytes, including separators and percent-encoded
+/// characters. Exceeding the limit yields [`Error::TooLong`].
+///
+/// Spans are not stored as such, but only their end offsets, as the formatted
+/// string guarantees that each value is followed by exactly one separator, so
+/// the start of a span can be derived from the end of the previous one. This
+/// halves the memory needed for spans, and makes cloning correspondingly
+/// cheaper. [`Span`] is merely a view that is computed on demand.
+///
 /// # Examples
 ///
 /// ```
@@ -85,8 +110,8 @@ where
 {
     /// String representation.
     value: C,
-    /// Set of spans.
-    spans: [Span; N],
+    /// Set of span ends.
+    ends: [u16; N],
     /// Encoding flags.
     flags: u64,
 }
@@ -95,15 +120,1508 @@ where
 // Implementations
 // ----------------------------------------------------------------------------
 
-impl<const N: usize, C> Format<N, C>
-where
-    C: Container,
-{
-    /// Creates a formatted string.
+impl<const N: usize, C> Format<N, C>
+where
+    C: Container,
+{
+    /// Maximum length in bytes.
+    pub const MAX_LEN: usize = MAX_LEN;
+
+    /// Creates a formatted string.
+    ///
+    /// The span count must be in `1..=64`, which is checked at compile time,
+    /// as the encoding flags only have room for 64 spans.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string
+    /// let mut format = Format::<3>::new();
+    /// format.set(0, "a")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Unsupported span counts are rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string with too many spans
+    /// let format = Format::<65>::new();
+    /// ```
+    ///
+    /// This includes the [`Default`] implementation:
+    ///
+    /// ```compile_fail
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string with no spans
+    /// let format = Format::<0>::default();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string with too many spans
+    /// let format = Format::<65>::default();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            value: C::from(&[b':'; N][1..]), // N - 1
+            ends: const { init_ends::<N>() },
+            flags: 0,
+        }
+    }
+
+    /// Creates a formatted string with the given capacity.
+    ///
+    /// The capacity is forwarded to the container as a hint, which allows to
+    /// avoid reallocations when values are set one after another, e.g., when
+    /// the total length of all values is known in advance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string with capacity
+    /// let mut format = Format::<3, Vec<u8>>::with_capacity(256);
+    /// format.set(2, "a".repeat(200))?;
+    /// assert!(format.into_inner().capacity() >= 256);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_capacity(bytes: usize) -> Self {
+        let mut format = Self::new();
+        format.reserve(bytes.saturating_sub(N - 1));
+        format
+    }
+
+    /// Creates a formatted string from the given parts.
+    ///
+    /// Each part is encoded exactly once, and written to the container in a
+    /// single pass, recording span ends and encoding flags along the way. This
+    /// is considerably faster than setting each value one after another, or
+    /// concatenating and parsing a string.
+    ///
+    /// # Errors
+    ///
+    /// If the formatted string would exceed [`Format::MAX_LEN`] bytes, the
+    /// parts can't be represented, and [`Error::TooLong`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string from parts
+    /// let format = Format::<3>::from_parts(["a", "b:c", "d"])?;
+    /// assert_eq!(format.as_str(), "a:b%3Ac:d");
+    /// assert_eq!(format.get(1), "b:c");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Parts that need encoding are flagged, so they're decoded on access, and
+    /// parts that would overflow the format are rejected:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::{Error as FormatError, Format};
+    ///
+    /// // Create formatted string from parts that need encoding
+    /// let format = Format::<3>::from_parts([":", "a", "b::c"])?;
+    /// assert_eq!(format.as_str(), "%3A:a:b%3A%3Ac");
+    /// assert_eq!(format, "%3A:a:b%3A%3Ac".parse()?);
+    /// let values: Vec<_> = format.iter().collect();
+    /// assert_eq!(values, [":", "a", "b::c"]);
+    ///
+    /// // Create formatted string from parts that overflow
+    /// let long = "a".repeat(u16::MAX as usize - 4);
+    /// assert!(Format::<3>::from_parts([long.as_str(), "b", "c"]).is_ok());
+    /// let res = Format::<3>::from_parts([long.as_str(), "bc", "d"]);
+    /// assert!(matches!(res, Err(FormatError::TooLong { len: 65536, .. })));
+    ///
+    /// // Ensure overflow is detected after encoding
+    /// let res = Format::<3>::from_parts([long.as_str(), ":", ""]);
+    /// assert!(matches!(res, Err(FormatError::TooLong { len: 65536, .. })));
+    ///
+    /// // Create formatted string from parts that consist of a `%`
+    /// let format = Format::<3>::from_parts(["%", "a", "%"])?;
+    /// assert_eq!(format.as_str(), "%25:a:%25");
+    /// assert_eq!(format, "%25:a:%25".parse()?);
+    /// assert_eq!(format.get(0), "%");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Formatted strings are identical to those obtained by encoding and
+    /// joining the parts, and parsing the resulting string:
+    ///
+    /// ```
+    /// # #[cfg(feature = "proptest")]
+    /// # fn main() {
+    /// use proptest::prelude::*;
+    /// use zrx_id::format::encoding::encode;
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create strategy for parts, including characters to encode
+    /// let part = "[a-z:% é\n]{0,8}";
+    ///
+    /// // Ensure formatted strings are identical to parsed formatted strings
+    /// proptest!(|(parts in [part, part, part])| {
+    ///     let format = Format::<3>::from_parts(parts.clone()).unwrap();
+    ///     let value = parts
+    ///         .iter()
+    ///         .map(|part| encode(part.as_bytes()))
+    ///         .collect::<Vec<_>>()
+    ///         .join(":");
+    ///     let parsed: Format<3> = value.parse().unwrap();
+    ///     prop_assert_eq!(format.as_str(), parsed.as_str());
+    ///     prop_assert_eq!(format.iter().collect::<Vec<_>>(), parts);
+    ///     prop_assert_eq!(format, parsed);
+    /// });
+    /// # }
+    /// # #[cfg(not(feature = "proptest"))]
+    /// # fn main() {}
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn from_parts<S>(parts: [S; N]) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        const { check_spans::<N>() };
+        let parts = parts.each_ref().map(|part| encode(part.as_ref()));
+
+        // Compute required capacity, which must fit into the span ends
+        let capacity = parts.iter().map(|part| part.len() + 1).sum::<usize>();
+        check_len(capacity - 1)?;
+
+        // Write all parts with `:` separators to the container, and record the
+        // span ends, as well as whether a part needed encoding
+        let mut value = C::from(&[]);
+        value.reserve(capacity);
+        let mut ends = [0; N];
+        let mut flags = 0;
+        for (index, part) in parts.iter().enumerate() {
+            if index > 0 {
+                value.extend(b":");
+            }
+            value.extend(part.as_bytes());
+
+            // Since the total length was checked, truncation can't happen
+            #[allow(clippy::cast_possible_truncation)]
+            {
+                ends[index] = value.len() as u16;
+            }
+            if let Cow::Owned(_) = part {
+                flags |= 1 << index;
+            }
+        }
+
+        // Return formatted string
+        Ok(Self { value, ends, flags })
+    }
+
+    /// Attempts to create a formatted string from bytes.
+    ///
+    /// This is the equivalent of [`FromStr`] for bytes, e.g., when reading
+    /// from memory-mapped files, which validates the bytes as UTF-8, while
+    /// computing the spans in a single pass, so bytes are scanned only once.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are not valid UTF-8, [`Error::Utf8`] is returned with the
+    /// offset of the first invalid byte. If the span count is off,
+    /// [`Error::Cardinality`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::{Error as FormatError, Format};
+    ///
+    /// // Create formatted string from bytes
+    /// let format = Format::<3>::from_bytes("a:b%3Ac:d\u{e9}".as_bytes())?;
+    /// assert_eq!(format.get(1), "b:c");
+    /// assert_eq!(format.get(2), "d\u{e9}");
+    ///
+    /// // Create formatted string from bytes that are not valid UTF-8
+    /// let res = Format::<3>::from_bytes(b"a:b\xc3\xa9:\xff");
+    /// assert!(matches!(res, Err(FormatError::Utf8 { offset: 6 })));
+    /// let res = Format::<3>::from_bytes(b"a:b:c:\xc3");
+    /// assert!(matches!(res, Err(FormatError::Utf8 { offset: 6 })));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn from_bytes(value: &[u8]) -> Result<Self> {
+        parse::<N, true, _, _, _>(value, N, |_, _| Ok(()))
+    }
+
+    /// Attempts to create a formatted string from a string, checking each byte.
+    ///
+    /// This is the single-pass variant of [`FromStr`] for callers that need to
+    /// validate each byte of the string, i.e., identifiers and selectors, and
+    /// which receives the byte offset along with each byte. All errors returned
+    /// by the check take precedence over format errors. If the
+    /// string contains at least `min` values, missing trailing values are
+    /// filled in as empty values.
+    #[inline]
+    pub(crate) fn from_str_with<F, E>(
+        value: &str, min: usize, check: F,
+    ) -> result::Result<Self, E>
+    where
+        F: FnMut(usize, u8) -> result::Result<(), E>,
+        E: From<Error>,
+    {
+        parse::<N, false, _, _, _>(value.as_bytes(), min, check)
+    }
+
+    /// Attempts to create a formatted string from bytes, checking each byte.
+    ///
+    /// This is the variant of [`Format::from_str_with`] for bytes, which are
+    /// validated as UTF-8 in the same pass.
+    #[inline]
+    pub(crate) fn from_bytes_with<F, E>(
+        value: &[u8], min: usize, check: F,
+    ) -> result::Result<Self, E>
+    where
+        F: FnMut(usize, u8) -> result::Result<(), E>,
+        E: From<Error>,
+    {
+        parse::<N, true, _, _, _>(value, min, check)
+    }
+
+    /// Returns the value at the given index.
+    ///
+    /// If the value is not percent-encoded, which means it does not contain a
+    /// `:` or `%` character, a borrowed reference is returned, which is a
+    /// zero-cost operation and expected to be the common case. Otherwise, the
+    /// value is percent-decoded and an owned value is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds. Since [`Format`] is a low-level
+    /// construct, we don't expect this to happen, as indexes should be known.
+    /// Use [`Format::try_get`] for the non-panicking variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string
+    /// let mut format = Format::<3>::new();
+    /// format.set(0, "a")?;
+    ///
+    /// // Obtain value at index
+    /// let value = format.get(0);
+    /// assert_eq!(value, "a");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get(&self, index: usize) -> Cow<'_, str> {
+        let range: Range<_> = span(&self.ends, index).into();
+        if self.flags & (1 << index) == 0 {
+            // SAFETY: The value is guaranteed to be valid UTF-8, as it was
+            // created from a valid UTF-8 string. Additionally, the value is
+            // not percent-encoded, so we can just return a borrowed reference
+            // to the formatted string value, which is the common fast path.
+            unsafe { Cow::Borrowed(from_utf8_unchecked(&self.value[range])) }
+        } else {
+            decode(&self.value[range])
+        }
+    }
+
+    /// Returns the value at the given index, if any.
+    ///
+    /// This is the non-panicking variant of [`Format::get`], which returns
+    /// [`None`] if the index is out of bounds, and otherwise behaves exactly
+    /// the same, including the borrowed fast path for values that are not
+    /// percent-encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string
+    /// let mut format = Format::<3>::new();
+    /// format.set(0, "a")?;
+    /// format.set(2, "b:c")?;
+    ///
+    /// // Obtain values at indexes
+    /// assert!(matches!(format.try_get(0), Some(Cow::Borrowed("a"))));
+    /// assert!(matches!(format.try_get(1), Some(Cow::Borrowed(""))));
+    /// assert!(matches!(format.try_get(2), Some(Cow::Owned(v)) if v == "b:c"));
+    /// assert_eq!(format.try_get(3), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn try_get(&self, index: usize) -> Option<Cow<'_, str>> {
+        (index < N).then(|| self.get(index))
+    }
+
+    /// Returns the value at the given index, failing on invalid UTF-8.
+    ///
+    /// Other than [`Format::get`], which replaces invalid UTF-8 hidden behind
+    /// percent-encoding with the Unicode replacement character, this method
+    /// returns an error, since distinct values would otherwise be obtained as
+    /// the same value. Values that are not percent-encoded are always valid.
+    ///
+    /// # Errors
+    ///
+    /// If the decoded value is not valid UTF-8, [`Error::Utf8`] is returned,
+    /// carrying the offset of the first invalid byte in the decoded value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::{Error as FormatError, Format};
+    ///
+    /// // Create formatted string from string with invalid UTF-8
+    /// let format: Format<3> = "a:b%3Ac:d%FF".parse()?;
+    /// assert_eq!(format.get_strict(0)?, "a");
+    /// assert_eq!(format.get_strict(1)?, "b:c");
+    ///
+    /// // Obtain value with invalid UTF-8
+    /// let res = format.get_strict(2);
+    /// assert!(matches!(res, Err(FormatError::Utf8 { offset: 1 })));
+    /// assert_eq!(format.get(2), "d\u{FFFD}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_strict(&self, index: usize) -> Result<Cow<'_, str>> {
+        let range: Range<_> = span(&self.ends, index).into();
+        if self.flags & (1 << index) == 0 {
+            Ok(self.get(index))
+        } else {
+            decode_strict(&self.value[range])
+                .map_err(|err| Error::Utf8 { offset: err.valid_up_to() })
+        }
+    }
+
+    /// Updates the value at the given index.
+    ///
+    /// # Errors
+    ///
+    /// If the formatted string would overflow, [`Error::TooLong`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds. Use [`Format::try_set`] for the
+    /// non-panicking variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and insert value
+    /// let mut format = Format::<3>::new();
+    /// format.set(0, "a")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Values containing `%` are percent-encoded, so they round trip, even if
+    /// they look like percent-encoded characters:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Ensure values containing `%` round trip through strings
+    /// for value in ["%", "%4", "%41", "%zz", "file%41.md", "a:%3A"] {
+    ///     let mut format = Format::<3>::new();
+    ///     format.set(1, value)?;
+    ///     assert!(format.is_encoded(1));
+    ///     let parsed: Format<3> = format.as_str().parse()?;
+    ///     assert_eq!(parsed.get(1), value);
+    ///     assert_eq!(parsed, format);
+    /// }
+    ///
+    /// // Obtain string representation with encoded `%`
+    /// let format = Format::<3>::new().with(1, "file%41.md")?;
+    /// assert_eq!(format.as_str(), ":file%2541.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Values that would make the formatted string exceed [`Format::MAX_LEN`]
+    /// bytes are rejected, leaving the formatted string unchanged:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::{Error as FormatError, Format};
+    ///
+    /// // Create formatted string and insert long values
+    /// let mut format = Format::<3>::new();
+    /// format.set(1, "a".repeat(40_000))?;
+    /// format.set(1, "b".repeat(Format::<3>::MAX_LEN - 2))?;
+    /// let res = format.set(0, "c");
+    /// assert!(matches!(
+    ///     res,
+    ///     Err(FormatError::TooLong { len: 65_536, max: 65_535 })
+    /// ));
+    /// assert_eq!(format.as_str().len(), Format::<3>::MAX_LEN);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set<S>(&mut self, index: usize, value: S) -> Result
+    where
+        S: AsRef<[u8]>,
+    {
+        let value = value.as_ref();
+        replace(
+            &mut self.value,
+            &mut self.ends,
+            &mut self.flags,
+            index,
+            value,
+        )
+    }
+
+    /// Updates the value at the given index with raw bytes.
+    ///
+    /// This is a sharp tool for copying values that are already encoded, e.g.,
+    /// obtained with [`Format::raw`], which avoids the round trip of decoding
+    /// and re-encoding. The bytes are written as is, and the encoding flag is
+    /// set as given. The caller must ensure that the bytes are a valid span:
+    ///
+    /// - The bytes must not contain a `:`.
+    /// - If `encoded` is set, the bytes must contain at least one `%`, and all
+    ///   `%` must be followed by two uppercase hexadecimal digits, as the
+    ///   encoder would emit them. Otherwise, the bytes must not contain `%`.
+    ///
+    /// Those invariants are only checked in debug builds. The bytes must also
+    /// be valid UTF-8, which is always checked, as the formatted string would
+    /// be unsound otherwise.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are not valid UTF-8, [`Error::Utf8`] is returned, and if
+    /// the formatted string would overflow, [`Error::TooLong`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds, and in debug builds, if the bytes
+    /// violate the invariants of a span.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted strings and copy raw value
+    /// let mut format = Format::<3>::new();
+    /// let other: Format<3> = "a:b%3Ac:d".parse()?;
+    /// format.set_raw(0, other.raw(1), other.is_encoded(1))?;
+    /// assert_eq!(format.as_str(), "b%3Ac::");
+    /// assert_eq!(format.get(0), "b:c");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_raw(
+        &mut self, index: usize, value: &[u8], encoded: bool,
+    ) -> Result {
+        validate_utf8(value, 0)?;
+        debug_assert!(!value.contains(&b':'), "raw value must not contain `:`");
+        debug_assert!(
+            is_raw(value) == encoded,
+            "raw value must match encoding flag"
+        );
+        replace_raw(
+            &mut self.value,
+            &mut self.ends,
+            &mut self.flags,
+            index,
+            value,
+            encoded,
+        )
+    }
+
+    /// Updates the values at the given indexes.
+    ///
+    /// All values are encoded exactly once, and the formatted string is rebuilt
+    /// in a single pass, which avoids shifting subsequent spans for each value,
+    /// as happens when setting values one after another. If an index is given
+    /// more than once, the last value wins. Updates are atomic - either all of
+    /// them are applied, or none are.
+    ///
+    /// # Errors
+    ///
+    /// If an index is out of bounds, [`Error::Index`] is returned, and if the
+    /// formatted string would overflow, [`Error::TooLong`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::{Error as FormatError, Format};
+    ///
+    /// // Create formatted string and update values
+    /// let mut format: Format<3> = "a:b%3Ac:d".parse()?;
+    /// format.set_many([(2, "e:f"), (1, "g"), (2, "h:i")])?;
+    /// assert_eq!(format.as_str(), "a:g:h%3Ai");
+    /// assert_eq!(format.get(1), "g");
+    /// assert_eq!(format.get(2), "h:i");
+    ///
+    /// // Update values with out of bounds index
+    /// let res = format.set_many([(0, "j"), (3, "k")]);
+    /// assert!(matches!(res, Err(FormatError::Index(3))));
+    /// assert_eq!(format.as_str(), "a:g:h%3Ai");
+    ///
+    /// // Update values with overflowing length
+    /// let long = "a".repeat(1 << 16);
+    /// let res = format.set_many([(0, "j"), (1, long.as_str())]);
+    /// assert!(matches!(res, Err(FormatError::TooLong { .. })));
+    /// assert_eq!(format.as_str(), "a:g:h%3Ai");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_many<I, S>(&mut self, values: I) -> Result
+    where
+        I: IntoIterator<Item = (usize, S)>,
+        S: AsRef<[u8]>,
+    {
+        let mut updates: [Option<S>; N] = std::array::from_fn(|_| None);
+        for (index, value) in values {
+            *updates.get_mut(index).ok_or(Error::Index(index))? = Some(value);
+        }
+
+        // Encode all updated values, and compute the required capacity from
+        // the new values and the retained ones, which must fit into the ends
+        let parts = updates
+            .each_ref()
+            .map(|update| update.as_ref().map(|value| encode(value.as_ref())));
+        let mut capacity = 0;
+        for (index, part) in parts.iter().enumerate() {
+            capacity += part
+                .as_ref()
+                .map_or_else(|| self.span(index).len(), |part| part.len());
+            capacity += 1;
+        }
+        check_len(capacity - 1)?;
+
+        // Write all values with `:` separators to a new container, and record
+        // the span ends, as well as whether an updated value needed encoding
+        let mut value = C::from(&[]);
+        value.reserve(capacity);
+        let mut ends = [0; N];
+        let mut flags = self.flags;
+        for (index, part) in parts.iter().enumerate() {
+            if index > 0 {
+                value.extend(b":");
+            }
+            match part {
+                Some(Cow::Borrowed(part)) => {
+                    value.extend(part.as_bytes());
+                    flags &= !(1 << index);
+                }
+                Some(Cow::Owned(part)) => {
+                    value.extend(part.as_bytes());
+                    flags |= 1 << index;
+                }
+                None => value.extend(&self.value[self.span(index)]),
+            }
+
+            // Since the total length was checked, truncation can't happen
+            #[allow(clippy::cast_possible_truncation)]
+            {
+                ends[index] = value.len() as u16;
+            }
+        }
+
+        // Replace formatted string
+        *self = Self { value, ends, flags };
+        Ok(())
+    }
+
+    /// Copies the values at the given indexes from another formatted string.
+    ///
+    /// The raw bytes of the values are copied together with their encoding
+    /// flags, so values are neither decoded nor encoded, and the formatted
+    /// string is rebuilt in a single pass. If an index is given more than
+    /// once, it is copied once. Updates are atomic - either all of them are
+    /// applied, or none are.
+    ///
+    /// # Errors
+    ///
+    /// If an index is out of bounds, [`Error::Index`] is returned, and if the
+    /// formatted string would overflow, [`Error::TooLong`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::{Error as FormatError, Format};
+    ///
+    /// // Create formatted strings and copy values
+    /// let mut format: Format<3> = "a:b:c".parse()?;
+    /// let other: Format<3> = "d:e%3Af:g".parse()?;
+    /// format.copy_from(&other, &[1, 2])?;
+    /// assert_eq!(format.as_str(), "a:e%3Af:g");
+    /// assert_eq!(format.get(1), "e:f");
+    /// assert!(format.is_encoded(1));
+    ///
+    /// // Copy values with out of bounds index
+    /// let res = format.copy_from(&other, &[0, 3]);
+    /// assert!(matches!(res, Err(FormatError::Index(3))));
+    /// assert_eq!(format.as_str(), "a:e%3Af:g");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_from(&mut self, other: &Self, indexes: &[usize]) -> Result {
+        let mut copied = 0u64;
+        for &index in indexes {
+            if index >= N {
+                return Err(Error::Index(index));
+            }
+            copied |= 1 << index;
+        }
+
+        // Assemble the formatted string from the copied and retained values
+        let parts = std::array::from_fn(|index| {
+            let source = if copied & (1 << index) == 0 {
+                &*self
+            } else {
+                other
+            };
+            (source.raw(index), source.is_encoded(index))
+        });
+        *self = assemble(parts)?;
+        Ok(())
+    }
+
+    /// Creates a formatted string from the values at the given indexes.
+    ///
+    /// The raw bytes of the values are copied together with their encoding
+    /// flags, so values are neither decoded nor encoded. Indexes can be given
+    /// in any order, and more than once.
+    ///
+    /// # Errors
+    ///
+    /// If an index is out of bounds, [`Error::Index`] is returned, and if the
+    /// formatted string would overflow, [`Error::TooLong`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::{Error as FormatError, Format};
+    ///
+    /// // Create formatted string and project values
+    /// let format: Format<4> = "a:b%3Ac:d:e".parse()?;
+    /// let projected: Format<3> = format.project([3, 1, 0])?;
+    /// assert_eq!(projected.as_str(), "e:b%3Ac:a");
+    /// assert_eq!(projected.get(1), "b:c");
+    ///
+    /// // Project values with out of bounds index
+    /// let res = format.project([0, 4]);
+    /// assert!(matches!(res, Err(FormatError::Index(4))));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn project<const M: usize>(
+        &self, indexes: [usize; M],
+    ) -> Result<Format<M, C>> {
+        if let Some(&index) = indexes.iter().find(|&&index| index >= N) {
+            return Err(Error::Index(index));
+        }
+        assemble(indexes.map(|index| (self.raw(index), self.is_encoded(index))))
+    }
+
+    /// Creates a formatted string by concatenating two formatted strings.
+    ///
+    /// The values of both formatted strings are copied together with their
+    /// encoding flags, so values are neither decoded nor encoded. The span
+    /// count of the resulting formatted string must be the sum of the span
+    /// counts of both formatted strings, which is checked at compile time.
+    ///
+    /// # Errors
+    ///
+    /// If the formatted string would overflow, [`Error::TooLong`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::{Error as FormatError, Format};
+    ///
+    /// // Create formatted strings and concatenate them
+    /// let a: Format<2> = "a:b%3Ac".parse()?;
+    /// let b: Format<3> = "d::e".parse()?;
+    /// let format: Format<5> = Format::concat(&a, &b)?;
+    /// assert_eq!(format.as_str(), "a:b%3Ac:d::e");
+    /// assert_eq!(format.get(1), "b:c");
+    ///
+    /// // Concatenate formatted strings that overflow
+    /// let long = "a".repeat(u16::MAX as usize / 2);
+    /// let a = Format::<2>::from_parts([long.as_str(), ""])?;
+    /// let res = Format::<2>::concat::<2, 4>(&a, &a);
+    /// assert!(matches!(res, Err(FormatError::TooLong { .. })));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Span counts that don't add up are rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Concatenate formatted strings into the wrong span count
+    /// let a = Format::<2>::new();
+    /// let format: Format<3> = Format::concat(&a, &a)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn concat<const M: usize, const K: usize>(
+        a: &Self, b: &Format<M, C>,
+    ) -> Result<Format<K, C>> {
+        const { assert!(K == N + M, "span count must be the sum") };
+        assemble(std::array::from_fn(|index| {
+            if index < N {
+                (a.raw(index), a.is_encoded(index))
+            } else {
+                (b.raw(index - N), b.is_encoded(index - N))
+            }
+        }))
+    }
+
+    /// Updates the value at the given index, if any.
+    ///
+    /// This is the non-panicking variant of [`Format::set`].
+    ///
+    /// # Errors
+    ///
+    /// If the index is out of bounds, [`Error::Index`] is returned, and if the
+    /// formatted string would overflow, [`Error::TooLong`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::{Error as FormatError, Format};
+    ///
+    /// // Create formatted string and insert values
+    /// let mut format = Format::<3>::new();
+    /// format.try_set(0, "a")?;
+    /// format.try_set(2, "b:c")?;
+    /// assert_eq!(format.as_str(), "a::b%3Ac");
+    ///
+    /// // Insert value at out of bounds index
+    /// let res = format.try_set(3, "d");
+    /// assert!(matches!(res, Err(FormatError::Index(3))));
+    /// assert_eq!(format.as_str(), "a::b%3Ac");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn try_set<S>(&mut self, index: usize, value: S) -> Result
+    where
+        S: AsRef<[u8]>,
+    {
+        if index < N {
+            self.set(index, value)
+        } else {
+            Err(Error::Index(index))
+        }
+    }
+
+    /// Returns the formatted string with the value at the given index updated.
+    ///
+    /// This is the consuming variant of [`Format::set`], which allows to derive
+    /// formatted strings in expression position.
+    ///
+    /// # Errors
+    ///
+    /// If the formatted string would overflow, [`Error::TooLong`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string with values
+    /// let format = Format::<3>::new().with(0, "a")?.with(2, "b:c")?;
+    /// assert_eq!(format.as_str(), "a::b%3Ac");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with<S>(mut self, index: usize, value: S) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set(index, value)?;
+        Ok(self)
+    }
+
+    /// Swaps the values at the given indexes.
+    ///
+    /// The raw bytes of both values are exchanged together with their encoding
+    /// flags, so values are neither decoded nor encoded, and the formatted
+    /// string is rewritten once. Since the total length doesn't change, this
+    /// can never overflow, and all spans in between are shifted accordingly.
+    ///
+    /// # Errors
+    ///
+    /// If an index is out of bounds, [`Error::Index`] is returned, and the
+    /// formatted string is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::{Error as FormatError, Format};
+    ///
+    /// // Create formatted string and swap values
+    /// let mut format: Format<4> = "a:bc:d%3Ae:f".parse()?;
+    /// format.swap(0, 2)?;
+    /// assert_eq!(format.as_str(), "d%3Ae:bc:a:f");
+    /// assert_eq!(format.get(0), "d:e");
+    /// assert_eq!(format.get(1), "bc");
+    /// assert!(format.is_encoded(0));
+    /// assert!(!format.is_encoded(2));
+    ///
+    /// // Swap values with out of bounds index
+    /// let res = format.swap(1, 4);
+    /// assert!(matches!(res, Err(FormatError::Index(4))));
+    /// assert_eq!(format.as_str(), "d%3Ae:bc:a:f");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Arbitrary sequences of swaps and takes keep all spans consistent:
+    ///
+    /// ```
+    /// # #[cfg(feature = "proptest")]
+    /// # fn main() {
+    /// use proptest::collection::vec;
+    /// use proptest::prelude::*;
+    /// use zrx_id::format::Format;
+    ///
+    /// // Apply swaps and takes, and refill emptied values, so spans of all
+    /// // lengths and encodings are moved around
+    /// let op = (0..5usize, 0..5usize, 0..3u8, 0..4usize);
+    /// proptest!(|(ops in vec(op, 0..64))| {
+    ///     let mut format: Format<5> = "a:bc::d%3Ae:fgh".parse().unwrap();
+    ///     let mut values: Vec<String> =
+    ///         format.iter().map(Into::into).collect();
+    ///     for (i, j, kind, value) in ops {
+    ///         match kind {
+    ///             0 => {
+    ///                 format.swap(i, j).unwrap();
+    ///                 values.swap(i, j);
+    ///             }
+    ///             1 => {
+    ///                 let value = std::mem::take(&mut values[i]);
+    ///                 prop_assert_eq!(format.take(i), value);
+    ///             }
+    ///             _ => {
+    ///                 let value = ["x", "y:z", "", "%3A"][value];
+    ///                 format.set(i, value).unwrap();
+    ///                 values[i] = value.to_string();
+    ///             }
+    ///         }
+    ///
+    ///         // Ensure separators, spans and values are consistent
+    ///         prop_assert_eq!(format.as_str().matches(':').count(), 4);
+    ///         let parsed: Format<5> = format.as_str().parse().unwrap();
+    ///         prop_assert_eq!(parsed, format.clone());
+    ///         let expected = values.iter().map(String::as_str);
+    ///         prop_assert!(format.iter().eq(expected));
+    ///     }
+    /// });
+    /// # }
+    /// # #[cfg(not(feature = "proptest"))]
+    /// # fn main() {}
+    /// ```
+    pub fn swap(&mut self, i: usize, j: usize) -> Result {
+        for index in [i, j] {
+            if index >= N {
+                return Err(Error::Index(index));
+            }
+        }
+
+        // Order indexes, and return early if there's nothing to swap
+        let (i, j) = (i.min(j), i.max(j));
+        if i == j {
+            return Ok(());
+        }
+
+        // Rewrite the range from the start of the first to the end of the
+        // second value, exchanging both values and retaining everything else
+        let (first, second) = (span(&self.ends, i), span(&self.ends, j));
+        let (lhs, rhs) = (Range::from(first), Range::from(second));
+        let mut bytes = Vec::with_capacity(rhs.end - lhs.start);
+        bytes.extend_from_slice(&self.value[rhs.clone()]);
+        bytes.extend_from_slice(&self.value[lhs.end..rhs.start]);
+        bytes.extend_from_slice(&self.value[lhs.clone()]);
+        self.value.splice(lhs.start..rhs.end, bytes);
+
+        // Shift the ends of the first and all spans in between, which is safe,
+        // since the ends of the second and all subsequent spans are unchanged
+        for end in &mut self.ends[i..j] {
+            *end = *end - first.len() + second.len();
+        }
+
+        // Exchange encoding flags, if they differ
+        if (self.flags >> i ^ self.flags >> j) & 1 != 0 {
+            self.flags ^= 1 << i | 1 << j;
+        }
+
+        // No errors occurred
+        Ok(())
+    }
+
+    /// Takes the value at the given index, leaving an empty value.
+    ///
+    /// The value is decoded, if necessary, and the span is cleared, shifting
+    /// all subsequent spans. Since the formatted string only shrinks, this
+    /// can never fail.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and take values
+    /// let mut format: Format<3> = "a:b%3Ac:d".parse()?;
+    /// assert_eq!(format.take(1), "b:c");
+    /// assert_eq!(format.take(2), "d");
+    /// assert_eq!(format.as_str(), "a::");
+    /// assert!(!format.is_encoded(1));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn take(&mut self, index: usize) -> String {
+        let value = self.get(index).into_owned();
+
+        // Clear the span, and shift the ends of the affected and subsequent
+        // spans, which can't underflow, as they are at least the span start
+        let span = span(&self.ends, index);
+        self.value.splice(span, []);
+        for end in &mut self.ends[index..] {
+            *end -= span.len();
+        }
+
+        // Clear encoding flag and return value
+        self.flags &= !(1 << index);
+        value
+    }
+
+    /// Reserves capacity for at least the given number of additional bytes.
+    ///
+    /// This is forwarded to the container as a hint, so containers that can't
+    /// grow ahead of time, e.g., fixed-size containers, might ignore it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and reserve capacity
+    /// let mut format = Format::<3, Vec<u8>>::new();
+    /// format.reserve(256);
+    /// assert!(format.into_inner().capacity() >= 258);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.value.reserve(additional);
+    }
+
+    /// Returns the string representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string
+    /// let mut format = Format::<3>::new();
+    /// format.set(0, "a")?;
+    /// format.set(1, "b")?;
+    /// format.set(2, "c")?;
+    ///
+    /// // Obtain string representation
+    /// assert_eq!(format.as_str(), "a:b:c");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: The value is guaranteed to be valid UTF-8, as it was created
+        // from a valid UTF-8 string, so we can just return a borrowed reference
+        unsafe { from_utf8_unchecked(&self.value) }
+    }
+
+    /// Returns the byte representation.
+    ///
+    /// This is the same as [`Format::as_str`], without the detour via UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and obtain byte representation
+    /// let format: Format<3> = "a:b%3Ac:d".parse()?;
+    /// assert_eq!(format.as_bytes(), b"a:b%3Ac:d");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Writes the string representation to the given writer.
+    ///
+    /// This avoids the intermediate [`String`] that is created when using
+    /// [`ToString::to_string`], as the formatted string is written as is.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the writer are propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::io::{self, Write};
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and write it
+    /// let format: Format<3> = "a:b%3Ac:d".parse()?;
+    /// let mut buffer = Vec::new();
+    /// format.write_to(&mut buffer)?;
+    /// assert_eq!(buffer, b"a:b%3Ac:d");
+    ///
+    /// // Define writer that always fails
+    /// struct Failing;
+    /// impl Write for Failing {
+    ///     fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+    ///         Err(io::ErrorKind::BrokenPipe.into())
+    ///     }
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// // Ensure errors are propagated
+    /// let err = format.write_to(&mut Failing).unwrap_err();
+    /// assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    /// let err = format.write_decoded_to(&mut Failing).unwrap_err();
+    /// assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn write_to<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        writer.write_all(&self.value)
+    }
+
+    /// Writes the string representation to the given formatter.
+    ///
+    /// This is the [`fmt::Write`] variant of [`Format::write_to`].
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the writer are propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::fmt::{self, Write};
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and write it
+    /// let format: Format<3> = "a:b%3Ac:d".parse()?;
+    /// let mut buffer = String::from("> ");
+    /// format.fmt_to(&mut buffer)?;
+    /// assert_eq!(buffer, "> a:b%3Ac:d");
+    ///
+    /// // Define writer that always fails
+    /// struct Failing;
+    /// impl Write for Failing {
+    ///     fn write_str(&mut self, _: &str) -> fmt::Result {
+    ///         Err(fmt::Error)
+    ///     }
+    /// }
+    ///
+    /// // Ensure errors are propagated
+    /// assert!(format.fmt_to(&mut Failing).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn fmt_to<W>(&self, writer: &mut W) -> fmt::Result
+    where
+        W: fmt::Write,
+    {
+        writer.write_str(self.as_str())
+    }
+
+    /// Writes the decoded values to the given writer.
+    ///
+    /// This is intended for human-readable output, e.g., for dumps, as values
+    /// are decoded one after another and separated by `:`, where `:` and `\`
+    /// characters contained in values are escaped with a `\`, so the output
+    /// remains unambiguous. Values are streamed without intermediate buffers,
+    /// except for percent-encoded values, which must be decoded first.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the writer are propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and write decoded values
+    /// let format = Format::<3>::from_parts(["a", "b:c", "d\\e"])?;
+    /// let mut buffer = Vec::new();
+    /// format.write_decoded_to(&mut buffer)?;
+    /// assert_eq!(buffer, b"a:b\\:c:d\\\\e");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_decoded_to<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        for (index, value) in self.iter().enumerate() {
+            if index > 0 {
+                writer.write_all(b":")?;
+            }
+
+            // Write value, escaping separators and escape characters
+            let mut rest = value.as_bytes();
+            while let Some(at) =
+                rest.iter().position(|&byte| matches!(byte, b':' | b'\\'))
+            {
+                writer.write_all(&rest[..at])?;
+                writer.write_all(&[b'\\', rest[at]])?;
+                rest = &rest[at + 1..];
+            }
+            writer.write_all(rest)?;
+        }
+
+        // No errors occurred
+        Ok(())
+    }
+
+    /// Writes the labeled and decoded values to the given formatter.
+    ///
+    /// This is intended for human-readable output, e.g., for the alternate
+    /// [`fmt::Display`] mode of types built on top of formatted strings, as
+    /// values are decoded and written as `label=value` pairs on a single line,
+    /// separated by spaces. Empty values, as well as values with empty labels,
+    /// are omitted. Note that the output is not meant to be parsed.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the writer are propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and write labeled values
+    /// let format = Format::<4>::from_parts(["x", "a", "", "b:c"])?;
+    /// let mut buffer = String::new();
+    /// format.fmt_components(&mut buffer, ["", "first", "second", "third"])?;
+    /// assert_eq!(buffer, "first=a third=b:c");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fmt_components<W>(
+        &self, writer: &mut W, labels: [&str; N],
+    ) -> fmt::Result
+    where
+        W: fmt::Write,
+    {
+        let mut first = true;
+        for (index, label) in labels.into_iter().enumerate() {
+            if label.is_empty() || self.is_component_empty(index) {
+                continue;
+            }
+
+            // Write separator, unless this is the first written value
+            if !first {
+                writer.write_char(' ')?;
+            }
+            first = false;
+
+            // Write label and decoded value
+            writer.write_str(label)?;
+            writer.write_char('=')?;
+            writer.write_str(&self.get(index))?;
+        }
+
+        // No errors occurred
+        Ok(())
+    }
+
+    /// Converts the formatted string into its container.
+    ///
+    /// Note that the container holds the full formatted string, including all
+    /// `:` separators and percent-encoded values as is, i.e., exactly what is
+    /// returned by [`Format::as_bytes`], and not any individual value. It can
+    /// be parsed back into an equal formatted string with [`FromStr`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::str::from_utf8;
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and convert into container
+    /// let format: Format<3, Vec<u8>> = "a:b%3Ac:".parse()?;
+    /// let bytes = format.clone().into_inner();
+    /// assert_eq!(bytes, b"a:b%3Ac:");
+    ///
+    /// // Ensure container round-trips byte for byte
+    /// let parsed: Format<3, Vec<u8>> = from_utf8(&bytes)?.parse()?;
+    /// assert_eq!(parsed, format);
+    /// assert_eq!(parsed.as_bytes(), bytes);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> C {
+        self.value
+    }
+
+    /// Returns the stable 64-bit hash of the string representation.
+    ///
+    /// Other than [`Hash`], this hash is computed with FNV-1a, and is thus
+    /// stable across processes, platforms and versions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and compute stable hash
+    /// let format: Format<3> = "a:b:c".parse()?;
+    /// assert_eq!(format.stable_hash(), 0x8672_f05b_cfb8_f097);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn stable_hash(&self) -> u64 {
+        fnv1a_64(&self.value)
+    }
+
+    /// Returns the stable 128-bit hash of the string representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and compute stable hash
+    /// let format: Format<3> = "a:b:c".parse()?;
+    /// assert_eq!(
+    ///     format.stable_hash128(),
+    ///     0x3505_80ba_8783_d94f_7081_4551_7c1c_e9b7
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn stable_hash128(&self) -> u128 {
+        fnv1a_128(&self.value)
+    }
+
+    /// Returns the fingerprint of the string representation.
+    ///
+    /// This is the same as [`Format::stable_hash`], i.e., the 64-bit FNV-1a
+    /// hash of the string representation, using the standard offset basis as
+    /// the seed, and is meant for keys in compact on-disk indexes and logs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string and compute fingerprint
+    /// let format: Format<3> = "a:b:c".parse()?;
+    /// assert_eq!(format.fingerprint(), format.stable_hash());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn fingerprint(&self) -> u64 {
+        self.stable_hash()
+    }
+
+    /// Returns the byte range of the value at the given index.
+    ///
+    /// The range refers to the string representation returned by
+    /// [`Format::as_str`], and includes percent-encoded characters as is. It
+    /// remains valid until the next mutation of the formatted string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string
+    /// let mut format = Format::<3>::new();
+    /// format.set(0, "a")?;
+    /// format.set(1, "b:c")?;
+    ///
+    /// // Obtain byte ranges of values
+    /// assert_eq!(format.span(0), 0..1);
+    /// assert_eq!(format.span(1), 2..7);
+    /// assert_eq!(&format.as_str()[format.span(1)], "b%3Ac");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn span(&self, index: usize) -> Range<usize> {
+        span(&self.ends, index).into()
+    }
+
+    /// Returns the length of the value at the given index.
+    ///
+    /// The length refers to the string representation returned by
+    /// [`Format::as_str`], and includes percent-encoded characters as is, so
+    /// it is obtained in constant time, without decoding the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string
+    /// let mut format = Format::<3>::new();
+    /// format.set(0, "a")?;
+    /// format.set(1, "b:c")?;
+    ///
+    /// // Obtain lengths of values
+    /// assert_eq!(format.component_len(0), 1);
+    /// assert_eq!(format.component_len(1), 5);
+    /// assert_eq!(format.component_len(2), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn component_len(&self, index: usize) -> usize {
+        usize::from(span(&self.ends, index).len())
+    }
+
+    /// Returns whether the value at the given index is empty.
+    ///
+    /// Since percent-encoded values can never be empty, this is equivalent to
+    /// checking whether the decoded value is empty, but never decodes it.
     ///
     /// # Panics
     ///
-    /// Panics if the span count is greater than 64.
+    /// Panics if the index is out of bounds.
     ///
     /// # Examples
     ///
@@ -114,31 +1632,30 @@ where
     ///
     /// // Create formatted string
     /// let mut format = Format::<3>::new();
-    /// format.set(0, "a")?;
+    /// format.set(1, ":")?;
+    ///
+    /// // Check if values are empty
+    /// assert!(format.is_component_empty(0));
+    /// assert!(!format.is_component_empty(1));
+    /// assert!(format.is_component_empty(2));
     /// # Ok(())
     /// # }
     /// ```
+    #[inline]
     #[must_use]
-    pub fn new() -> Self {
-        debug_assert!(N <= 64, "span count must be <= 64");
-        Self {
-            value: C::from(&[b':'; N][1..]), // N - 1
-            spans: init::<N>(),
-            flags: 0,
-        }
+    pub fn is_component_empty(&self, index: usize) -> bool {
+        self.component_len(index) == 0
     }
 
-    /// Returns the value at the given index.
+    /// Returns whether the value at the given index is percent-encoded.
     ///
-    /// If the value is not percent-encoded, which means it does not contain a
-    /// `:` character, a borrowed reference is returned which is essentially a
-    /// zero-cost operation and expected to be the common case. Otherwise, the
-    /// value is percent-decoded and an owned value is returned.
+    /// Values that are percent-encoded must be decoded when obtained with
+    /// [`Format::get`], which means that a borrowed reference is only possible
+    /// for values that are not percent-encoded.
     ///
     /// # Panics
     ///
-    /// Panics if the index is out of bounds. Since [`Format`] is a low-level
-    /// construct, we don't expect this to happen, as indexes should be known.
+    /// Panics if the index is out of bounds.
     ///
     /// # Examples
     ///
@@ -150,31 +1667,40 @@ where
     /// // Create formatted string
     /// let mut format = Format::<3>::new();
     /// format.set(0, "a")?;
+    /// format.set(1, "b:c")?;
+    /// assert!(!format.is_encoded(0));
+    /// assert!(format.is_encoded(1));
+    /// assert!(!format.is_encoded(2));
     ///
-    /// // Obtain value at index
-    /// let value = format.get(0);
-    /// assert_eq!(value, "a");
+    /// // Update value, which resets the encoding flag
+    /// format.set(1, "b")?;
+    /// assert!(!format.is_encoded(1));
+    ///
+    /// // Create formatted string from string with percent-encoded value
+    /// let format: Format::<3> = "a:b%3Ac:d%".parse()?;
+    /// assert!(!format.is_encoded(0));
+    /// assert!(format.is_encoded(1));
+    /// assert!(!format.is_encoded(2));
     /// # Ok(())
     /// # }
     /// ```
-    pub fn get(&self, index: usize) -> Cow<str> {
-        let range: Range<_> = self.spans[index].into();
-        if self.flags & (1 << index) == 0 {
-            // SAFETY: The value is guaranteed to be valid UTF-8, as it was
-            // created from a valid UTF-8 string. Additionally, the value is
-            // not percent-encoded, so we can just return a borrowed reference
-            // to the formatted string value, which is the common fast path.
-            unsafe { Cow::Borrowed(from_utf8_unchecked(&self.value[range])) }
-        } else {
-            decode(&self.value[range])
-        }
+    #[inline]
+    #[must_use]
+    pub fn is_encoded(&self, index: usize) -> bool {
+        assert!(index < N, "index out of bounds: {index}");
+        self.flags & (1 << index) != 0
     }
 
-    /// Updates the value at the given index.
+    /// Returns the raw bytes of the value at the given index.
     ///
-    /// # Errors
+    /// Other than [`Format::get`], this method never decodes the value, and
+    /// returns it as contained in the formatted string, which allows to defer
+    /// decoding to the caller, e.g., in combination with
+    /// [`Format::is_encoded`].
+    ///
+    /// # Panics
     ///
-    /// If the span overflows, [`Error::Length`] is returned.
+    /// Panics if the index is out of bounds.
     ///
     /// # Examples
     ///
@@ -183,49 +1709,97 @@ where
     /// # fn main() -> Result<(), Box<dyn Error>> {
     /// use zrx_id::format::Format;
     ///
-    /// // Create formatted string and insert value
+    /// // Create formatted string
     /// let mut format = Format::<3>::new();
     /// format.set(0, "a")?;
+    /// format.set(1, "b:c")?;
+    ///
+    /// // Obtain raw bytes of values
+    /// assert_eq!(format.raw(0), b"a");
+    /// assert_eq!(format.raw(1), b"b%3Ac");
+    /// assert_eq!(format.raw(2), b"");
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set<S>(&mut self, index: usize, value: S) -> Result
-    where
-        S: AsRef<[u8]>,
-    {
-        let value = encode(value.as_ref());
-
-        // Now, check if the value is borrowed or owned. If it is borrowed, it
-        // means that no encoding was necessary, and we can just return a slice
-        // of the formatted string when required. Otherwise, at least one byte
-        // was encoded, so we set the flag to indicate the need for decoding.
-        match value {
-            Cow::Borrowed(_) => self.flags &= !(1 << index),
-            Cow::Owned(_) => self.flags |= 1 << index,
-        }
+    #[inline]
+    #[must_use]
+    pub fn raw(&self, index: usize) -> &[u8] {
+        &self.value[self.span(index)]
+    }
 
-        // Replace value in affected span
-        self.value.splice(self.spans[index], value.as_ref());
-
-        // Compute the difference in lengths of the new and prior value, as we
-        // need to shift the end of the affected span, as well as the start and
-        // end of all subsequent spans in order to maintain a valid format
-        let by = i16::try_from(value.len())
-            .ok()
-            .and_then(|len| len.checked_sub_unsigned(self.spans[index].len()))
-            .ok_or(Error::Length)?;
-
-        // Shift affected span and all subsequent spans
-        self.spans[index].shift_end(by)?;
-        for i in (index + 1)..N {
-            self.spans[i].shift(by)?;
-        }
+    /// Returns an iterator over all values.
+    ///
+    /// Values are obtained lazily with [`Format::get`], so values that are not
+    /// percent-encoded are borrowed, and only percent-encoded values are
+    /// decoded, once they are reached. The iterator always yields exactly `N`
+    /// values, and can also be consumed from the back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted string from string
+    /// let format: Format::<3> = "a:b%3Ac:".parse()?;
+    ///
+    /// // Obtain all values
+    /// let values: Vec<_> = format.iter().collect();
+    /// assert_eq!(values, ["a", "b:c", ""]);
+    /// assert_eq!(format.iter().len(), 3);
+    ///
+    /// // Obtain all values in reverse
+    /// let values: Vec<_> = format.iter().rev().collect();
+    /// assert_eq!(values, ["", "b:c", "a"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn iter(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = Cow<'_, str>> + ExactSizeIterator {
+        (0..N).map(|index| self.get(index))
+    }
 
-        // No errors occurred
-        Ok(())
+    /// Orders two formatted strings by their decoded values.
+    ///
+    /// Other than [`Ord`], which compares the raw bytes for efficiency, this
+    /// method compares values one after another after decoding, so separators
+    /// and percent-encoded characters don't influence the ordering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::cmp::Ordering;
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create and compare formatted strings with encoded values
+    /// let a: Format::<2> = "a%3Ab:c".parse()?;
+    /// let b: Format::<2> = "a-b:c".parse()?;
+    /// assert_eq!(a.cmp(&b), Ordering::Less);
+    /// assert_eq!(a.cmp_decoded(&b), Ordering::Greater);
+    ///
+    /// // Create and compare formatted strings with prefix values
+    /// let a: Format::<2> = "a/b:c".parse()?;
+    /// let b: Format::<2> = "a:c".parse()?;
+    /// assert_eq!(a.cmp(&b), Ordering::Less);
+    /// assert_eq!(a.cmp_decoded(&b), Ordering::Greater);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn cmp_decoded(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
     }
 
-    /// Returns the string representation.
+    /// Returns an iterator over the byte ranges of all values.
+    ///
+    /// The ranges refer to the string representation returned by
+    /// [`Format::as_str`], and remain valid until the next mutation of the
+    /// formatted string.
     ///
     /// # Examples
     ///
@@ -234,22 +1808,21 @@ where
     /// # fn main() -> Result<(), Box<dyn Error>> {
     /// use zrx_id::format::Format;
     ///
-    /// // Create formatted string
-    /// let mut format = Format::<3>::new();
-    /// format.set(0, "a")?;
-    /// format.set(1, "b")?;
-    /// format.set(2, "c")?;
+    /// // Create formatted string from string
+    /// let format: Format::<3> = "a:bc:".parse()?;
     ///
-    /// // Obtain string representation
-    /// assert_eq!(format.as_str(), "a:b:c");
+    /// // Obtain byte ranges of all values
+    /// let spans: Vec<_> = format.spans().collect();
+    /// assert_eq!(spans, [0..1, 2..4, 5..5]);
+    /// for (index, range) in format.spans().enumerate() {
+    ///     assert_eq!(&format.as_str()[range], format.get(index));
+    /// }
     /// # Ok(())
     /// # }
     /// ```
     #[inline]
-    pub fn as_str(&self) -> &str {
-        // SAFETY: The value is guaranteed to be valid UTF-8, as it was created
-        // from a valid UTF-8 string, so we can just return a borrowed reference
-        unsafe { from_utf8_unchecked(&self.value) }
+    pub fn spans(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        (0..N).map(|index| span(&self.ends, index).into())
     }
 }
 
@@ -265,6 +1838,14 @@ where
 
     /// Attempts to create a formatted string from a string.
     ///
+    /// Percent-encoded characters are canonicalized to uppercase hexadecimal
+    /// digits, which is also what the encoder emits, so that formatted strings
+    /// which only differ in the casing of hexadecimal digits compare equal and
+    /// hash identically. No other canonicalization is applied, so encoded
+    /// characters that don't need encoding, unencoded non-ASCII characters and
+    /// a literal `%` are retained as given, and compare unequal to what the
+    /// encoder emits for the same values.
+    ///
     /// # Errors
     ///
     /// If the span count is off, [`Error::Cardinality`] is returned.
@@ -281,62 +1862,96 @@ where
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// Percent-encoded characters with lowercase hexadecimal digits are
+    /// canonicalized:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted strings from strings with different casing
+    /// let a: Format<2> = "a%3ab%2f:c%3A%c3%a9".parse()?;
+    /// let b: Format<2> = "a%3Ab%2F:c%3A%C3%A9".parse()?;
+    /// assert_eq!(a.as_str(), "a%3Ab%2F:c%3A%C3%A9");
+    /// assert_eq!(a, b);
+    ///
+    /// // Ensure sequences that aren't percent-encoded are retained
+    /// let format: Format<2> = "%%3a%ag:%zz".parse()?;
+    /// assert_eq!(format.as_str(), "%%3A%ag:%zz");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Other spellings of the same values are retained, and only compare equal
+    /// by their decoded values:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::Format;
+    ///
+    /// // Create formatted strings from different spellings of the same values
+    /// for (value, encoded) in [
+    ///     ("a%41.md", "aA.md"),
+    ///     ("café.md", "caf%C3%A9.md"),
+    ///     ("100%.md", "100%25.md"),
+    /// ] {
+    ///     let a: Format<1> = value.parse()?;
+    ///     let mut b = Format::<1>::default();
+    ///     b.set(0, a.get(0).as_bytes())?;
+    ///     assert_eq!(b.as_str(), encoded);
+    ///
+    ///     // Ensure formatted strings differ, but their values don't
+    ///     assert_ne!(a, b);
+    ///     assert_eq!(a.get(0), b.get(0));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Strings with too many separators are rejected:
+    ///
+    /// ```
+    /// use zrx_id::format::{Error, Format};
+    ///
+    /// // Ensure strings with N, N + 1 and many more separators are rejected
+    /// let many = ":".repeat(1000);
+    /// for value in ["a:b:c:d", "a:b:c:d:e", many.as_str()] {
+    ///     let res = value.parse::<Format<3>>();
+    ///     assert!(matches!(res, Err(Error::Cardinality { .. })), "{value}");
+    /// }
+    ///
+    /// // Obtain number of found and expected separators from error
+    /// let err = "a:b:c:d".parse::<Format<3>>().unwrap_err();
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "invalid span count: found 3 separators, expected 2 in \"a:b:c:d\""
+    /// );
+    /// ```
+    ///
+    /// Arbitrary strings never panic, and are accepted if, and only if, they
+    /// contain exactly the expected number of separators:
+    ///
+    /// ```
+    /// # #[cfg(feature = "proptest")]
+    /// # fn main() {
+    /// use proptest::prelude::*;
+    /// use zrx_id::format::Format;
+    ///
+    /// // Ensure colon-heavy strings are only accepted with two separators
+    /// proptest!(|(value in "[a:%3A]{0,16}")| {
+    ///     let count = value.matches(':').count();
+    ///     prop_assert_eq!(value.parse::<Format<3>>().is_ok(), count == 2);
+    /// });
+    /// # }
+    /// # #[cfg(not(feature = "proptest"))]
+    /// # fn main() {}
+    /// ```
+    #[inline]
     fn from_str(value: &str) -> Result<Self> {
-        let mut format = Format::new();
-        format.value = C::from(value.as_bytes());
-
-        // Initialize start and span counter
-        let mut start = 0u16;
-        let mut index = 0;
-        let mut shift = 1;
-
-        // Compute spans from characters
-        for (i, char) in value.char_indices() {
-            match char {
-                // If the current character is a separator, finalize the span.
-                // It's very unlikely that any conversion here results in an
-                // error, but since identifiers might potentially contain user
-                // data, we handle it and return an error.
-                ':' => {
-                    let end = u16::try_from(i).map_err(|_| Error::Length)?;
-
-                    // Finalize current span
-                    format.spans[index] = Span::new(start, end);
-                    index += 1;
-
-                    // Continue after separator
-                    start = end + 1;
-                    shift = 1 << index;
-                }
-
-                // If the current span contains a percent sign, and we haven't
-                // already marked the span as percent-encoded, check if the next
-                // two characters are valid hexadecimal digits. If so, mark it
-                // as percent-encoded. Otherwise, proceed without modification.
-                '%' if format.flags & shift == 0 => {
-                    let bytes = value.as_bytes();
-                    if let Some(&[b1, b2]) = bytes.get(i + 1..i + 3) {
-                        if b1.is_ascii_hexdigit() && b2.is_ascii_hexdigit() {
-                            format.flags |= shift;
-                        }
-                    }
-                }
-
-                // Consume all other characters
-                _ => {}
-            }
-        }
-
-        // Finalize last span
-        let end = u16::try_from(value.len()).map_err(|_| Error::Length)?;
-        format.spans[index] = Span::new(start, end);
-
-        // Return format or error on incorrect span count
-        if index == N - 1 {
-            Ok(format)
-        } else {
-            Err(Error::Cardinality)
-        }
+        parse::<N, false, _, _, _>(value.as_bytes(), N, |_, _| Ok(()))
     }
 }
 
@@ -347,9 +1962,12 @@ where
     C: Container,
 {
     /// Hashes the formatted string.
+    ///
+    /// The string representation is hashed, and not the underlying container,
+    /// so the hash is consistent with [`str`], allowing for borrowed lookups.
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.value.hash(state);
+        self.as_str().hash(state);
     }
 }
 
@@ -480,8 +2098,380 @@ where
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Format")
             .field("value", &self.as_str())
-            .field("spans", &self.spans)
+            .field("ends", &self.ends)
             .field("flags", &self.flags)
             .finish()
     }
 }
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Parses a formatted string, checking each byte.
+///
+/// Spans are computed from separators in a single pass over the bytes, which
+/// is safe, since `:` and `%` are ASCII characters, and thus can never be part
+/// of a multi-byte sequence in a valid UTF-8 string. If there are at least
+/// `min` values, missing trailing values are appended as empty values.
+///
+/// If `UTF8` is set, the bytes are additionally validated as UTF-8 during the
+/// same pass. ASCII bytes are always valid, so only runs of non-ASCII bytes
+/// are handed to [`from_utf8`], once the next ASCII byte is reached.
+fn parse<const N: usize, const UTF8: bool, C, F, E>(
+    bytes: &[u8], min: usize, mut check: F,
+) -> result::Result<Format<N, C>, E>
+where
+    C: Container,
+    F: FnMut(usize, u8) -> result::Result<(), E>,
+    E: From<Error>,
+{
+    // If the value is too long, we can't represent it, but the check must be
+    // applied anyway, as its errors take precedence. Otherwise, we know that
+    // all offsets fit, which allows us to skip all conversions below.
+    let Ok(len) = u16::try_from(bytes.len()) else {
+        bytes
+            .iter()
+            .enumerate()
+            .try_for_each(|(offset, &byte)| check(offset, byte))?;
+        if UTF8 {
+            validate_utf8(bytes, 0)?;
+        }
+        let len = bytes.len();
+        return Err(Error::TooLong { len, max: MAX_LEN }.into());
+    };
+
+    // Initialize span counter
+    const { check_spans::<N>() };
+    let mut ends = [0; N];
+    let mut flags = 0;
+    let mut index = 0;
+    let mut shift = 1;
+
+    // Initialize start of current run of non-ASCII bytes, if any, and whether
+    // there are percent-encoded characters with lowercase hexadecimal digits
+    let mut run = None;
+    let mut lowercase = false;
+
+    // Compute spans from bytes
+    for (i, &byte) in bytes.iter().enumerate() {
+        check(i, byte)?;
+        if UTF8 {
+            if byte.is_ascii() {
+                if let Some(start) = run.take() {
+                    validate_utf8(&bytes[start..i], start)?;
+                }
+            } else if run.is_none() {
+                run = Some(i);
+            }
+        }
+        match byte {
+            // If the current byte is a separator, finalize the span, unless
+            // it's the last span, which means there are too many separators.
+            // In that case, the check must still be applied to all remaining
+            // bytes, as its errors take precedence over format errors, and
+            // we count the remaining separators for the error message.
+            b':' => {
+                if index == N - 1 {
+                    let mut found = N;
+                    for (j, &byte) in bytes[i + 1..].iter().enumerate() {
+                        check(i + 1 + j, byte)?;
+                        found += usize::from(byte == b':');
+                    }
+                    if UTF8 {
+                        validate_utf8(&bytes[i + 1..], i + 1)?;
+                    }
+                    return Err(Error::Cardinality {
+                        found,
+                        expected: N - 1,
+                        value: String::from_utf8_lossy(bytes).into_owned(),
+                    }
+                    .into());
+                }
+
+                #[allow(clippy::cast_possible_truncation)]
+                let end = i as u16;
+
+                // Finalize current span
+                ends[index] = end;
+                index += 1;
+
+                // Continue after separator
+                shift = 1 << index;
+            }
+
+            // If the current span contains a percent sign, check if the next
+            // two bytes are valid hexadecimal digits. If so, mark the span as
+            // percent-encoded, and remember whether the digits need to be
+            // uppercased. Otherwise, proceed without modification.
+            b'%' => {
+                if let Some(&[b1, b2]) = bytes.get(i + 1..i + 3) {
+                    if b1.is_ascii_hexdigit() && b2.is_ascii_hexdigit() {
+                        flags |= shift;
+                        lowercase |=
+                            b1.is_ascii_lowercase() || b2.is_ascii_lowercase();
+                    }
+                }
+            }
+
+            // Consume all other bytes
+            _ => {}
+        }
+    }
+
+    // Validate trailing run of non-ASCII bytes, if any
+    if let Some(start) = run {
+        validate_utf8(&bytes[start..], start)?;
+    }
+
+    // Finalize last span
+    ends[index] = len;
+
+    // Uppercase hexadecimal digits of percent-encoded characters, so that
+    // equivalent formatted strings share the same canonical representation,
+    // which is rare, so we only copy the bytes if it's necessary
+    let container = || {
+        if lowercase {
+            C::from(&uppercase(bytes))
+        } else {
+            C::from(bytes)
+        }
+    };
+
+    // Return format or error on incorrect span count
+    if index == N - 1 {
+        Ok(Format {
+            value: container(),
+            ends,
+            flags,
+        })
+
+    // Append separators for missing trailing values, which must not exceed
+    // the maximum length, since we need to shift the ends accordingly
+    } else if index + 1 >= min {
+        check_len(usize::from(len) + N - 1 - index)?;
+        let mut value = container();
+        value.reserve(N - 1 - index);
+        for (by, end) in (1..).zip(&mut ends[index + 1..]) {
+            value.extend(b":");
+            *end = len + by;
+        }
+        Ok(Format { value, ends, flags })
+    } else {
+        Err(Error::Cardinality {
+            found: index,
+            expected: min - 1,
+            value: String::from_utf8_lossy(bytes).into_owned(),
+        }
+        .into())
+    }
+}
+
+/// Uppercases the hexadecimal digits of all percent-encoded characters.
+///
+/// Percent-encoded characters never overlap, since a `%` is never a valid
+/// hexadecimal digit, so each `%` can be checked independently.
+fn uppercase(bytes: &[u8]) -> Vec<u8> {
+    let mut value = bytes.to_vec();
+    for i in 0..value.len().saturating_sub(2) {
+        if value[i] == b'%'
+            && value[i + 1].is_ascii_hexdigit()
+            && value[i + 2].is_ascii_hexdigit()
+        {
+            value[i + 1..i + 3].make_ascii_uppercase();
+        }
+    }
+    value
+}
+
+/// Returns whether the given bytes are a valid percent-encoded value.
+///
+/// This is the case if the bytes contain at least one `%`, and all `%` are
+/// followed by two uppercase hexadecimal digits, which is how the encoder
+/// emits them, and what parsing canonicalizes to.
+fn is_raw(value: &[u8]) -> bool {
+    let mut found = false;
+    for (i, &byte) in value.iter().enumerate() {
+        if byte == b'%' {
+            match value.get(i + 1..i + 3) {
+                Some(&[b1, b2]) if is_upper_hex(b1) && is_upper_hex(b2) => {
+                    found = true;
+                }
+                _ => return false,
+            }
+        }
+    }
+    found
+}
+
+/// Returns whether the given byte is an uppercase hexadecimal digit.
+fn is_upper_hex(byte: u8) -> bool {
+    matches!(byte, b'0'..=b'9' | b'A'..=b'F')
+}
+
+/// Validates that the given bytes are UTF-8, which start at the given offset.
+#[inline]
+fn validate_utf8(bytes: &[u8], offset: usize) -> Result {
+    match from_utf8(bytes) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::Utf8 {
+            offset: offset + err.valid_up_to(),
+        }),
+    }
+}
+
+/// Ensures that the given length doesn't exceed the maximum length.
+///
+/// # Errors
+///
+/// If the length exceeds [`MAX_LEN`], [`Error::TooLong`] is returned.
+#[inline]
+pub(crate) fn check_len(len: usize) -> Result {
+    if len > MAX_LEN {
+        Err(Error::TooLong { len, max: MAX_LEN })
+    } else {
+        Ok(())
+    }
+}
+
+/// Assembles a formatted string from raw parts and their encoding flags.
+///
+/// This function is shared among all methods that copy values between
+/// formatted strings, writing all raw parts with `:` separators in a single
+/// pass, after checking that the span ends can represent them.
+fn assemble<const N: usize, C>(
+    parts: [(&[u8], bool); N],
+) -> Result<Format<N, C>>
+where
+    C: Container,
+{
+    const { check_spans::<N>() };
+    let capacity = parts.iter().map(|(part, _)| part.len() + 1).sum::<usize>();
+    check_len(capacity - 1)?;
+
+    // Write all parts with `:` separators to the container, and record the
+    // span ends, as well as the encoding flags
+    let mut value = C::from(&[]);
+    value.reserve(capacity);
+    let mut ends = [0; N];
+    let mut flags = 0;
+    for (index, (part, encoded)) in parts.into_iter().enumerate() {
+        if index > 0 {
+            value.extend(b":");
+        }
+        value.extend(part);
+
+        // Since the total length was checked, truncation can't happen
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            ends[index] = value.len() as u16;
+        }
+        if encoded {
+            flags |= 1 << index;
+        }
+    }
+
+    // Return formatted string
+    Ok(Format { value, ends, flags })
+}
+
+/// Returns the span at the given index from a set of span ends.
+///
+/// The start of the first span is always `0`, and the start of every other span
+/// is the end of the previous span plus one, which skips the separator.
+#[inline]
+fn span(ends: &[u16], index: usize) -> Span {
+    let start = index.checked_sub(1).map_or(0, |prev| ends[prev] + 1);
+    Span::new(start, ends[index])
+}
+
+/// Ensures that the span count is supported.
+///
+/// This is a `const` function that must be evaluated in a `const` block by all
+/// constructors, so unsupported span counts fail to compile, instead of
+/// silently corrupting the encoding flags, which have room for 64 spans.
+const fn check_spans<const N: usize>() {
+    assert!(0 < N && N <= 64, "span count must be in 1..=64");
+}
+
+/// Initializes a set of span ends.
+///
+/// This is a `const` function that allows to create a set of ends of empty
+/// spans which are spaced by a separator, executed at compile time. It also
+/// ensures that the span count is supported.
+#[allow(clippy::cast_possible_truncation)]
+const fn init_ends<const N: usize>() -> [u16; N] {
+    check_spans::<N>();
+    let mut ends = [0; N];
+    let mut index = 0;
+    while index < N {
+        ends[index] = index as u16;
+        index += 1;
+    }
+    ends
+}
+
+/// Replaces the value at the given index in a set of span ends.
+///
+/// This function is shared among all formatted string variants, and encodes
+/// the given value, replaces the affected span in the buffer, updates the
+/// encoding flags, and shifts all subsequent span ends to maintain a valid
+/// format. If the formatted string would overflow, it is left unchanged.
+fn replace<C>(
+    buffer: &mut C, ends: &mut [u16], flags: &mut u64, index: usize,
+    value: &[u8],
+) -> Result
+where
+    C: Container,
+{
+    // Now, check if the value is borrowed or owned. If it is borrowed, it
+    // means that no encoding was necessary, and we can just return a slice
+    // of the formatted string when required. Otherwise, at least one byte
+    // was encoded, so we set the flag to indicate the need for decoding.
+    let value = encode(value);
+    let encoded = matches!(value, Cow::Owned(_));
+    replace_raw(buffer, ends, flags, index, value.as_bytes(), encoded)
+}
+
+/// Replaces the value at the given index with raw bytes.
+///
+/// The bytes are written as is, and the encoding flag is set as given, which
+/// means that the caller must ensure that the bytes are a valid span. All
+/// subsequent span ends are shifted. If the formatted string would overflow,
+/// it is left unchanged.
+fn replace_raw<C>(
+    buffer: &mut C, ends: &mut [u16], flags: &mut u64, index: usize,
+    value: &[u8], encoded: bool,
+) -> Result
+where
+    C: Container,
+{
+    let span = span(ends, index);
+
+    // Compute the total length after replacing the value, as we need to shift
+    // the end of the affected span, as well as the ends of all subsequent
+    // spans. Since ends are monotonic, it's sufficient to check whether the
+    // last end overflows, before we touch anything.
+    let end = usize::from(ends[ends.len() - 1]);
+    check_len(end - usize::from(span.len()) + value.len())?;
+
+    // Update encoding flag, to indicate whether the value must be decoded
+    if encoded {
+        *flags |= 1 << index;
+    } else {
+        *flags &= !(1 << index);
+    }
+
+    // Replace value in affected span, and shift affected and subsequent ends
+    // Since the total length was checked, truncation can't happen, and all
+    // shifted ends are guaranteed to fit, so wrapping can only be temporary
+    #[allow(clippy::cast_possible_truncation)]
+    let (add, sub) = (value.len() as u16, span.len());
+    buffer.splice(span, value);
+    for end in &mut ends[index..] {
+        *end = end.wrapping_add(add).wrapping_sub(sub);
+    }
+
+    // No errors occurred
+    Ok(())
+}
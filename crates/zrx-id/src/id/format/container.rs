@@ -47,6 +47,37 @@ where
     where
         R: Into<Range<usize>>,
         S: AsRef<[u8]>;
+
+    /// Reserve capacity for at least the given number of additional bytes.
+    ///
+    /// This is only a hint, which is why the default implementation does
+    /// nothing, but it allows to avoid reallocations when writing parts.
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+
+    /// Append the given value at the end.
+    #[inline]
+    fn extend<S>(&mut self, value: S)
+    where
+        S: AsRef<[u8]>,
+    {
+        let len = self.len();
+        self.splice(len..len, value);
+    }
+
+    /// Converts the container into a vector of bytes.
+    ///
+    /// The default implementation copies the bytes, which implementations
+    /// should avoid where the bytes are already stored on the heap.
+    #[inline]
+    fn into_vec(self) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        self.to_vec()
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -62,6 +93,24 @@ impl Container for Vec<u8> {
     {
         self.splice(range.into(), value.as_ref().iter().copied());
     }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional);
+    }
+
+    #[inline]
+    fn extend<S>(&mut self, value: S)
+    where
+        S: AsRef<[u8]>,
+    {
+        self.extend_from_slice(value.as_ref());
+    }
+
+    #[inline]
+    fn into_vec(self) -> Vec<u8> {
+        self
+    }
 }
 
 #[cfg(feature = "tinyvec")]
@@ -74,6 +123,27 @@ impl<A: Array<Item = u8>> Container for TinyVec<A> {
     {
         self.splice(range.into(), value.as_ref().iter().copied());
     }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        TinyVec::reserve(self, additional);
+    }
+
+    #[inline]
+    fn extend<S>(&mut self, value: S)
+    where
+        S: AsRef<[u8]>,
+    {
+        self.extend_from_slice(value.as_ref());
+    }
+
+    #[inline]
+    fn into_vec(self) -> Vec<u8> {
+        match self {
+            TinyVec::Inline(array) => array.to_vec(),
+            TinyVec::Heap(vec) => vec,
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
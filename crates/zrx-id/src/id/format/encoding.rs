@@ -24,23 +24,97 @@
 
 use percent_encoding::{percent_decode, percent_encode, AsciiSet, CONTROLS};
 use std::borrow::Cow;
+use std::str::{from_utf8_unchecked, Utf8Error};
 
 // ----------------------------------------------------------------------------
 // Constants
 // ----------------------------------------------------------------------------
 
 /// Character set to be percent-encoded.
-const SET: &AsciiSet = &CONTROLS.add(b':');
+///
+/// Note that `%` must be encoded as well, since literal sequences like `%41`
+/// would otherwise be indistinguishable from percent-encoded characters when
+/// parsing, and be decoded, which would break round trips.
+const SET: &AsciiSet = &CONTROLS.add(b':').add(b'%');
+
+/// Lookup table of bytes to be percent-encoded.
+///
+/// This table must be kept in sync with [`SET`], and additionally includes all
+/// non-ASCII bytes, as those are always percent-encoded.
+const TABLE: [bool; 256] = {
+    let mut table = [false; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        table[byte] = byte < 0x20
+            || byte >= 0x7F
+            || byte == b':' as usize
+            || byte == b'%' as usize;
+        byte += 1;
+    }
+    table
+};
 
 // ----------------------------------------------------------------------------
 // Functions
 // ----------------------------------------------------------------------------
 
 /// Encodes a byte slice.
+///
+/// If the value doesn't need to be encoded, which is expected to be the common
+/// case, the percent-encoding iterator is skipped entirely, and the value is
+/// returned as a borrowed reference. Otherwise, the prefix up to the first byte
+/// that needs to be encoded is copied as is, and only the rest is encoded.
+#[inline]
+#[must_use]
+pub fn encode(value: &[u8]) -> Cow<'_, str> {
+    let Some(index) = position(value) else {
+        // SAFETY: The value doesn't contain any non-ASCII bytes, as those would
+        // need to be encoded, so it's guaranteed to be valid UTF-8
+        return Cow::Borrowed(unsafe { from_utf8_unchecked(value) });
+    };
+
+    // Always return an owned value when encoding happened, since callers rely
+    // on the variant to set the encoding flag
+    let (prefix, rest) = value.split_at(index);
+    let mut encoded = String::with_capacity(value.len() + 8);
+
+    // SAFETY: The prefix doesn't contain any non-ASCII bytes, as the position
+    // of the first byte that needs to be encoded marks its end
+    encoded.push_str(unsafe { from_utf8_unchecked(prefix) });
+    encoded.extend(percent_encode(rest, SET));
+    Cow::Owned(encoded)
+}
+
+/// Returns whether a byte slice needs to be encoded.
+///
+/// This function performs a single scan over the given value, checking each
+/// byte against a lookup table, which is considerably faster than running the
+/// percent-encoding iterator. It can also be used by validation layers to
+/// reject values that would otherwise be silently encoded, e.g., values that
+/// contain a `:` or `%` character.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::format::encoding::needs_encoding;
+///
+/// // Check if values need to be encoded
+/// assert!(!needs_encoding(b"docs/index.md"));
+/// assert!(needs_encoding(b"c:d"));
+/// assert!(needs_encoding(b"100%"));
+/// assert!(needs_encoding(b"line\n"));
+/// assert!(needs_encoding("caf\u{e9}".as_bytes()));
+/// ```
 #[inline]
 #[must_use]
-pub fn encode(value: &[u8]) -> Cow<str> {
-    percent_encode(value, SET).into()
+pub fn needs_encoding(value: &[u8]) -> bool {
+    position(value).is_some()
+}
+
+/// Returns the position of the first byte that needs to be encoded.
+#[inline]
+fn position(value: &[u8]) -> Option<usize> {
+    value.iter().position(|&byte| TABLE[usize::from(byte)])
 }
 
 /// Decodes a byte slice.
@@ -49,6 +123,34 @@ pub fn encode(value: &[u8]) -> Cow<str> {
 /// character �, as otherwise, this would lead to a much less ergonomic API.
 #[inline]
 #[must_use]
-pub fn decode(value: &[u8]) -> Cow<str> {
+pub fn decode(value: &[u8]) -> Cow<'_, str> {
     percent_decode(value).decode_utf8_lossy()
 }
+
+/// Decodes a byte slice, failing on invalid UTF-8.
+///
+/// Other than [`decode`], this function doesn't replace invalid UTF-8, since
+/// distinct values might otherwise decode to the same value, which is not
+/// acceptable when values are treated as keys.
+///
+/// # Errors
+///
+/// If the decoded value is not valid UTF-8, a [`Utf8Error`] is returned.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::format::encoding::{decode, decode_strict};
+///
+/// // Decode valid and invalid values
+/// assert_eq!(decode_strict(b"a%3Ab").unwrap(), "a:b");
+/// assert!(decode_strict(b"a%FFb").is_err());
+/// assert!(decode_strict(b"a%FEb").is_err());
+///
+/// // Lossy decoding maps both invalid values to the same value
+/// assert_eq!(decode(b"a%FFb"), decode(b"a%FEb"));
+/// ```
+#[inline]
+pub fn decode_strict(value: &[u8]) -> Result<Cow<'_, str>, Utf8Error> {
+    percent_decode(value).decode_utf8()
+}
@@ -31,14 +31,45 @@ use thiserror::Error;
 
 /// Format error.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Invalid span count.
-    #[error("invalid span count")]
-    Cardinality,
+    #[error(
+        "invalid span count: found {found} separators, expected {expected} \
+         in {value:?}"
+    )]
+    Cardinality {
+        /// Number of separators found.
+        found: usize,
+        /// Number of separators expected.
+        expected: usize,
+        /// Offending value.
+        value: String,
+    },
+
+    /// Invalid UTF-8.
+    #[error("invalid UTF-8 at offset {offset}")]
+    Utf8 {
+        /// Byte offset of the first invalid byte.
+        offset: usize,
+    },
 
     /// Invalid span length.
     #[error("invalid span length")]
     Length,
+
+    /// Formatted string too long.
+    #[error("formatted string too long: {len} bytes, maximum is {max}")]
+    TooLong {
+        /// Length in bytes.
+        len: usize,
+        /// Maximum length in bytes.
+        max: usize,
+    },
+
+    /// Invalid span index.
+    #[error("invalid span index: {0}")]
+    Index(usize),
 }
 
 // ----------------------------------------------------------------------------
@@ -0,0 +1,580 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Formatted string with variable arity.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::str::{from_utf8_unchecked, FromStr};
+
+use super::container::{Container, Recommended};
+use super::encoding::decode;
+use super::error::{Error, Result};
+use super::{init_ends, replace, span};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Formatted string with variable arity.
+///
+/// This is a variant of [`Format`][] which allows the number of values to vary
+/// between `MIN` and `MAX`, and records the actual number of values, which is
+/// referred to as the arity. Only the separators of values that are present are
+/// part of the string representation, so trailing values can be omitted, which
+/// is different from them being present but empty.
+///
+/// Setting a value beyond the current arity grows the formatted string, adding
+/// empty values in between if necessary, while [`FlexFormat::truncate`] can be
+/// used to shrink it again. The arity can never drop below `MIN`.
+///
+/// [`Format`]: crate::format::Format
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::format::FlexFormat;
+///
+/// // Create formatted string from string
+/// let mut format: FlexFormat::<2, 4> = "a:b".parse()?;
+/// assert_eq!(format.arity(), 2);
+///
+/// // Update value beyond arity
+/// format.set(3, "d")?;
+/// assert_eq!(format.arity(), 4);
+/// assert_eq!(format.as_str(), "a:b::d");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FlexFormat<const MIN: usize, const MAX: usize, C = Recommended>
+where
+    C: Container,
+{
+    /// String representation.
+    value: C,
+    /// Set of span ends.
+    ends: [u16; MAX],
+    /// Encoding flags.
+    flags: u64,
+    /// Number of values.
+    arity: usize,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl<const MIN: usize, const MAX: usize, C> FlexFormat<MIN, MAX, C>
+where
+    C: Container,
+{
+    /// Creates a formatted string with variable arity.
+    ///
+    /// The formatted string is initialized with `MIN` empty values.
+    ///
+    /// The maximum arity must be in `1..=64`, and the minimum arity in
+    /// `1..=MAX`, which is checked at compile time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::format::FlexFormat;
+    ///
+    /// // Create formatted string
+    /// let format = FlexFormat::<2, 4>::new();
+    /// assert_eq!(format.as_str(), ":");
+    /// ```
+    ///
+    /// Unsupported arities are rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// use zrx_id::format::FlexFormat;
+    ///
+    /// // Create formatted string with minimum arity greater than maximum
+    /// let format = FlexFormat::<4, 2>::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        const { assert!(0 < MIN && MIN <= MAX, "arity must be in 1..=MAX") };
+        Self {
+            value: C::from(&[b':'; MAX][..MIN - 1]), // MIN - 1
+            ends: const { init_ends::<MAX>() },
+            flags: 0,
+            arity: MIN,
+        }
+    }
+
+    /// Returns the value at the given index, if present.
+    ///
+    /// If the index is beyond the current arity, [`None`] is returned. This is
+    /// different from a value that is present but empty, which is returned as
+    /// an empty string. Otherwise, this method behaves like [`Format::get`][].
+    ///
+    /// [`Format::get`]: crate::format::Format::get
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::FlexFormat;
+    ///
+    /// // Create formatted string from string
+    /// let format: FlexFormat::<2, 4> = "a::c".parse()?;
+    ///
+    /// // Obtain values at indexes
+    /// assert_eq!(format.get(0).as_deref(), Some("a"));
+    /// assert_eq!(format.get(1).as_deref(), Some(""));
+    /// assert_eq!(format.get(2).as_deref(), Some("c"));
+    /// assert_eq!(format.get(3).as_deref(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get(&self, index: usize) -> Option<Cow<'_, str>> {
+        if index >= self.arity {
+            return None;
+        }
+
+        // Obtain value, see Format::get for the fast path
+        let range: Range<_> = span(&self.ends, index).into();
+        if self.flags & (1 << index) == 0 {
+            // SAFETY: The value is guaranteed to be valid UTF-8, as it was
+            // created from a valid UTF-8 string, and it's not percent-encoded
+            unsafe {
+                Some(Cow::Borrowed(from_utf8_unchecked(&self.value[range])))
+            }
+        } else {
+            Some(decode(&self.value[range]))
+        }
+    }
+
+    /// Updates the value at the given index.
+    ///
+    /// If the index is beyond the current arity, the formatted string is grown
+    /// to include the value at the given index, and all values in between are
+    /// added as empty values.
+    ///
+    /// # Errors
+    ///
+    /// If the index is not smaller than `MAX`, [`Error::Cardinality`] is
+    /// returned. If the span overflows, [`Error::Length`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::FlexFormat;
+    ///
+    /// // Create formatted string and insert values
+    /// let mut format = FlexFormat::<2, 4>::new();
+    /// format.set(0, "a")?;
+    /// format.set(2, "c")?;
+    /// assert_eq!(format.as_str(), "a::c");
+    ///
+    /// // Updating beyond the maximum arity is an error
+    /// assert!(format.set(4, "e").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set<S>(&mut self, index: usize, value: S) -> Result
+    where
+        S: AsRef<[u8]>,
+    {
+        if index >= MAX {
+            return Err(Error::Cardinality {
+                found: index,
+                expected: MAX - 1,
+                value: self.as_str().to_owned(),
+            });
+        }
+
+        // Grow formatted string by appending separators and empty spans until
+        // the value at the given index is present, before updating the value
+        while self.arity <= index {
+            let len = self.value.len();
+            let at = u16::try_from(len + 1).map_err(|_| Error::Length)?;
+            self.value.splice(len..len, b":");
+            self.ends[self.arity] = at;
+            self.arity += 1;
+        }
+
+        // Update value in affected span
+        let value = value.as_ref();
+        let ends = &mut self.ends[..self.arity];
+        replace(&mut self.value, ends, &mut self.flags, index, value)
+    }
+
+    /// Shortens the formatted string to the given arity.
+    ///
+    /// All values at indexes greater than or equal to the given arity are
+    /// removed, together with their separators. If the given arity is greater
+    /// than or equal to the current arity, this method does nothing.
+    ///
+    /// # Errors
+    ///
+    /// If the given arity is smaller than `MIN`, [`Error::Cardinality`] is
+    /// returned, and the formatted string is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::FlexFormat;
+    ///
+    /// // Create formatted string from string and truncate it
+    /// let mut format: FlexFormat::<2, 4> = "a:b:c:d".parse()?;
+    /// format.truncate(2)?;
+    /// assert_eq!(format.arity(), 2);
+    /// assert_eq!(format.as_str(), "a:b");
+    ///
+    /// // Truncating below the minimum arity is an error
+    /// assert!(format.truncate(1).is_err());
+    ///
+    /// // Grow formatted string again with percent-encoded value
+    /// format.set(2, "c:d")?;
+    /// assert_eq!(format.as_str(), "a:b:c%3Ad");
+    /// assert_eq!(format.get(2).as_deref(), Some("c:d"));
+    /// assert_eq!(format.get(3).as_deref(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn truncate(&mut self, arity: usize) -> Result {
+        if arity < MIN {
+            return Err(Error::Cardinality {
+                found: arity.saturating_sub(1),
+                expected: MIN - 1,
+                value: self.as_str().to_owned(),
+            });
+        }
+
+        // Remove all values beyond the given arity including separators, and
+        // reset the encoding flags, so they can be grown again
+        if arity < self.arity {
+            let end = usize::from(self.ends[arity - 1]);
+            self.value.splice(end..self.value.len(), []);
+            self.flags &= (1 << arity) - 1;
+            self.arity = arity;
+        }
+
+        // No errors occurred
+        Ok(())
+    }
+
+    /// Returns the string representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::FlexFormat;
+    ///
+    /// // Create formatted string
+    /// let mut format = FlexFormat::<2, 4>::new();
+    /// format.set(0, "a")?;
+    /// format.set(1, "b")?;
+    ///
+    /// // Obtain string representation
+    /// assert_eq!(format.as_str(), "a:b");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: The value is guaranteed to be valid UTF-8, as it was created
+        // from a valid UTF-8 string, so we can just return a borrowed reference
+        unsafe { from_utf8_unchecked(&self.value) }
+    }
+
+    /// Returns an iterator over the byte ranges of all present values.
+    ///
+    /// The ranges refer to the string representation returned by
+    /// [`FlexFormat::as_str`], and remain valid until the next mutation of the
+    /// formatted string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::FlexFormat;
+    ///
+    /// // Create formatted string from string
+    /// let format: FlexFormat::<2, 4> = "a:bc:".parse()?;
+    ///
+    /// // Obtain byte ranges of all present values
+    /// let spans: Vec<_> = format.spans().collect();
+    /// assert_eq!(spans, [0..1, 2..4, 5..5]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn spans(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        (0..self.arity).map(|index| span(&self.ends, index).into())
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl<const MIN: usize, const MAX: usize, C> FlexFormat<MIN, MAX, C>
+where
+    C: Container,
+{
+    /// Returns the number of values.
+    #[inline]
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<const MIN: usize, const MAX: usize, C> FromStr for FlexFormat<MIN, MAX, C>
+where
+    C: Container,
+{
+    type Err = Error;
+
+    /// Attempts to create a formatted string with variable arity from a string.
+    ///
+    /// # Errors
+    ///
+    /// If the span count is not within `MIN` and `MAX`, [`Error::Cardinality`]
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::FlexFormat;
+    ///
+    /// // Create formatted strings from strings
+    /// let format: FlexFormat::<2, 4> = "a:b".parse()?;
+    /// assert_eq!(format.arity(), 2);
+    /// let format: FlexFormat::<2, 4> = "a:b:c:d".parse()?;
+    /// assert_eq!(format.arity(), 4);
+    ///
+    /// // Span counts outside of the arity range are errors
+    /// assert!("a".parse::<FlexFormat::<2, 4>>().is_err());
+    /// assert!("a:b:c:d:e".parse::<FlexFormat::<2, 4>>().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from_str(value: &str) -> Result<Self> {
+        let mut format = FlexFormat::new();
+        format.value = C::from(value.as_bytes());
+
+        // Initialize span counter
+        let mut index = 0;
+        let mut shift = 1;
+
+        // Compute spans from characters - see Format::from_str for details,
+        // the only difference being that we check bounds on every separator
+        for (i, char) in value.char_indices() {
+            match char {
+                ':' => {
+                    let end = u16::try_from(i).map_err(|_| Error::Length)?;
+                    if index + 1 == MAX {
+                        return Err(Error::Cardinality {
+                            found: value.matches(':').count(),
+                            expected: MAX - 1,
+                            value: value.to_owned(),
+                        });
+                    }
+
+                    // Finalize current span
+                    format.ends[index] = end;
+                    index += 1;
+
+                    // Continue after separator
+                    shift = 1 << index;
+                }
+
+                // Check if the span is percent-encoded
+                '%' if format.flags & shift == 0 => {
+                    let bytes = value.as_bytes();
+                    if let Some(&[b1, b2]) = bytes.get(i + 1..i + 3) {
+                        if b1.is_ascii_hexdigit() && b2.is_ascii_hexdigit() {
+                            format.flags |= shift;
+                        }
+                    }
+                }
+
+                // Consume all other characters
+                _ => {}
+            }
+        }
+
+        // Finalize last span
+        let end = u16::try_from(value.len()).map_err(|_| Error::Length)?;
+        format.ends[index] = end;
+        format.arity = index + 1;
+
+        // Return format or error on incorrect span count
+        if format.arity >= MIN {
+            Ok(format)
+        } else {
+            Err(Error::Cardinality {
+                found: index,
+                expected: MIN - 1,
+                value: value.to_owned(),
+            })
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<const MIN: usize, const MAX: usize, C> Hash for FlexFormat<MIN, MAX, C>
+where
+    C: Container,
+{
+    /// Hashes the formatted string.
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<const MIN: usize, const MAX: usize, C> PartialEq
+    for FlexFormat<MIN, MAX, C>
+where
+    C: Container + Eq,
+{
+    /// Compares two formatted strings for equality.
+    ///
+    /// Since only present values are part of the string representation, two
+    /// formatted strings with different arities are never equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::format::FlexFormat;
+    ///
+    /// // Create and compare formatted strings
+    /// let a: FlexFormat::<2, 4> = "a:b".parse()?;
+    /// let b: FlexFormat::<2, 4> = "a:b:".parse()?;
+    /// assert_ne!(a, b);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, C> Eq for FlexFormat<MIN, MAX, C> where
+    C: Container + Eq
+{
+}
+
+// ----------------------------------------------------------------------------
+
+impl<const MIN: usize, const MAX: usize, C> PartialOrd
+    for FlexFormat<MIN, MAX, C>
+where
+    C: Container + Ord,
+{
+    /// Orders two formatted strings.
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, C> Ord for FlexFormat<MIN, MAX, C>
+where
+    C: Container + Ord,
+{
+    /// Orders two formatted strings.
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<const MIN: usize, const MAX: usize, C> Default for FlexFormat<MIN, MAX, C>
+where
+    C: Container,
+{
+    /// Creates a formatted string with variable arity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::format::FlexFormat;
+    ///
+    /// // Create formatted string
+    /// let format = FlexFormat::<2, 4>::default();
+    /// assert_eq!(format.arity(), 2);
+    /// ```
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<const MIN: usize, const MAX: usize, C> fmt::Display
+    for FlexFormat<MIN, MAX, C>
+where
+    C: Container,
+{
+    /// Formats the formatted string for display.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, C> fmt::Debug
+    for FlexFormat<MIN, MAX, C>
+where
+    C: Container,
+{
+    /// Formats the formatted string for debugging.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FlexFormat")
+            .field("value", &self.as_str())
+            .field("ends", &&self.ends[..self.arity])
+            .field("flags", &self.flags)
+            .field("arity", &self.arity)
+            .finish()
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Stable hashing.
+//!
+//! This module implements the 64-bit and 128-bit variants of the FNV-1a hash
+//! function, which are used to compute digests of formatted strings that are
+//! stable across processes, platforms and versions, e.g., for cache keys. The
+//! algorithm is fixed, and won't change without a major version bump.
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Offset basis of the 64-bit variant.
+const OFFSET_64: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Prime of the 64-bit variant.
+const PRIME_64: u64 = 0x0000_0100_0000_01b3;
+
+/// Offset basis of the 128-bit variant.
+const OFFSET_128: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+
+/// Prime of the 128-bit variant.
+const PRIME_128: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Computes the 64-bit FNV-1a hash of a byte slice.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::format::hash::fnv1a_64;
+///
+/// // Compute hashes of known test vectors
+/// assert_eq!(fnv1a_64(b""), 0xcbf29ce484222325);
+/// assert_eq!(fnv1a_64(b"a"), 0xaf63dc4c8601ec8c);
+/// assert_eq!(fnv1a_64(b"foobar"), 0x85944171f73967e8);
+/// ```
+#[must_use]
+pub const fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut hash = OFFSET_64;
+    let mut index = 0;
+    while index < bytes.len() {
+        hash ^= bytes[index] as u64;
+        hash = hash.wrapping_mul(PRIME_64);
+        index += 1;
+    }
+    hash
+}
+
+/// Computes the 128-bit FNV-1a hash of a byte slice.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::format::hash::fnv1a_128;
+///
+/// // Compute hashes of known test vectors
+/// assert_eq!(fnv1a_128(b""), 0x6c62272e07bb014262b821756295c58d);
+/// assert_eq!(fnv1a_128(b"a"), 0xd228cb696f1a8caf78912b704e4a8964);
+/// assert_eq!(fnv1a_128(b"foobar"), 0x343e1662793c64bf6f0d3597ba446f18);
+/// ```
+#[must_use]
+pub const fn fnv1a_128(bytes: &[u8]) -> u128 {
+    let mut hash = OFFSET_128;
+    let mut index = 0;
+    while index < bytes.len() {
+        hash ^= bytes[index] as u128;
+        hash = hash.wrapping_mul(PRIME_128);
+        index += 1;
+    }
+    hash
+}
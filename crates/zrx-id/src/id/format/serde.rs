@@ -0,0 +1,118 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Serialization and deserialization of formatted strings.
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::container::Container;
+use super::Format;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Visitor for formatted strings.
+struct FormatVisitor<const N: usize, C> {
+    /// Marker for container.
+    marker: PhantomData<C>,
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<const N: usize, C> Serialize for Format<N, C>
+where
+    C: Container,
+{
+    /// Serializes the formatted string as its string representation.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de, const N: usize, C> Deserialize<'de> for Format<N, C>
+where
+    C: Container,
+{
+    /// Deserializes a formatted string from a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use serde::de::value::{BorrowedStrDeserializer, Error as ValueError};
+    /// use serde::de::Deserialize;
+    /// use zrx_id::format::Format;
+    ///
+    /// // Deserialize formatted string from string
+    /// let value = BorrowedStrDeserializer::<ValueError>::new("a:b%3Ac:d");
+    /// let format = Format::<3>::deserialize(value)?;
+    /// assert_eq!(format.get(1), "b:c");
+    ///
+    /// // Deserialize formatted string from string, invalid span count
+    /// let value = BorrowedStrDeserializer::<ValueError>::new("a:b");
+    /// let res = Format::<3>::deserialize(value);
+    /// assert_eq!(
+    ///     res.unwrap_err().to_string(),
+    ///     "invalid span count: found 1 separators, expected 2 in \"a:b\""
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(FormatVisitor { marker: PhantomData })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<const N: usize, C> Visitor<'_> for FormatVisitor<N, C>
+where
+    C: Container,
+{
+    type Value = Format<N, C>;
+
+    /// Formats the expected value.
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a formatted string with {N} values")
+    }
+
+    /// Visits a string, parsing it into a formatted string.
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value.parse().map_err(E::custom)
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Fragment utilities.
+//!
+//! Fragments are commonly used to point to source locations within resources,
+//! i.e., a line and an optional column. The canonical encoding is `L{line}` or
+//! `L{line}C{column}`, e.g., `L10C5`, which deliberately avoids `:`, as it is
+//! the component separator, and would otherwise be percent-encoded.
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Formats a source location as a fragment.
+///
+/// This function always emits the canonical encoding, which is `L{line}` if
+/// no column is given, and `L{line}C{column}` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::fragment::format_location;
+///
+/// // Format source locations
+/// assert_eq!(format_location(10, None), "L10");
+/// assert_eq!(format_location(10, Some(5)), "L10C5");
+/// ```
+#[must_use]
+pub fn format_location(line: u32, column: Option<u32>) -> String {
+    match column {
+        Some(column) => format!("L{line}C{column}"),
+        None => format!("L{line}"),
+    }
+}
+
+/// Parses a source location from a fragment.
+///
+/// Besides the canonical encoding emitted by [`format_location`], parsing is
+/// forgiving about legacy forms, which means that the `L` prefix is optional,
+/// `:` is accepted as a separator between line and column, and the `L` and
+/// `C` markers are matched case-insensitively. Anything else, including the
+/// empty string, returns [`None`].
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::fragment::parse_location;
+///
+/// // Parse source locations in canonical encoding
+/// assert_eq!(parse_location("L10"), Some((10, None)));
+/// assert_eq!(parse_location("L10C5"), Some((10, Some(5))));
+///
+/// // Parse source locations in legacy forms
+/// assert_eq!(parse_location("10"), Some((10, None)));
+/// assert_eq!(parse_location("10:5"), Some((10, Some(5))));
+/// assert_eq!(parse_location("L10:5"), Some((10, Some(5))));
+/// assert_eq!(parse_location("l10c5"), Some((10, Some(5))));
+///
+/// // Reject anything else
+/// assert_eq!(parse_location("anchor"), None);
+/// assert_eq!(parse_location("L10C"), None);
+/// assert_eq!(parse_location(""), None);
+/// ```
+#[must_use]
+pub fn parse_location(value: &str) -> Option<(u32, Option<u32>)> {
+    let value = value.strip_prefix(['L', 'l']).unwrap_or(value);
+
+    // Split line from column, if any, and ensure that both only consist of
+    // ASCII digits, since `u32::from_str` would also accept a leading `+`
+    let (line, column) = match value.split_once([':', 'C', 'c']) {
+        Some((line, column)) => (line, Some(column)),
+        None => (value, None),
+    };
+    let line = parse_number(line)?;
+    match column {
+        Some(column) => Some((line, Some(parse_number(column)?))),
+        None => Some((line, None)),
+    }
+}
+
+/// Parses a number consisting only of ASCII digits.
+fn parse_number(value: &str) -> Option<u32> {
+    if value.is_empty() || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
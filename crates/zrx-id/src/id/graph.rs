@@ -0,0 +1,558 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Identifier graph.
+
+use std::collections::{BTreeSet, HashMap};
+
+#[cfg(feature = "globset")]
+use crate::Matcher;
+
+use super::{Id, IdSet};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Identifier graph.
+///
+/// Identifier graphs track dependencies between resources, where an edge from
+/// `a` to `b` denotes that `a` depends on `b`. Identifiers are interned, so
+/// each identifier is stored exactly once, and edges are stored as pairs of
+/// indexes in both directions, which makes queries for dependencies and
+/// dependents equally cheap. Direct queries return identifiers in the order
+/// in which they were first added to the graph, and transitive queries return
+/// an [`IdSet`], so the order of all results is deterministic.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::prelude::*;
+/// use zrx_id::IdGraph;
+///
+/// // Create identifiers
+/// let page = Id::new("file", "docs", "index.md")?;
+/// let image = Id::new("file", "docs", "logo.png")?;
+///
+/// // Create graph and add edge
+/// let mut graph = IdGraph::new();
+/// graph.add_edge(&page, &image);
+///
+/// // Obtain dependents of image
+/// let dependents: Vec<_> = graph.dependents_of(&image).collect();
+/// assert_eq!(dependents, [&page]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IdGraph {
+    /// Interned identifiers.
+    ids: Vec<Id>,
+    /// Indexes of interned identifiers.
+    indexes: HashMap<Id, usize>,
+    /// Dependencies of each identifier.
+    dependencies: Vec<BTreeSet<usize>>,
+    /// Dependents of each identifier.
+    dependents: Vec<BTreeSet<usize>>,
+}
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Visitation state for cycle detection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    /// Not yet visited.
+    Unvisited,
+    /// On the current path.
+    Active,
+    /// Fully visited.
+    Done,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl IdGraph {
+    /// Creates an identifier graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::IdGraph;
+    ///
+    /// // Create graph
+    /// let graph = IdGraph::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an identifier to the graph, returning its index.
+    ///
+    /// If the identifier is already part of the graph, its index is returned,
+    /// so adding an identifier more than once has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdGraph;
+    ///
+    /// // Create graph and add identifier
+    /// let mut graph = IdGraph::new();
+    /// let index = graph.add(&Id::new("file", "docs", "index.md")?);
+    /// assert_eq!(index, 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add(&mut self, id: &Id) -> usize {
+        if let Some(&index) = self.indexes.get(id) {
+            return index;
+        }
+
+        // Intern identifier, and initialize its edges
+        let index = self.ids.len();
+        self.ids.push(id.clone());
+        self.indexes.insert(id.clone(), index);
+        self.dependencies.push(BTreeSet::new());
+        self.dependents.push(BTreeSet::new());
+        index
+    }
+
+    /// Adds an edge denoting that `from` depends on `to`.
+    ///
+    /// Both identifiers are added to the graph if necessary. Self-edges are
+    /// permitted, and are considered cycles. Returns whether the edge is new.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdGraph;
+    ///
+    /// // Create identifiers
+    /// let page = Id::new("file", "docs", "index.md")?;
+    /// let image = Id::new("file", "docs", "logo.png")?;
+    ///
+    /// // Create graph and add edge
+    /// let mut graph = IdGraph::new();
+    /// assert!(graph.add_edge(&page, &image));
+    /// assert!(!graph.add_edge(&page, &image));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_edge(&mut self, from: &Id, to: &Id) -> bool {
+        let from = self.add(from);
+        let to = self.add(to);
+        self.dependents[to].insert(from);
+        self.dependencies[from].insert(to)
+    }
+
+    /// Returns the direct dependencies of the given identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdGraph;
+    ///
+    /// // Create identifiers
+    /// let page = Id::new("file", "docs", "index.md")?;
+    /// let image = Id::new("file", "docs", "logo.png")?;
+    ///
+    /// // Create graph and add edge
+    /// let mut graph = IdGraph::new();
+    /// graph.add_edge(&page, &image);
+    ///
+    /// // Obtain dependencies of page
+    /// let dependencies: Vec<_> = graph.dependencies_of(&page).collect();
+    /// assert_eq!(dependencies, [&image]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dependencies_of(&self, id: &Id) -> impl Iterator<Item = &Id> {
+        self.neighbors(&self.dependencies, id)
+    }
+
+    /// Returns the direct dependents of the given identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdGraph;
+    ///
+    /// // Create identifiers
+    /// let page = Id::new("file", "docs", "index.md")?;
+    /// let image = Id::new("file", "docs", "logo.png")?;
+    ///
+    /// // Create graph and add edge
+    /// let mut graph = IdGraph::new();
+    /// graph.add_edge(&page, &image);
+    ///
+    /// // Obtain dependents of image
+    /// let dependents: Vec<_> = graph.dependents_of(&image).collect();
+    /// assert_eq!(dependents, [&page]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dependents_of(&self, id: &Id) -> impl Iterator<Item = &Id> {
+        self.neighbors(&self.dependents, id)
+    }
+
+    /// Returns all transitive dependencies of the given identifier.
+    ///
+    /// The traversal visits each identifier at most once, so it terminates in
+    /// the presence of cycles. The identifier itself is only included, if it
+    /// is part of a cycle, i.e., if it transitively depends on itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdGraph;
+    ///
+    /// // Create identifiers
+    /// let a = Id::new("file", "docs", "a.md")?;
+    /// let b = Id::new("file", "docs", "b.md")?;
+    /// let c = Id::new("file", "docs", "c.md")?;
+    ///
+    /// // Create graph and add edges
+    /// let mut graph = IdGraph::new();
+    /// graph.add_edge(&a, &b);
+    /// graph.add_edge(&b, &c);
+    ///
+    /// // Obtain transitive dependencies of a
+    /// let dependencies = graph.transitive_dependencies_of(&a);
+    /// assert_eq!(dependencies.into_iter().collect::<Vec<_>>(), [b, c]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn transitive_dependencies_of(&self, id: &Id) -> IdSet {
+        let start = self.indexes.get(id).copied();
+        self.collect(self.traverse(&self.dependencies, start))
+    }
+
+    /// Returns all transitive dependents of the given identifier.
+    ///
+    /// The traversal visits each identifier at most once, so it terminates in
+    /// the presence of cycles. The identifier itself is only included, if it
+    /// is part of a cycle, i.e., if it transitively depends on itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdGraph;
+    ///
+    /// // Create identifiers
+    /// let a = Id::new("file", "docs", "a.md")?;
+    /// let b = Id::new("file", "docs", "b.md")?;
+    ///
+    /// // Create graph and add edges forming a cycle
+    /// let mut graph = IdGraph::new();
+    /// graph.add_edge(&a, &b);
+    /// graph.add_edge(&b, &a);
+    ///
+    /// // Obtain transitive dependents of a
+    /// let dependents = graph.transitive_dependents_of(&a);
+    /// assert_eq!(dependents.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Large fan-outs are traversed without recursion:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdGraph;
+    ///
+    /// // Create graph and add chain of edges
+    /// let mut graph = IdGraph::new();
+    /// let root = Id::new("file", "docs", "style.css")?;
+    /// let mut prev = root.clone();
+    /// for n in 0..10_000 {
+    ///     let page = Id::new("file", "docs", format!("{n}.md"))?;
+    ///     graph.add_edge(&page, &root);
+    ///     graph.add_edge(&page, &prev);
+    ///     prev = page;
+    /// }
+    ///
+    /// // Obtain transitive dependents of root
+    /// let dependents = graph.transitive_dependents_of(&root);
+    /// assert_eq!(dependents.len(), 10_000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn transitive_dependents_of(&self, id: &Id) -> IdSet {
+        let start = self.indexes.get(id).copied();
+        self.collect(self.traverse(&self.dependents, start))
+    }
+
+    /// Returns all identifiers affected by a change of matching identifiers.
+    ///
+    /// This method returns all identifiers of the graph that are matched by
+    /// the given matcher, together with all of their transitive dependents,
+    /// which is the set of identifiers that must be invalidated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdGraph;
+    ///
+    /// // Create identifiers
+    /// let page = Id::new("file", "docs", "index.md")?;
+    /// let style = Id::new("file", "docs", "style.css")?;
+    /// let image = Id::new("file", "docs", "logo.png")?;
+    ///
+    /// // Create graph and add edges
+    /// let mut graph = IdGraph::new();
+    /// graph.add_edge(&page, &style);
+    /// graph.add_edge(&style, &image);
+    ///
+    /// // Obtain identifiers affected by changes to images
+    /// let matcher: Matcher = "zrs::::**/*.png:".parse()?;
+    /// let affected = graph.affected_by(&matcher);
+    /// assert_eq!(affected.len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "globset")]
+    #[must_use]
+    pub fn affected_by(&self, matcher: &Matcher) -> IdSet {
+        let mut visited = vec![false; self.ids.len()];
+        for (index, id) in self.ids.iter().enumerate() {
+            if !visited[index] && matches!(matcher.is_match(id), Ok(true)) {
+                visited[index] = true;
+                visit(&self.dependents, index, &mut visited);
+            }
+        }
+        self.collect(visited)
+    }
+
+    /// Returns a cycle in the graph, if any.
+    ///
+    /// The cycle is returned as a path of identifiers, where each identifier
+    /// depends on the next one, and the last one depends on the first one.
+    /// Self-edges are returned as cycles consisting of a single identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdGraph;
+    ///
+    /// // Create identifiers
+    /// let a = Id::new("file", "docs", "a.md")?;
+    /// let b = Id::new("file", "docs", "b.md")?;
+    /// let c = Id::new("file", "docs", "c.md")?;
+    ///
+    /// // Create graph and add edges
+    /// let mut graph = IdGraph::new();
+    /// graph.add_edge(&a, &b);
+    /// graph.add_edge(&b, &c);
+    /// assert!(graph.find_cycle().is_none());
+    ///
+    /// // Add edge closing the cycle
+    /// graph.add_edge(&c, &b);
+    /// assert_eq!(graph.find_cycle(), Some(vec![&b, &c]));
+    ///
+    /// // Add self-edge
+    /// let mut graph = IdGraph::new();
+    /// graph.add_edge(&a, &a);
+    /// assert_eq!(graph.find_cycle(), Some(vec![&a]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn find_cycle(&self) -> Option<Vec<&Id>> {
+        let mut state = vec![State::Unvisited; self.ids.len()];
+        for root in 0..self.ids.len() {
+            if state[root] != State::Unvisited {
+                continue;
+            }
+
+            // Perform an iterative depth-first search, keeping the current
+            // path on the stack, together with the remaining dependencies
+            state[root] = State::Active;
+            let mut stack = vec![(root, self.dependencies[root].iter())];
+            while let Some((index, iter)) = stack.last_mut() {
+                let index = *index;
+                match iter.next() {
+                    // If the dependency is on the current path, we found a
+                    // cycle, which starts at the dependency on the stack
+                    Some(&next) if state[next] == State::Active => {
+                        let start = stack
+                            .iter()
+                            .position(|&(index, _)| index == next)
+                            .unwrap_or(0);
+                        return Some(
+                            stack[start..]
+                                .iter()
+                                .map(|&(index, _)| &self.ids[index])
+                                .collect(),
+                        );
+                    }
+
+                    // If the dependency wasn't visited yet, descend into it
+                    Some(&next) if state[next] == State::Unvisited => {
+                        state[next] = State::Active;
+                        stack.push((next, self.dependencies[next].iter()));
+                    }
+
+                    // If the dependency was already fully visited, skip it
+                    Some(_) => {}
+
+                    // All dependencies were visited, so ascend from here
+                    None => {
+                        state[index] = State::Done;
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        // No cycle found
+        None
+    }
+
+    /// Returns an iterator over all identifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdGraph;
+    ///
+    /// // Create graph and add identifier
+    /// let mut graph = IdGraph::new();
+    /// graph.add(&Id::new("file", "docs", "index.md")?);
+    ///
+    /// // Obtain identifiers
+    /// for id in graph.iter() {
+    ///     println!("{id}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Id> {
+        self.ids.iter()
+    }
+
+    /// Returns the direct neighbors of the given identifier.
+    fn neighbors<'a>(
+        &'a self, edges: &'a [BTreeSet<usize>], id: &Id,
+    ) -> impl Iterator<Item = &'a Id> {
+        let index = self.indexes.get(id).copied();
+        index
+            .into_iter()
+            .flat_map(move |index| edges[index].iter())
+            .map(|&index| &self.ids[index])
+    }
+
+    /// Traverses the edges transitively from the given start index.
+    fn traverse(
+        &self, edges: &[BTreeSet<usize>], start: Option<usize>,
+    ) -> Vec<bool> {
+        let mut visited = vec![false; self.ids.len()];
+        if let Some(start) = start {
+            visit(edges, start, &mut visited);
+        }
+        visited
+    }
+
+    /// Collects all visited identifiers into a set.
+    fn collect(&self, visited: Vec<bool>) -> IdSet {
+        let iter = visited.into_iter().zip(&self.ids);
+        iter.filter(|&(visited, _)| visited)
+            .map(|(_, id)| id.clone())
+            .collect()
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl IdGraph {
+    /// Returns the number of identifiers.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns whether there are any identifiers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Returns whether the given identifier is part of the graph.
+    #[inline]
+    pub fn contains(&self, id: &Id) -> bool {
+        self.indexes.contains_key(id)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Visits all identifiers transitively reachable from the given index.
+fn visit(edges: &[BTreeSet<usize>], start: usize, visited: &mut [bool]) {
+    let mut stack = vec![start];
+    while let Some(index) = stack.pop() {
+        for &next in &edges[index] {
+            if !visited[next] {
+                visited[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+}
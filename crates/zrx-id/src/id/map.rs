@@ -0,0 +1,390 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Identifier map.
+
+use std::collections::btree_map::{self, BTreeMap};
+#[cfg(feature = "globset")]
+use std::mem;
+
+#[cfg(feature = "globset")]
+use crate::Matcher;
+
+use super::{Id, Result};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Identifier map.
+///
+/// Identifier maps associate identifiers with values, and allow to query and
+/// mutate all entries whose identifiers match a [`Matcher`][], which is what
+/// schedulers need to dispatch work to subscribers. Entries are ordered by
+/// identifier, so iteration order is deterministic.
+///
+/// [`Matcher`]: crate::Matcher
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::prelude::*;
+/// use zrx_id::IdMap;
+///
+/// // Create map and insert entry
+/// let mut map = IdMap::new();
+/// map.insert(Id::new("file", "docs", "index.md")?, 1);
+///
+/// // Obtain value of entry
+/// assert_eq!(map.get(&Id::new("file", "docs", "index.md")?), Some(&1));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdMap<V> {
+    /// Entries ordered by identifier.
+    entries: BTreeMap<Id, V>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl<V> IdMap<V> {
+    /// Creates an identifier map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::IdMap;
+    ///
+    /// // Create map
+    /// let map = IdMap::<u32>::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self { entries: BTreeMap::new() }
+    }
+
+    /// Inserts a value, returning the previous value, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdMap;
+    ///
+    /// // Create map and insert entry
+    /// let mut map = IdMap::new();
+    /// assert_eq!(map.insert(Id::new("file", "docs", "index.md")?, 1), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, id: Id, value: V) -> Option<V> {
+        self.entries.insert(id, value)
+    }
+
+    /// Removes an entry, returning its value, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdMap;
+    ///
+    /// // Create map and insert entry
+    /// let mut map = IdMap::new();
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// map.insert(id.clone(), 1);
+    ///
+    /// // Remove entry
+    /// assert_eq!(map.remove(&id), Some(1));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, id: &Id) -> Option<V> {
+        self.entries.remove(id)
+    }
+
+    /// Returns a reference to the value of an entry.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, id: &Id) -> Option<&V> {
+        self.entries.get(id)
+    }
+
+    /// Returns a mutable reference to the value of an entry.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, id: &Id) -> Option<&mut V> {
+        self.entries.get_mut(id)
+    }
+
+    /// Returns the value of an entry, inserting it if the entry is new.
+    ///
+    /// The given string is parsed exactly once, and the resulting identifier
+    /// is used for both lookup and insertion.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given string is not a valid
+    /// identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::IdMap;
+    ///
+    /// // Create map and insert entry
+    /// let mut map = IdMap::new();
+    /// *map.entry_or_insert_with_str("zri:file::docs:index.md:", || 0)? += 1;
+    /// *map.entry_or_insert_with_str("zri:file::docs:index.md:", || 0)? += 1;
+    /// assert_eq!(map.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn entry_or_insert_with_str<F>(
+        &mut self, value: &str, f: F,
+    ) -> Result<&mut V>
+    where
+        F: FnOnce() -> V,
+    {
+        let id = value.parse()?;
+        Ok(self.entries.entry(id).or_insert_with(f))
+    }
+
+    /// Returns an iterator over all entries.
+    #[inline]
+    pub fn iter(&self) -> btree_map::Iter<'_, Id, V> {
+        self.entries.iter()
+    }
+
+    /// Returns a mutable iterator over all entries.
+    #[inline]
+    pub fn iter_mut(&mut self) -> btree_map::IterMut<'_, Id, V> {
+        self.entries.iter_mut()
+    }
+}
+
+#[cfg(feature = "globset")]
+impl<V> IdMap<V> {
+    /// Returns an iterator over all entries matching a matcher.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdMap;
+    ///
+    /// // Create map and insert entries
+    /// let mut map = IdMap::new();
+    /// map.insert(Id::new("file", "docs", "index.md")?, 1);
+    /// map.insert(Id::new("file", "docs", "logo.png")?, 2);
+    ///
+    /// // Obtain entries matching the matcher
+    /// let matcher: Matcher = "zrs::::**/*.md:".parse()?;
+    /// let values: Vec<_> =
+    ///     map.iter_matching(&matcher).map(|(_, v)| v).collect();
+    /// assert_eq!(values, [&1]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_matching<'a>(
+        &'a self, matcher: &'a Matcher,
+    ) -> impl Iterator<Item = (&'a Id, &'a V)> {
+        self.entries
+            .iter()
+            .filter(move |(id, _)| is_match(matcher, id))
+    }
+
+    /// Returns a mutable iterator over all entries matching a matcher.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdMap;
+    ///
+    /// // Create map and insert entries
+    /// let mut map = IdMap::new();
+    /// map.insert(Id::new("file", "docs", "index.md")?, 1);
+    /// map.insert(Id::new("file", "docs", "about.md")?, 2);
+    /// map.insert(Id::new("file", "docs", "logo.png")?, 3);
+    ///
+    /// // Mutate entries matching the matcher during iteration
+    /// let matcher: Matcher = "zrs::::**/*.md:".parse()?;
+    /// for (_, value) in map.iter_matching_mut(&matcher) {
+    ///     *value *= 10;
+    /// }
+    ///
+    /// // Ensure only matching entries were mutated
+    /// let values: Vec<_> = map.iter().map(|(_, v)| *v).collect();
+    /// assert_eq!(values, [20, 10, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_matching_mut<'a>(
+        &'a mut self, matcher: &'a Matcher,
+    ) -> impl Iterator<Item = (&'a Id, &'a mut V)> {
+        self.entries
+            .iter_mut()
+            .filter(move |(id, _)| is_match(matcher, id))
+    }
+
+    /// Removes all entries matching a matcher, and returns them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdMap;
+    ///
+    /// // Create map and insert entries
+    /// let mut map = IdMap::new();
+    /// map.insert(Id::new("file", "docs", "index.md")?, 1);
+    /// map.insert(Id::new("file", "docs", "logo.png")?, 2);
+    ///
+    /// // Drain entries matching the matcher
+    /// let matcher: Matcher = "zrs::::**/*.md:".parse()?;
+    /// let drained = map.drain_matching(&matcher);
+    /// assert_eq!(drained.len(), 1);
+    /// assert_eq!(map.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn drain_matching(&mut self, matcher: &Matcher) -> Vec<(Id, V)> {
+        let mut drained = Vec::new();
+        for (id, value) in mem::take(&mut self.entries) {
+            if is_match(matcher, &id) {
+                drained.push((id, value));
+            } else {
+                self.entries.insert(id, value);
+            }
+        }
+        drained
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl<V> IdMap<V> {
+    /// Returns the number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether there are any entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns whether there is an entry for the given identifier.
+    #[inline]
+    pub fn contains_key(&self, id: &Id) -> bool {
+        self.entries.contains_key(id)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<V> Default for IdMap<V> {
+    /// Creates an identifier map.
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> FromIterator<(Id, V)> for IdMap<V> {
+    /// Creates an identifier map from an iterator.
+    #[inline]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (Id, V)>,
+    {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<V> IntoIterator for IdMap<V> {
+    type Item = (Id, V);
+    type IntoIter = btree_map::IntoIter<Id, V>;
+
+    /// Creates a consuming iterator over all entries.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a IdMap<V> {
+    type Item = (&'a Id, &'a V);
+    type IntoIter = btree_map::Iter<'a, Id, V>;
+
+    /// Creates an iterator over all entries.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a mut IdMap<V> {
+    type Item = (&'a Id, &'a mut V);
+    type IntoIter = btree_map::IterMut<'a, Id, V>;
+
+    /// Creates a mutable iterator over all entries.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter_mut()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Checks whether an identifier matches a matcher.
+#[cfg(feature = "globset")]
+fn is_match(matcher: &Matcher, id: &Id) -> bool {
+    matches!(matcher.is_match(id), Ok(true))
+}
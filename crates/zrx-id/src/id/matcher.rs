@@ -22,18 +22,83 @@
 
 //! Matcher.
 
+#[cfg(feature = "globset")]
 use globset::GlobSet;
+use std::borrow::Cow;
+#[cfg(feature = "globset")]
+use std::cell::RefCell;
+#[cfg(feature = "globset")]
+use std::collections::HashMap;
+#[cfg(feature = "globset")]
+use std::fmt;
+#[cfg(feature = "globset")]
 use std::str::FromStr;
+#[cfg(feature = "globset")]
+use std::sync::Arc;
+#[cfg(all(feature = "globset", feature = "tinyvec"))]
+use tinyvec::TinyVec;
 
-use super::ToId;
+#[cfg(feature = "globset")]
+use super::{Component, ComponentSet, Id, ToId};
 
+#[cfg(feature = "globset")]
 mod builder;
+#[cfg(feature = "captures")]
+mod capture;
+#[cfg(feature = "dfa")]
+mod dfa;
+#[cfg(feature = "globset")]
+mod dispatcher;
 mod error;
+#[cfg(feature = "lite-matcher")]
+mod lite;
+#[cfg(feature = "globset")]
+mod map;
+mod pattern;
+#[cfg(all(feature = "globset", feature = "serde"))]
+mod persistent;
+#[cfg(feature = "captures")]
+mod rewrite;
 mod selector;
 
+#[cfg(feature = "globset")]
 use builder::Builder;
+#[cfg(feature = "globset")]
+pub use builder::{Diagnostic, EmptyBehavior, Report, Severity};
+#[cfg(feature = "captures")]
+use capture::Captures;
+#[cfg(feature = "captures")]
+pub use capture::Match;
+#[cfg(feature = "dfa")]
+pub use dfa::DfaMatcher;
+#[cfg(feature = "globset")]
+pub use dispatcher::{Dispatcher, SubscriptionId};
 pub use error::{Error, Result};
-pub use selector::{Selector, ToSelector};
+#[cfg(feature = "lite-matcher")]
+pub use lite::LiteMatcher;
+#[cfg(feature = "globset")]
+pub use map::SelectorMap;
+#[cfg(all(feature = "globset", feature = "serde"))]
+pub use persistent::Persistent;
+#[cfg(feature = "captures")]
+pub use rewrite::{Rewrite, RewriteSet};
+#[cfg(feature = "serde")]
+pub use selector::SelectorRecord;
+pub use selector::{
+    Selector, SelectorBuilder, SelectorSet, SelectorView, ToSelector,
+};
+
+// ----------------------------------------------------------------------------
+// Thread-locals
+// ----------------------------------------------------------------------------
+
+#[cfg(feature = "globset")]
+thread_local! {
+    /// Scratch buffers for candidates and matches of components.
+    static SCRATCH: RefCell<(Vec<usize>, Vec<usize>)> = const {
+        RefCell::new((Vec::new(), Vec::new()))
+    };
+}
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -51,12 +116,21 @@ pub use selector::{Selector, ToSelector};
 /// identifier in linear time in relation to the length of the input string,
 /// and which return the set of matched selectors.
 ///
+/// The compiled glob sets are shared, so cloning a [`Matcher`] is cheap, and
+/// matchers can be handed to as many threads as necessary.
+///
+/// Negated selectors, i.e., `!zrs:...`, are exclusions, and use strict
+/// subtraction: if any negated selector matches an identifier, the identifier
+/// doesn't match, regardless of how many positive selectors match it, and the
+/// order in which selectors were added. Negated selectors never show up in
+/// match sets, and a matcher with only negated selectors matches nothing.
+///
 /// # Examples
 ///
 /// ```
 /// # use std::error::Error;
 /// # fn main() -> Result<(), Box<dyn Error>> {
-/// use zrx_id::{Id, Matcher};
+/// use zrx_id::prelude::*;
 ///
 /// // Create identifier
 /// let id = Id::new("file", "docs", "index.md")?;
@@ -73,31 +147,65 @@ pub use selector::{Selector, ToSelector};
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Clone, Debug)]
+///
+/// Identifiers, selectors and matchers can be sent and shared across threads:
+///
+/// ```
+/// use zrx_id::prelude::*;
+///
+/// // Ensure types are thread-safe
+/// fn assert_send_sync<T: Send + Sync>() {}
+/// assert_send_sync::<Id>();
+/// assert_send_sync::<Selector>();
+/// assert_send_sync::<Matcher>();
+/// ```
+#[cfg(feature = "globset")]
+#[derive(Clone)]
 pub struct Matcher {
     /// Glob set for scheme.
-    scheme: GlobSet,
+    scheme: Arc<GlobSet>,
     /// Glob set for binding.
-    binding: GlobSet,
+    binding: Arc<GlobSet>,
     /// Glob set for context.
-    context: GlobSet,
+    context: Arc<GlobSet>,
     /// Glob set for path.
-    path: GlobSet,
+    path: Arc<GlobSet>,
     /// Glob set for fragment.
-    fragment: GlobSet,
+    fragment: Arc<GlobSet>,
+    /// Selectors, in the order they were added.
+    selectors: Arc<[Selector]>,
+    /// Specificity of selectors.
+    specificity: Arc<[(usize, usize)]>,
+    /// Indexes of negated selectors, in ascending order.
+    negated: Arc<[usize]>,
+    /// Components of selectors matching absent values, i.e., empty or
+    /// empty-only components.
+    absent: Arc<[ComponentSet]>,
+    /// Indexes of selectors compiled into glob sets, in ascending order.
+    compiled: Arc<[usize]>,
+    /// Indexes of literal selectors, by the identifier they match.
+    literals: Arc<HashMap<String, Vec<usize>>>,
+    /// Whether matching is case-insensitive for all selectors.
+    case_insensitive: bool,
+    /// Behavior if there are no selectors.
+    on_empty: EmptyBehavior,
+    /// Capture-aware regular expressions of selectors.
+    #[cfg(feature = "captures")]
+    captures: Arc<[Captures]>,
 }
 
 // ----------------------------------------------------------------------------
 // Implementations
 // ----------------------------------------------------------------------------
 
+#[cfg(feature = "globset")]
 impl Matcher {
     /// Creates a matcher builder.
     ///
     /// # Examples
     ///
     /// ```
-    /// use zrx_id::Matcher;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create matcher builder
     /// let mut builder = Matcher::builder();
@@ -113,7 +221,10 @@ impl Matcher {
     /// Components are compared in descending variability and their likelihood
     /// for mismatch, starting with the `path`. This approach effectively tries
     /// to short-circuits the comparison. Note that empty components must be
-    /// considered wildcards, so they will always match.
+    /// considered wildcards, so they will always match, and that they're the
+    /// only ones matching absent values, i.e., bindings and fragments, other
+    /// than empty-only components, i.e., [`Selector::EMPTY`], which match
+    /// nothing else.
     ///
     /// # Errors
     ///
@@ -124,7 +235,7 @@ impl Matcher {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::{Id, Matcher};
+    /// use zrx_id::prelude::*;
     ///
     /// // Create identifier
     /// let id = Id::new("file", "docs", "index.md")?;
@@ -142,19 +253,105 @@ impl Matcher {
     /// # }
     /// ```
     ///
+    /// Negated selectors exclude identifiers, even if they were added before
+    /// the positive selectors they overlap with:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add("!zrs::::drafts/**:")?;
+    /// builder.add("zrs::::**/*.md:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Check if the ids match the selectors
+    /// assert!(matcher.is_match(&Id::new("file", "docs", "index.md")?)?);
+    /// assert!(!matcher.is_match(&Id::new("file", "docs", "drafts/a.md")?)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Absent values are only matched by empty components, so patterns like
+    /// `*` require a value to be present:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers with and without fragment
+    /// let a = Id::new("file", "docs", "index.md")?;
+    /// let b = a.clone().with_fragment("top")?;
+    ///
+    /// // Create matchers for any and for present fragments
+    /// let any: Matcher = "zrs::::**/*.md:".parse()?;
+    /// let present: Matcher = "zrs::::**/*.md:*".parse()?;
+    ///
+    /// // Check if the ids match the selectors
+    /// assert!(any.is_match(&a)? && any.is_match(&b)?);
+    /// assert!(!present.is_match(&a)?);
+    /// assert!(present.is_match(&b)?);
+    /// assert!(present.matches(&a)?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Literal selectors match all spellings of the identifiers they match,
+    /// regardless of how their values are percent-encoded:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers and literal selectors with equivalent values
+    /// for (id, selector) in [
+    ///     ("zri:file::docs:a%20b.md:", "zrs:file:-:docs:a b.md:-"),
+    ///     ("zri:file::docs:100%.md:", "zrs:file:-:docs:100%.md:-"),
+    ///     ("zri:file::docs:a%41.md:", "zrs:file:-:docs:aA.md:-"),
+    ///     ("zri:file::docs:aA.md:", "zrs:file:-:docs:a%41.md:-"),
+    ///     ("zri:file::docs:café.md:", "zrs:file:-:docs:café.md:-"),
+    /// ] {
+    ///     let id: Id = id.parse()?;
+    ///     let matcher: Matcher = selector.parse()?;
+    ///
+    ///     // Check if the id matches the selector
+    ///     assert!(matcher.is_match(&id)?, "{id} {selector}");
+    ///     assert_eq!(matcher.matches(&id)?, [0], "{id} {selector}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     #[allow(clippy::needless_pass_by_value)]
     pub fn is_match<I>(&self, id: I) -> Result<bool>
     where
         I: ToId,
     {
         let id = id.to_id()?;
+        if self.is_empty() {
+            return Ok(self.on_empty == EmptyBehavior::MatchAll);
+        }
 
-        // Compare components in descending variability
-        Ok(compare(&self.path, Some(id.path().as_ref()))
-            && compare(&self.context, Some(id.context().as_ref()))
-            && compare(&self.scheme, Some(id.scheme().as_ref()))
-            && compare(&self.binding, id.binding().as_deref())
-            && compare(&self.fragment, id.fragment().as_deref()))
+        // Literal selectors are looked up first, so if one of them matches,
+        // the glob sets only need to be consulted for negated selectors
+        if self.negated.is_empty()
+            && !self.literals.is_empty()
+            && self.literals.contains_key(canonical(&id).as_ref())
+        {
+            return Ok(true);
+        }
+
+        // Obtain match set, and check whether it contains any selectors
+        Ok(SCRATCH.with(|scratch| {
+            let (candidates, buffer) = &mut *scratch.borrow_mut();
+            self.intersect(&id, candidates, buffer);
+            !candidates.is_empty()
+        }))
     }
 
     /// Returns the match set of the selectors that match an identifier.
@@ -162,12 +359,18 @@ impl Matcher {
     /// This method compares each component of the identifier against the
     /// corresponding component of a selector using the compiled globs, and
     /// returns the indexes of the matching selectors in the order they were
-    /// added to the [`Matcher`].
+    /// added to the [`Matcher`]. If a negated selector matches, the match set
+    /// is empty, and indexes of negated selectors are never included. Literal
+    /// selectors, i.e., selectors without glob syntax in any component, are
+    /// looked up by the identifier they match, and merged into the match set.
     ///
     /// Components are compared in descending variability and their likelihood
     /// for mismatch, starting with the `path`. This approach effectively tries
     /// to short-circuits the comparison. Note that empty components must be
-    /// considered wildcards, so they will always match.
+    /// considered wildcards, so they will always match, and that they're the
+    /// only ones matching absent values, other than empty-only components,
+    /// i.e., [`Selector::EMPTY`], which match nothing else. When matching many
+    /// identifiers, use [`Matcher::matches_into`] to reuse the match set.
     ///
     /// # Errors
     ///
@@ -178,7 +381,7 @@ impl Matcher {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::{Id, Matcher};
+    /// use zrx_id::prelude::*;
     ///
     /// // Create identifier
     /// let id = Id::new("file", "docs", "index.md")?;
@@ -196,52 +399,717 @@ impl Matcher {
     /// # Ok(())
     /// # }
     /// ```
-    #[allow(clippy::if_not_else)]
-    #[allow(clippy::needless_pass_by_value)]
+    ///
+    /// Negated selectors subtract from the match set, and overlapping negated
+    /// selectors exclude identifiers regardless of the order they were added:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("!zrs::::drafts/**:")?;
+    /// builder.add("zrs:::docs::")?;
+    /// builder.add("!zrs::::**/wip.md:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Obtain selectors matched by identifiers
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(matcher.matches(&id)?, [0, 2]);
+    /// let id = Id::new("file", "docs", "drafts/index.md")?;
+    /// assert!(matcher.matches(&id)?.is_empty());
+    /// let id = Id::new("file", "docs", "drafts/wip.md")?;
+    /// assert!(matcher.matches(&id)?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Literal selectors yield the same match sets as their globs would:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers
+    /// let ids = [
+    ///     Id::new("file", "docs", "index.md")?,
+    ///     Id::new("file", "docs", "index.md")?.with_fragment("top")?,
+    ///     Id::new("git", "docs", "index.md")?.with_binding("main")?,
+    ///     Id::new("file", "docs", "a:b.md")?,
+    ///     Id::new("file", "docs", "drafts/wip.md")?,
+    ///     Id::new("file", "docs", "a*.md")?,
+    /// ];
+    ///
+    /// // Create matcher builder and add literal and glob selectors
+    /// let mut builder = Matcher::builder();
+    /// let selectors = [
+    ///     "zrs::::**/*.md:",
+    ///     "zrs:file:-:docs:index.md:-",
+    ///     "zrs:file:-:docs:index.md:top",
+    ///     "zrs:git:main:docs:index.md:-",
+    ///     "zrs:file:-:docs:a%3Ab.md:-",
+    ///     "zrs:file::docs:index.md:",
+    ///     "zrs:file:-:docs:index.md:-",
+    ///     "zrs:file:-:docs:a[*].md:-",
+    ///     "!zrs:file:-:docs:drafts/wip.md:-",
+    /// ];
+    /// for value in selectors {
+    ///     builder.add(value)?;
+    /// }
+    ///
+    /// // Ensure match sets agree with matching each selector on its own
+    /// let matcher = builder.build()?;
+    /// for id in &ids {
+    ///     let matches = matcher.matches(id)?;
+    ///     assert_eq!(matcher.is_match(id)?, !matches.is_empty());
+    ///     if id == &ids[4] {
+    ///         assert!(matches.is_empty());
+    ///         continue;
+    ///     }
+    ///     for (index, value) in selectors.iter().enumerate().take(8) {
+    ///         let selector: Selector = value.parse()?;
+    ///         let expected = selector.is_match(id)?;
+    ///         assert_eq!(matches.contains(&index), expected, "{value} {id}");
+    ///     }
+    /// }
+    /// assert_eq!(matcher.matches(&ids[0])?, [0, 1, 5, 6]);
+    /// assert_eq!(matcher.matches(&ids[5])?, [0, 7]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
     pub fn matches<I>(&self, id: I) -> Result<Vec<usize>>
+    where
+        I: ToId,
+    {
+        let mut matches = Vec::new();
+        self.matches_into(id, &mut matches)?;
+        Ok(matches)
+    }
+
+    /// Writes the match set of the selectors that match an identifier into
+    /// the given vector.
+    ///
+    /// This method returns the same match set as [`Matcher::matches`], but
+    /// clears and fills the given vector instead of allocating a new one, and
+    /// intersects the matches of components in a thread-local scratch buffer.
+    /// Thus, once the vector and scratch buffer have grown to the size of the
+    /// largest match set, matching doesn't allocate anymore, which is useful
+    /// when matching many identifiers, e.g., one for each file event.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid, in
+    /// which case the given vector is left empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:git:::**/*.md:")?;
+    /// builder.add("zrs:::docs::")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Obtain selectors matched by identifiers, reusing the match set
+    /// let mut matches = Vec::new();
+    /// for path in ["index.md", "about.md", "image.png"] {
+    ///     let id = Id::new("file", "docs", path)?;
+    ///     matcher.matches_into(&id, &mut matches)?;
+    ///     assert_eq!(matches, matcher.matches(&id)?);
+    /// }
+    /// assert_eq!(matches, [2]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Once warmed up, matching doesn't allocate, which we can verify with an
+    /// allocator that counts allocations:
+    ///
+    /// ```
+    /// # use std::alloc::{GlobalAlloc, Layout, System};
+    /// # use std::error::Error;
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// # struct Counter;
+    /// # static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    /// # unsafe impl GlobalAlloc for Counter {
+    /// #     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    /// #         ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    /// #         System.alloc(layout)
+    /// #     }
+    /// #     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    /// #         System.dealloc(ptr, layout);
+    /// #     }
+    /// # }
+    /// # #[global_allocator]
+    /// # static GLOBAL: Counter = Counter;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:::docs::")?;
+    /// builder.add("!zrs::::drafts/**:")?;
+    ///
+    /// // Create matcher and identifiers
+    /// let matcher = builder.build()?;
+    /// let ids = ["index.md", "drafts/index.md", "image.png"]
+    ///     .into_iter()
+    ///     .map(|path| Id::new("file", "docs", path))
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// // Warm up match set and scratch buffer
+    /// let mut matches = Vec::new();
+    /// for id in &ids {
+    ///     matcher.matches_into(id, &mut matches)?;
+    /// }
+    ///
+    /// // Count allocations of reusing the match set
+    /// let before = ALLOCATIONS.load(Ordering::Relaxed);
+    /// for id in &ids {
+    ///     matcher.matches_into(id, &mut matches)?;
+    /// }
+    /// let reused = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    ///
+    /// // Count allocations of creating a match set on every call
+    /// let before = ALLOCATIONS.load(Ordering::Relaxed);
+    /// for id in &ids {
+    ///     let _ = matcher.matches(id)?;
+    /// }
+    /// let created = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    /// assert_eq!(reused, 0);
+    /// assert!(created > 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn matches_into<I>(&self, id: I, matches: &mut Vec<usize>) -> Result
+    where
+        I: ToId,
+    {
+        matches.clear();
+        let id = id.to_id()?;
+        SCRATCH.with(|scratch| {
+            let (_, buffer) = &mut *scratch.borrow_mut();
+            self.intersect(&id, matches, buffer);
+        });
+        Ok(())
+    }
+
+    /// Returns the match set of the selectors that match an identifier, along
+    /// with the values matched by the wildcards of each selector.
+    ///
+    /// This method returns the same match set as [`Matcher::matches`], but
+    /// each [`Match`] also carries the values matched by the wildcards of each
+    /// component, i.e., `*`, `**` and `?`, in order. As [`globset`] doesn't
+    /// expose captures, the components of matching selectors are matched a
+    /// second time with capture-aware regular expressions, which are compiled
+    /// when building the [`Matcher`]. If a wildcard can match in more than one
+    /// way, earlier wildcards match as much as possible. Components containing
+    /// character classes or alternations don't yield captures.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier
+    /// let id = Id::new("file", "docs", "blog/hello-world/index.md")?;
+    ///
+    /// // Create matcher builder and add selector
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::blog/*/index.md:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Obtain selectors matched by identifier, along with captures
+    /// let matches = matcher.matches_with_captures(&id)?;
+    /// assert_eq!(matches[0].index, 0);
+    /// assert_eq!(matches[0].path, ["hello-world"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Captures of `*`, `**` and `?`, and of multiple wildcards:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier
+    /// let id = Id::new("file", "docs", "a/b/c-d-e.md")?.with_fragment("L1")?;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs::::a/**:?*")?;
+    /// builder.add("zrs::::**/b/**/*-*.md:")?;
+    /// builder.add("zrs:f?le::d*::")?;
+    /// builder.add("zrs::::a/b/c-d-e.md:")?;
+    /// builder.add("zrs::::*/[b]/*:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Obtain selectors matched by identifier, along with captures
+    /// let matches = matcher.matches_with_captures(&id)?;
+    /// assert_eq!(matches.len(), 6);
+    /// assert_eq!(matches[0].path, ["a/b", "c-d-e"]);
+    /// assert_eq!(matches[1].path, ["b/c-d-e.md"]);
+    /// assert_eq!(matches[1].fragment, ["L", "1"]);
+    /// assert_eq!(matches[2].path, ["a", "", "c-d", "e"]);
+    /// assert_eq!(matches[3].scheme, ["i"]);
+    /// assert_eq!(matches[3].context, ["ocs"]);
+    /// assert!(matches[4].path.is_empty());
+    /// assert!(matches[5].path.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "captures")]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn matches_with_captures<I>(&self, id: I) -> Result<Vec<Match>>
     where
         I: ToId,
     {
         let id = id.to_id()?;
+        let view = id.view();
+        let values = [
+            Some(view.scheme.as_ref()),
+            view.binding.as_deref(),
+            Some(view.context.as_ref()),
+            Some(view.path.as_ref()),
+            view.fragment.as_deref(),
+        ];
+
+        // Re-match the components of all matching selectors to extract the
+        // values matched by their wildcards
+        let iter = self
+            .matches(id.as_ref())?
+            .into_iter()
+            .map(|index| self.captures[index].extract(index, values));
+
+        // Return matches
+        Ok(iter.collect())
+    }
 
-        // Create a vector and count the matches of each component in the slots
-        // of the vector to find all selectors that match the given identifier
-        let mut slots = vec![0u8; self.scheme.len()];
-        for (component, value) in [
-            (&self.path, Some(id.path())),
-            (&self.context, Some(id.context())),
-            (&self.scheme, Some(id.scheme())),
-            (&self.binding, id.binding()),
-            (&self.fragment, id.fragment()),
+    /// Returns the match set of the selectors that match an identifier, ordered
+    /// by descending specificity.
+    ///
+    /// This method returns the same match set as [`Matcher::matches`], but the
+    /// indexes are ordered by [`Selector::specificity`], most specific first,
+    /// which is precomputed when building the [`Matcher`]. Selectors of equal
+    /// specificity are kept in the order they were added, so "first rule wins"
+    /// is deterministic.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier
+    /// let id = Id::new("file", "docs", "index.md")?;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:file:::**/*.md:")?;
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs::::index.md:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Obtain selectors matched by identifier, most specific first
+    /// let matches = matcher.matches_by_specificity(&id)?;
+    /// assert_eq!(matches, [1, 3, 0, 2]);
+    ///
+    /// // Ensure match set agrees with unordered match set
+    /// let mut sorted = matches.clone();
+    /// sorted.sort_unstable();
+    /// assert_eq!(sorted, matcher.matches(&id)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn matches_by_specificity<I>(&self, id: I) -> Result<Vec<usize>>
+    where
+        I: ToId,
+    {
+        let mut matches = self.matches(id)?;
+
+        // Sorting is stable, and the match set is ordered by index, so ties
+        // are resolved by the order in which the selectors were added
+        matches.sort_by(|&a, &b| self.specificity[b].cmp(&self.specificity[a]));
+        Ok(matches)
+    }
+
+    /// Returns the selectors that match an identifier, along with their
+    /// indexes.
+    ///
+    /// This method returns the same match set as [`Matcher::matches`], but
+    /// pairs each index with the [`Selector`] it refers to, so callers don't
+    /// need to maintain a parallel mapping. Iteration order is guaranteed to
+    /// be the order in which the selectors were added to the [`Matcher`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier
+    /// let id = Id::new("file", "docs", "index.md")?;
+    ///
+    /// // Create matcher builder and add selectors, one of them twice
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:git:::**/*.md:")?;
+    /// builder.add("zrs::::**/*.md:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Obtain selectors matched by identifier
+    /// let matched: Vec<_> = matcher.matched(&id)?.collect();
+    /// assert_eq!(matched.len(), 2);
+    /// assert_eq!(matched[0], (0, &matcher.selectors()[0]));
+    /// assert_eq!(matched[1], (2, &matcher.selectors()[2]));
+    /// assert_eq!(matched[0].1, matched[1].1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn matched<I>(
+        &self, id: I,
+    ) -> Result<impl Iterator<Item = (usize, &Selector)> + '_>
+    where
+        I: ToId,
+    {
+        let matches = self.matches(id)?;
+        Ok(matches
+            .into_iter()
+            .map(|index| (index, &self.selectors[index])))
+    }
+
+    /// Returns the first selector that matches an identifier, along with its
+    /// index.
+    ///
+    /// This method returns the selector with the lowest index in the match set
+    /// of [`Matcher::matches`], i.e., the selector that was added first. The
+    /// match set is not collected, but intersected in thread-local scratch
+    /// buffers, which are reused, so this method doesn't allocate.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs:git:::**/*.md:")?;
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:::docs::")?;
+    /// builder.add("!zrs::::drafts/**:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Obtain first selector matched by identifiers
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// let (index, selector) = matcher.first_match(&id)?.expect("invariant");
+    /// assert_eq!(index, 1);
+    /// assert_eq!(selector.to_string(), "zrs::::**/*.md:");
+    ///
+    /// // Negated selectors exclude identifiers
+    /// let id = Id::new("file", "docs", "drafts/index.md")?;
+    /// assert!(matcher.first_match(&id)?.is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn first_match<I>(&self, id: I) -> Result<Option<(usize, &Selector)>>
+    where
+        I: ToId,
+    {
+        let id = id.to_id()?;
+        let index = SCRATCH.with(|scratch| {
+            let (candidates, buffer) = &mut *scratch.borrow_mut();
+            self.intersect(&id, candidates, buffer);
+            candidates.first().copied()
+        });
+        Ok(index.map(|index| (index, &self.selectors[index])))
+    }
+
+    /// Returns the match set of the selectors that match an identifier, using
+    /// a small-size-optimized container.
+    ///
+    /// This method returns the same match set as [`Matcher::matches`], but the
+    /// result is stored inline for up to two selectors, which is the common
+    /// case. Like in [`Matcher::matches_into`], the candidate sets of all
+    /// components are intersected in scratch buffers which are reused for
+    /// each thread, so no allocations happen for small match sets.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier
+    /// let id = Id::new("file", "docs", "index.md")?;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:git:::**/*.md:")?;
+    /// builder.add("zrs:::docs::")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Obtain selectors matched by identifier
+    /// let matches = matcher.matches_small(&id)?;
+    /// assert_eq!(matches.as_slice(), [0, 2]);
+    /// assert_eq!(matches.as_slice(), matcher.matches(&id)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tinyvec")]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn matches_small<I>(&self, id: I) -> Result<TinyVec<[usize; 2]>>
+    where
+        I: ToId,
+    {
+        let id = id.to_id()?;
+        SCRATCH.with(|scratch| {
+            let (candidates, buffer) = &mut *scratch.borrow_mut();
+            self.intersect(&id, candidates, buffer);
+            Ok(candidates.iter().copied().collect())
+        })
+    }
+
+    /// Intersects the matches of all components of an identifier, writing
+    /// the resulting match set into the given candidates.
+    ///
+    /// The candidates are obtained from the glob sets, and merged with the
+    /// literal selectors that match the identifier, which are looked up. If a
+    /// negated selector matches, the match set is cleared, as the identifier
+    /// must be excluded.
+    fn intersect(
+        &self, id: &Id, candidates: &mut Vec<usize>, buffer: &mut Vec<usize>,
+    ) {
+        self.intersect_globs(id, candidates, buffer);
+
+        // Map indexes in glob sets to indexes of selectors, which are only
+        // different if there are literal selectors, and merge them, as both
+        // sets are disjoint
+        if !self.literals.is_empty() {
+            for index in candidates.iter_mut() {
+                *index = self.compiled[*index];
+            }
+            if let Some(indexes) = self.literals.get(canonical(id).as_ref()) {
+                candidates.extend(indexes);
+                candidates.sort_unstable();
+            }
+        }
+
+        // Clear match set, if a negated selector matches
+        if self.exclude(candidates) {
+            candidates.clear();
+        }
+    }
+
+    /// Intersects the matches of all components of an identifier in the glob
+    /// sets, writing the resulting indexes into the given candidates.
+    ///
+    /// The candidates are obtained from the path, which is the most selective
+    /// component, and intersected with the matches of all other present
+    /// components, which are written to the given buffer, while absent ones
+    /// are only matched by empty or empty-only components.
+    fn intersect_globs(
+        &self, id: &Id, candidates: &mut Vec<usize>, buffer: &mut Vec<usize>,
+    ) {
+        let view = id.view();
+        self.path.matches_into(view.path.as_ref(), candidates);
+        for (component, globs, value) in [
+            (Component::Context, &self.context, Some(view.context)),
+            (Component::Scheme, &self.scheme, Some(view.scheme)),
+            (Component::Binding, &self.binding, view.binding),
+            (Component::Fragment, &self.fragment, view.fragment),
         ] {
+            if candidates.is_empty() {
+                return;
+            }
+
+            // Both sets are sorted, so we can use binary search. Absent values
+            // are only matched by selectors with empty or empty-only components
             if let Some(value) = value {
-                let matches = component.matches(value.as_ref());
-                if !matches.is_empty() {
-                    for index in matches {
-                        slots[index] += 1;
-                    }
-
-                // Short-circuit, as the current component doesn't match, so we
-                // know the result must be empty and can return immediately
-                } else {
-                    return Ok(Vec::new());
-                }
-
-            // Wildcard match, which means all slots must be updated
+                globs.matches_into(value.as_ref(), buffer);
+                candidates.retain(|index| buffer.binary_search(index).is_ok());
             } else {
-                slots.iter_mut().for_each(|count| *count += 1);
+                candidates.retain(|&index| {
+                    self.absent[self.compiled[index]].contains(component)
+                });
             }
         }
+    }
 
-        // Obtain match set by collecting the indexes of all matching selectors,
-        // which are the slots that match exactly five components
-        let iter = slots
-            .iter()
-            .enumerate()
-            .filter_map(|(index, &count)| (count == 5).then_some(index));
+    /// Removes negated selectors from a match set, returning whether any of
+    /// them matched, in which case the identifier must be excluded.
+    fn exclude(&self, matches: &mut Vec<usize>) -> bool {
+        if self.negated.is_empty() {
+            return false;
+        }
 
-        // Return match set
-        Ok(iter.collect())
+        // Both sets are sorted, so we can use binary search
+        let len = matches.len();
+        matches.retain(|index| self.negated.binary_search(index).is_err());
+        matches.len() != len
+    }
+}
+
+#[cfg(feature = "globset")]
+#[allow(clippy::must_use_candidate)]
+impl Matcher {
+    /// Returns the selector at the given index.
+    ///
+    /// Indexes correspond to the order in which selectors were added to the
+    /// [`Matcher`], and thus to the indexes returned by [`Matcher::matches`],
+    /// which allows to map matches back to the selectors they originate from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier
+    /// let id = Id::new("file", "docs", "index.md")?;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:git:::**/*.md:")?;
+    /// builder.add("zrs:::docs::")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Obtain selectors matched by identifier
+    /// for index in matcher.matches(&id)? {
+    ///     let selector = matcher.get(index).expect("invariant");
+    ///     assert!(selector.is_match(&id)?);
+    /// }
+    /// assert!(matcher.get(3).is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&Selector> {
+        self.selectors.get(index)
+    }
+
+    /// Returns the selectors, in the order they were added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers
+    /// let ids = [
+    ///     Id::new("file", "docs", "index.md")?,
+    ///     Id::new("git", "docs", "index.md")?,
+    ///     Id::new("file", "docs", "image.png")?,
+    ///     Id::new("file", "docs", "drafts/index.md")?,
+    /// ];
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:git:::**/*.md:")?;
+    /// builder.add("zrs:::docs::")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    /// assert_eq!(matcher.selectors()[1].to_string(), "zrs:git:::**/*.md:");
+    ///
+    /// // Ensure matches correspond to the selectors that match
+    /// for id in &ids {
+    ///     let matches = matcher.matches(id)?;
+    ///     for (index, selector) in matcher.selectors().iter().enumerate() {
+    ///         let expected = selector.is_match(id)?;
+    ///         assert_eq!(matches.contains(&index), expected, "{id}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn selectors(&self) -> &[Selector] {
+        &self.selectors
+    }
+
+    /// Returns the number of selectors.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.selectors.len()
+    }
+
+    /// Returns whether there are any selectors.
+    ///
+    /// Matchers without selectors match no identifiers, unless the builder is
+    /// configured otherwise, see [`EmptyBehavior`], so callers that need to
+    /// treat them differently can branch on this.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.selectors.is_empty()
     }
 }
 
@@ -249,6 +1117,36 @@ impl Matcher {
 // Trait implementations
 // ----------------------------------------------------------------------------
 
+#[cfg(feature = "globset")]
+impl fmt::Debug for Matcher {
+    /// Formats the matcher for debugging.
+    ///
+    /// The compiled glob sets are opaque, which is why the selectors are
+    /// printed in the order they were added, so indexes can be told apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher and format it for debugging
+    /// let matcher: Matcher = "zrs::::**/*.md:".parse()?;
+    /// assert!(format!("{matcher:?}").contains(r#"path: Some("**/*.md")"#));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Matcher")
+            .field("selectors", &self.selectors)
+            .field("case_insensitive", &self.case_insensitive)
+            .field("on_empty", &self.on_empty)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "globset")]
 impl FromStr for Matcher {
     type Err = Error;
 
@@ -271,7 +1169,7 @@ impl FromStr for Matcher {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Matcher;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create matcher from string
     /// let matcher: Matcher = "zrs::::**/*.md:".parse()?;
@@ -290,15 +1188,77 @@ impl FromStr for Matcher {
 // Functions
 // ----------------------------------------------------------------------------
 
-/// Compares a component against a value.
+/// Returns the canonical string of an identifier for looking up literals.
 ///
-/// If the value is absent, we must consider this as a wildcard match if and
-/// only if the globset was initially constructed with wildcards (i.e. `**`).
-/// Unfortunately, this information is not retained in the globset, and we do
-/// not want to use more space than necessary to track empty components.
+/// Literal selectors are keyed by the string [`Id::new_full`] creates from
+/// their values, so identifiers are looked up by the same string created from
+/// their decoded components, as equivalent spellings, e.g., with encoded `A`
+/// or unencoded `é`, must find the same selectors. As values are only encoded
+/// if they contain `%`, `:`, control or non-ASCII characters, the string of an
+/// identifier without `%` and any of those characters is already canonical.
+#[cfg(feature = "globset")]
+fn canonical(id: &Id) -> Cow<'_, str> {
+    let value = id.as_str();
+    if value
+        .bytes()
+        .all(|byte| byte.is_ascii_graphic() || byte == b' ')
+        && !value.contains('%')
+    {
+        return Cow::Borrowed(value);
+    }
+
+    // Re-encode decoded components - if that fails, no literal selector can
+    // have been created from them either, so the string is never found
+    let view = id.view();
+    Id::new_full(
+        view.scheme.as_bytes(),
+        view.binding.unwrap_or_default().as_bytes(),
+        view.context.as_bytes(),
+        view.path.as_bytes(),
+        view.fragment.unwrap_or_default().as_bytes(),
+    )
+    .map_or(Cow::Borrowed(value), |id| Cow::Owned(id.into_string()))
+}
+
+/// Escapes all glob metacharacters in a component value.
 ///
-/// However, falling back to `U+FFFE`, which is a non-character that should
-/// never appear in a proper UTF-8 string should be sufficient for the check.
-fn compare(component: &GlobSet, value: Option<&str>) -> bool {
-    component.is_match(value.unwrap_or("\u{FFFE}"))
+/// Each of the metacharacters `*`, `?`, `[`, `]`, `{` and `}` is wrapped in a
+/// character class, which is supported by all glob dialects, other than the
+/// escaping with backslashes, which are rejected in component values anyway.
+/// Values equal to [`Selector::EMPTY`] are wrapped as well, as they'd only
+/// match absent values otherwise. If there's nothing to escape, the value is
+/// returned as is.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::matcher::escape;
+///
+/// // Escape glob metacharacters
+/// assert_eq!(escape("what?.md"), "what[?].md");
+/// assert_eq!(escape("[draft] notes.md"), "[[]draft[]] notes.md");
+/// assert_eq!(escape("index.md"), "index.md");
+///
+/// // Escape reserved value for empty-only components
+/// assert_eq!(escape("-"), "[-]");
+/// ```
+#[must_use]
+pub fn escape(component: &str) -> Cow<'_, str> {
+    if component == Selector::EMPTY {
+        return Cow::Owned(format!("[{component}]"));
+    }
+    if !component.contains(['*', '?', '[', ']', '{', '}']) {
+        return Cow::Borrowed(component);
+    }
+
+    // Wrap each metacharacter in a character class
+    let mut escaped = String::with_capacity(component.len() * 2);
+    for char in component.chars() {
+        if matches!(char, '*' | '?' | '[' | ']' | '{' | '}') {
+            escaped.extend(['[', char, ']']);
+        } else {
+            escaped.push(char);
+        }
+    }
+    Cow::Owned(escaped)
 }
@@ -22,12 +22,25 @@
 
 //! Matcher builder.
 
-use globset::{Glob, GlobSetBuilder};
+use globset::{Glob, GlobBuilder, GlobSetBuilder};
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
 
+use crate::id::{Component, ComponentSet};
+use crate::Id;
+
+#[cfg(feature = "captures")]
+use super::capture::Captures;
 use super::error::Result;
-use super::selector::ToSelector;
+use super::selector::{Selector, ToSelector};
 use super::Matcher;
 
+mod report;
+
+pub use report::{Diagnostic, Report, Severity};
+
 // ----------------------------------------------------------------------------
 // Structs
 // ----------------------------------------------------------------------------
@@ -35,16 +48,34 @@ use super::Matcher;
 /// Matcher builder.
 #[derive(Clone, Debug)]
 pub struct Builder {
-    /// Glob set builder for scheme.
-    scheme: GlobSetBuilder,
-    /// Glob set builder for binding.
-    binding: GlobSetBuilder,
-    /// Glob set builder for context.
-    context: GlobSetBuilder,
-    /// Glob set builder for path.
-    path: GlobSetBuilder,
-    /// Glob set builder for fragment.
-    fragment: GlobSetBuilder,
+    /// Specificity of selectors.
+    specificity: Vec<(usize, usize)>,
+    /// Pending selectors.
+    selectors: Vec<Selector>,
+    /// Indexes of negated selectors.
+    negated: Vec<usize>,
+    /// Components of selectors matching absent values.
+    absent: Vec<ComponentSet>,
+    /// Indexes of literal selectors, by the identifier they match.
+    literals: HashMap<String, Vec<usize>>,
+    /// Whether matching is case-insensitive for all selectors.
+    case_insensitive: bool,
+    /// Behavior if there are no selectors.
+    on_empty: EmptyBehavior,
+}
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Matcher behavior if there are no selectors.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum EmptyBehavior {
+    /// Match no identifiers.
+    #[default]
+    MatchNone,
+    /// Match all identifiers, i.e., don't filter.
+    MatchAll,
 }
 
 // ----------------------------------------------------------------------------
@@ -62,7 +93,7 @@ impl Builder {
     /// # Examples
     ///
     /// ```
-    /// use zrx_id::Matcher;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create matcher builder
     /// let mut builder = Matcher::builder();
@@ -71,20 +102,160 @@ impl Builder {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            scheme: GlobSetBuilder::new(),
-            binding: GlobSetBuilder::new(),
-            context: GlobSetBuilder::new(),
-            path: GlobSetBuilder::new(),
-            fragment: GlobSetBuilder::new(),
+            specificity: Vec::new(),
+            selectors: Vec::new(),
+            negated: Vec::new(),
+            absent: Vec::new(),
+            literals: HashMap::new(),
+            case_insensitive: false,
+            on_empty: EmptyBehavior::default(),
         }
     }
 
+    /// Sets whether matching is case-insensitive for all selectors.
+    ///
+    /// When enabled, all components of all selectors match identifiers
+    /// regardless of the casing of ASCII characters, e.g., `docs/**/*.md`
+    /// matches `Docs/INDEX.MD`. Otherwise, only selectors that are marked as
+    /// case-insensitive themselves, see [`Selector::set_case_insensitive`],
+    /// are matched case-insensitively. Since globs are compiled when the
+    /// matcher is built, this applies to all selectors, regardless of
+    /// whether they were added before or after calling this method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier with mixed-case path
+    /// let id = Id::new("file", "docs", "Docs/INDEX.MD")?;
+    ///
+    /// // Create case-sensitive matcher
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::docs/**/*.md:")?;
+    /// assert!(!builder.build()?.is_match(&id)?);
+    ///
+    /// // Create case-insensitive matcher
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::docs/**/*.md:")?;
+    /// builder.case_insensitive(true);
+    /// assert!(builder.build()?.is_match(&id)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Case-insensitivity can also be enabled for individual selectors:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers with mixed-case paths
+    /// let a = Id::new("file", "docs", "Docs/INDEX.MD")?;
+    /// let b = Id::new("file", "docs", "Docs/Image.PNG")?;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add(
+    ///     &Selector::builder()
+    ///         .path("docs/**/*.md")?
+    ///         .case_insensitive(true)
+    ///         .build()?,
+    /// )?;
+    /// builder.add("zrs::::docs/**/*.png:")?;
+    ///
+    /// // Only the case-insensitive selector matches
+    /// let matcher = builder.build()?;
+    /// assert_eq!(matcher.matches(&a)?, [0]);
+    /// assert!(matcher.matches(&b)?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn case_insensitive(&mut self, case_insensitive: bool) -> &mut Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Sets the behavior of the matcher if there are no selectors.
+    ///
+    /// By default, a matcher without selectors matches no identifiers, which
+    /// is [`EmptyBehavior::MatchNone`]. With [`EmptyBehavior::MatchAll`], it
+    /// matches all identifiers instead, which is useful when an unconfigured
+    /// matcher should mean that no filtering is applied. This only affects
+    /// [`Matcher::is_match`], as there are no indexes of selectors that could
+    /// be returned by [`Matcher::matches`], so match sets are always empty.
+    /// Use [`Matcher::is_empty`] to tell both cases apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::EmptyBehavior;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier
+    /// let id = Id::new("file", "docs", "index.md")?;
+    ///
+    /// // Create matcher without selectors, matching nothing
+    /// let matcher = Matcher::builder().build()?;
+    /// assert!(matcher.is_empty());
+    /// assert!(!matcher.is_match(&id)?);
+    /// assert!(matcher.matches(&id)?.is_empty());
+    ///
+    /// // Create matcher without selectors, matching everything
+    /// let mut builder = Matcher::builder();
+    /// builder.on_empty(EmptyBehavior::MatchAll);
+    /// let matcher = builder.build()?;
+    /// assert!(matcher.is_empty());
+    /// assert!(matcher.is_match(&id)?);
+    /// assert!(matcher.matches(&id)?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Matchers with selectors are unaffected:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::EmptyBehavior;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selector
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::**/*.png:")?;
+    /// builder.on_empty(EmptyBehavior::MatchAll);
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert!(!matcher.is_match(&id)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn on_empty(&mut self, behavior: EmptyBehavior) -> &mut Self {
+        self.on_empty = behavior;
+        self
+    }
+
     /// Adds a selector to the matcher.
     ///
-    /// This method adds a [`Selector`][] to the matcher, creating a [`Glob`]
-    /// from each component, adding it to the corresponding [`GlobSetBuilder`].
-    /// If a component is empty, it is coerced to `**`, as the counts of all
+    /// This method adds a [`Selector`][] to the matcher, checking that a valid
+    /// [`Glob`] can be created from each component. Globs are compiled into a
+    /// [`GlobSetBuilder`] per component when the matcher is built. If a
+    /// component is empty, it is coerced to `**`, as the counts of all
     /// components must match for correct intersection in [`Matcher::matches`].
+    /// Selectors that only consist of literals, and thus match exactly one
+    /// identifier, are looked up by that identifier instead, unless they're
+    /// matched case-insensitively.
+    /// Negated selectors are added in the same way, but exclude identifiers
+    /// they match from the match set, see [`Matcher`] for details.
     ///
     /// [`Selector`]: crate::Selector
     ///
@@ -98,11 +269,12 @@ impl Builder {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Matcher;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create matcher builder and add selector
     /// let mut builder = Matcher::builder();
     /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("!zrs::::drafts/**:")?;
     /// # Ok(())
     /// # }
     /// ```
@@ -113,17 +285,157 @@ impl Builder {
     {
         let selector = selector.to_selector()?;
 
-        // Compile and add each component of the given selector
-        self.scheme.add(parse(selector.scheme().as_deref())?);
-        self.binding.add(parse(selector.binding().as_deref())?);
-        self.context.add(parse(selector.context().as_deref())?);
-        self.path.add(parse(selector.path().as_deref())?);
-        self.fragment.add(parse(selector.fragment().as_deref())?);
+        // Ensure each component of the given selector compiles, and track
+        // empty and empty-only components, as they match absent values
+        let mut absent = ComponentSet::new();
+        for (component, value) in
+            Component::ALL.into_iter().zip(components(&selector))
+        {
+            if value.is_some() {
+                parse(value.as_deref(), false)?;
+            }
+            if value.as_deref().map_or(true, str::is_empty) {
+                absent.insert(component);
+            }
+        }
+        self.absent.push(absent);
+
+        // Track literal selectors, which are looked up instead of matched
+        if let Some(key) = literal(&selector) {
+            let index = self.selectors.len();
+            self.literals.entry(key).or_default().push(index);
+        }
+
+        // Track negated selectors, which are excluded from match sets
+        if selector.is_negated() {
+            self.negated.push(self.selectors.len());
+        }
+
+        // Precompute specificity for ordering matches
+        self.specificity.push(selector.specificity());
+        self.selectors.push(selector.into_owned());
 
         // Return self for chaining
         Ok(self)
     }
 
+    /// Checks the pending selectors, collecting all problems in a report.
+    ///
+    /// This method is the non-consuming preflight for [`Builder::build`], and
+    /// compiles every component of every pending selector, and the glob set
+    /// of every component, reporting all errors instead of failing on the
+    /// first one. Additionally, warnings are reported for selectors that are
+    /// duplicates of earlier ones, or that match all identifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::Severity;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:::::")?;
+    /// builder.add("zrs::::**/*.md:")?;
+    ///
+    /// // Check pending selectors
+    /// let report = builder.check();
+    /// assert!(report.is_ok());
+    /// assert_eq!(report.warnings().count(), 2);
+    ///
+    /// // Obtain structured diagnostics
+    /// let diagnostic = report.iter().next().unwrap();
+    /// assert_eq!(diagnostic.severity, Severity::Warning);
+    /// assert_eq!(diagnostic.selector, Some(1));
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "warning [selector 1]: selector matches all identifiers\n\
+    ///      warning [selector 2]: selector is a duplicate of selector 0"
+    /// );
+    ///
+    /// // Build matcher, which is unaffected by warnings
+    /// let matcher = builder.build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::mutable_key_type)]
+    #[must_use]
+    pub fn check(&self) -> Report {
+        let mut report = Report::default();
+
+        // Compile each component of each pending selector, and ensure that
+        // selectors are neither duplicates, nor match all identifiers
+        let mut seen = HashMap::new();
+        for (index, selector) in self.selectors.iter().enumerate() {
+            let components = Component::ALL
+                .map(|component| (component, selector.glob(component)));
+            let case_insensitive = self.is_case_insensitive(selector);
+            for (component, value) in &components {
+                if let Err(err) = parse(value.as_deref(), case_insensitive) {
+                    let message = err.to_string();
+                    report.push(
+                        Severity::Error,
+                        Some(index),
+                        Some(*component),
+                        message,
+                    );
+                }
+            }
+
+            // Selectors without any components match all identifiers
+            if components.iter().all(|(_, value)| value.is_none()) {
+                let message = "selector matches all identifiers";
+                report.push(Severity::Warning, Some(index), None, message);
+            }
+
+            // Selectors that are duplicates never contribute new matches
+            match seen.entry(selector) {
+                Entry::Occupied(entry) => {
+                    let message = format!(
+                        "selector is a duplicate of selector {}",
+                        entry.get()
+                    );
+                    report.push(Severity::Warning, Some(index), None, message);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(index);
+                }
+            }
+        }
+
+        // Build the glob set of each component, which might fail even though
+        // all globs compile, e.g., when exceeding size limits. If globs don't
+        // compile, the errors were already reported for each selector above
+        if let Ok(builders) = self.compile(&self.compiled()) {
+            for (component, builder) in [
+                Component::Scheme,
+                Component::Binding,
+                Component::Context,
+                Component::Path,
+                Component::Fragment,
+            ]
+            .into_iter()
+            .zip(builders)
+            {
+                if let Err(err) = builder.build() {
+                    let message = err.to_string();
+                    report.push(
+                        Severity::Error,
+                        None,
+                        Some(component),
+                        message,
+                    );
+                }
+            }
+        }
+
+        // Return report
+        report
+    }
+
     /// Builds the matcher.
     ///
     /// # Errors
@@ -138,7 +450,7 @@ impl Builder {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Matcher;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create matcher builder and add selector
     /// let mut builder = Matcher::builder();
@@ -150,26 +462,152 @@ impl Builder {
     /// # }
     /// ```
     pub fn build(self) -> Result<Matcher> {
+        let compiled = self.compiled();
+        let [scheme, binding, context, path, fragment] =
+            self.compile(&compiled)?;
+
+        // Precompute regular expressions for extracting captures
+        #[cfg(feature = "captures")]
+        let captures = self
+            .selectors
+            .iter()
+            .map(|selector| {
+                Captures::new(selector, self.is_case_insensitive(selector))
+            })
+            .collect::<Result<_>>()?;
+
+        // Literal selectors are compiled into the glob sets if matching is
+        // case-insensitive for all selectors, so they can't be looked up
+        let literals = if self.case_insensitive {
+            HashMap::new()
+        } else {
+            self.literals
+        };
+
+        // Build the glob set of each component
         Ok(Matcher {
-            scheme: self.scheme.build()?,
-            binding: self.binding.build()?,
-            context: self.context.build()?,
-            path: self.path.build()?,
-            fragment: self.fragment.build()?,
+            scheme: Arc::new(scheme.build()?),
+            binding: Arc::new(binding.build()?),
+            context: Arc::new(context.build()?),
+            path: Arc::new(path.build()?),
+            fragment: Arc::new(fragment.build()?),
+            selectors: self.selectors.into(),
+            specificity: self.specificity.into(),
+            negated: self.negated.into(),
+            absent: self.absent.into(),
+            compiled: compiled.into(),
+            literals: Arc::new(literals),
+            case_insensitive: self.case_insensitive,
+            on_empty: self.on_empty,
+            #[cfg(feature = "captures")]
+            captures,
         })
     }
+
+    /// Returns the indexes of the selectors that must be compiled into glob
+    /// sets, which are all selectors that can't be looked up as literals.
+    fn compiled(&self) -> Vec<usize> {
+        let mut compiled = vec![true; self.selectors.len()];
+        if !self.case_insensitive {
+            for &index in self.literals.values().flatten() {
+                compiled[index] = false;
+            }
+        }
+        (0..compiled.len())
+            .filter(|&index| compiled[index])
+            .collect()
+    }
+
+    /// Compiles the globs of the given selectors into glob set builders.
+    fn compile(&self, compiled: &[usize]) -> Result<[GlobSetBuilder; 5]> {
+        let mut builders = [(); 5].map(|()| GlobSetBuilder::new());
+        for selector in compiled.iter().map(|&index| &self.selectors[index]) {
+            let case_insensitive = self.is_case_insensitive(selector);
+            for (builder, value) in
+                builders.iter_mut().zip(components(selector))
+            {
+                builder.add(parse(value.as_deref(), case_insensitive)?);
+            }
+        }
+        Ok(builders)
+    }
+
+    /// Returns whether the given selector is matched case-insensitively.
+    fn is_case_insensitive(&self, selector: &Selector) -> bool {
+        self.case_insensitive || selector.is_case_insensitive()
+    }
 }
 
 // ----------------------------------------------------------------------------
 // Functions
 // ----------------------------------------------------------------------------
 
+/// Returns the globs of the components of a selector in matching order.
+fn components(selector: &Selector) -> [Option<Cow<'_, str>>; 5] {
+    Component::ALL.map(|component| selector.glob(component))
+}
+
+/// Returns the identifier a selector matches, if it only consists of literals.
+///
+/// This is the case if all components are present and contain no glob syntax,
+/// other than metacharacters escaped with [`escape`][super::escape], or are
+/// empty-only, which corresponds to absent values, and the selector is
+/// case-sensitive. The identifier is returned as its canonical string, which
+/// is used to look up literal selectors without matching the glob sets.
+fn literal(selector: &Selector) -> Option<String> {
+    if selector.is_case_insensitive() {
+        return None;
+    }
+
+    // Ensure all components are present and free of glob metacharacters
+    let [scheme, binding, context, path, fragment] = components(selector);
+    let [scheme, binding, context, path, fragment] = [
+        unescape(&scheme?)?,
+        unescape(&binding?)?,
+        unescape(&context?)?,
+        unescape(&path?)?,
+        unescape(&fragment?)?,
+    ];
+
+    // Create identifier, which doesn't normalize values, so its components are
+    // exactly the values matched by the globs of the selector
+    let id = Id::new_full(scheme, binding, context, path, fragment);
+    id.ok().map(|id| id.as_str().to_owned())
+}
+
+/// Returns the value a glob matches, if it only consists of literals.
+///
+/// Metacharacters are only accepted when wrapped in a character class of their
+/// own, e.g., `[*]`, which is how [`escape`][super::escape] escapes them, as
+/// the class then matches exactly the wrapped character.
+fn unescape(glob: &str) -> Option<String> {
+    let mut value = String::with_capacity(glob.len());
+    let mut chars = glob.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '[' => match (chars.next(), chars.next()) {
+                (Some(char), Some(']')) if !matches!(char, '!' | '^') => {
+                    value.push(char);
+                }
+                _ => return None,
+            },
+            '*' | '?' | ']' | '{' | '}' => return None,
+            _ => value.push(char),
+        }
+    }
+    Some(value)
+}
+
 /// Parses a component into a glob.
 ///
 /// Note that wildcards are implicit, which means that empty components are
-/// coerced to `**` to provide an ergonomic API for creating selectors. We must
-/// create a selector for each component, or the component count of selectors
-/// will not be coherent, which is essential for correct matching.
-fn parse(component: Option<&str>) -> Result<Glob> {
-    Ok(Glob::new(component.unwrap_or("**"))?)
+/// coerced to `**` to provide an ergonomic API for creating selectors, while
+/// empty-only components are given as the empty glob, so they never match
+/// present values. We must create a selector for each component, or the
+/// component count of selectors will not be coherent, which is essential for
+/// correct matching.
+fn parse(component: Option<&str>, case_insensitive: bool) -> Result<Glob> {
+    Ok(GlobBuilder::new(component.unwrap_or("**"))
+        .case_insensitive(case_insensitive)
+        .build()?)
 }
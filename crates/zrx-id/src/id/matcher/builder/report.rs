@@ -0,0 +1,189 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Matcher builder report.
+
+use std::fmt;
+use std::slice::Iter;
+
+use crate::id::Component;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Matcher builder report.
+///
+/// Reports are returned when checking the builder returned by
+/// [`Matcher::builder`][], and collect all problems of the pending selectors
+/// at once, so a configuration can be validated in a single pass. Errors are
+/// problems that make building the matcher fail, while warnings are selectors
+/// that are likely not what was intended.
+///
+/// [`Matcher::builder`]: crate::Matcher::builder
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Diagnostics.
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Matcher builder diagnostic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Severity.
+    pub severity: Severity,
+    /// Selector index, if specific to a selector.
+    pub selector: Option<usize>,
+    /// Component, if specific to a component.
+    pub component: Option<Component>,
+    /// Message.
+    pub message: String,
+}
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Matcher builder diagnostic severity.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Warning, which doesn't prevent building.
+    Warning,
+    /// Error, which prevents building.
+    Error,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Report {
+    /// Adds a diagnostic to the report.
+    pub(crate) fn push(
+        &mut self, severity: Severity, selector: Option<usize>,
+        component: Option<Component>, message: impl Into<String>,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            selector,
+            component,
+            message: message.into(),
+        });
+    }
+
+    /// Creates an iterator over the diagnostics.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, Diagnostic> {
+        self.diagnostics.iter()
+    }
+
+    /// Creates an iterator over the errors.
+    #[inline]
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    /// Creates an iterator over the warnings.
+    #[inline]
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Warning)
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl Report {
+    /// Returns whether the report contains no errors.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Returns the number of diagnostics.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.diagnostics.len()
+    }
+
+    /// Returns whether there are any diagnostics.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<'a> IntoIterator for &'a Report {
+    type Item = &'a Diagnostic;
+    type IntoIter = Iter<'a, Diagnostic>;
+
+    /// Creates an iterator over the diagnostics.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Report {
+    /// Formats the report for display, one diagnostic per line.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, diagnostic) in self.diagnostics.iter().enumerate() {
+            if index > 0 {
+                f.write_str("\n")?;
+            }
+            diagnostic.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    /// Formats the diagnostic for display.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.severity.fmt(f)?;
+        match (self.selector, self.component) {
+            (Some(selector), Some(component)) => {
+                write!(f, " [selector {selector}, {component}]")?;
+            }
+            (Some(selector), None) => write!(f, " [selector {selector}]")?,
+            (None, Some(component)) => write!(f, " [{component}]")?,
+            (None, None) => {}
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl fmt::Display for Severity {
+    /// Formats the severity for display.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Match with captures.
+
+use regex_automata::meta::Regex;
+use regex_automata::util::syntax;
+
+use crate::id::Component;
+
+use super::error::Result;
+use super::pattern::Pattern;
+use super::selector::Selector;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Match with captures.
+///
+/// Matches are returned by [`Matcher::matches_with_captures`][], and carry the
+/// index of the matching selector, as well as the values matched by each of
+/// the wildcards of a component in order, i.e., `*`, `**` and `?`. Recursive
+/// wildcards capture the directories they match without the separators that
+/// surround them. Components without wildcards have no captures.
+///
+/// [`Matcher::matches_with_captures`]: crate::Matcher::matches_with_captures
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Match {
+    /// Index of the selector.
+    pub index: usize,
+    /// Captures of the scheme component.
+    pub scheme: Vec<String>,
+    /// Captures of the binding component.
+    pub binding: Vec<String>,
+    /// Captures of the context component.
+    pub context: Vec<String>,
+    /// Captures of the path component.
+    pub path: Vec<String>,
+    /// Captures of the fragment component.
+    pub fragment: Vec<String>,
+}
+
+/// Capture-aware regular expressions of a selector.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Captures {
+    /// Regular expressions of components with wildcards.
+    regexes: [Option<Regex>; 5],
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Match {
+    /// Returns the captures of the given component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher and obtain captures of path
+    /// let matcher: Matcher = "zrs::::blog/*/index.md:".parse()?;
+    /// let id = Id::new("file", "docs", "blog/hello-world/index.md")?;
+    /// let matches = matcher.matches_with_captures(&id)?;
+    /// assert_eq!(matches[0].captures(Component::Path), ["hello-world"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn captures(&self, component: Component) -> &[String] {
+        match component {
+            Component::Scheme => &self.scheme,
+            Component::Binding => &self.binding,
+            Component::Context => &self.context,
+            Component::Path => &self.path,
+            Component::Fragment => &self.fragment,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Captures {
+    /// Compiles the capture-aware regular expressions of a selector.
+    ///
+    /// Since [`globset`][] doesn't expose captures, each component containing
+    /// wildcards is translated into a regular expression with one capture
+    /// group for each wildcard. Components containing character classes or
+    /// alternations are not translated, and thus don't yield captures. If the
+    /// selector is matched case-insensitively, so are the regular expressions.
+    ///
+    /// [`globset`]: https://crates.io/crates/globset
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Regex`][], if a regular expression can't
+    /// be built, which should never happen.
+    ///
+    /// [`Error::Regex`]: super::Error::Regex
+    pub fn new(selector: &Selector, case_insensitive: bool) -> Result<Self> {
+        let mut regexes = [None, None, None, None, None];
+        for (regex, value) in regexes.iter_mut().zip([
+            selector.scheme(),
+            selector.binding(),
+            selector.context(),
+            selector.path(),
+            selector.fragment(),
+        ]) {
+            let Some(Ok(pattern)) = value.as_deref().map(Pattern::new) else {
+                continue;
+            };
+
+            // Only components with wildcards can have captures
+            if pattern.wildcards() > 0 {
+                let config = syntax::Config::new()
+                    .utf8(false)
+                    .case_insensitive(case_insensitive);
+                *regex = Some(
+                    Regex::builder()
+                        .syntax(config)
+                        .build(&pattern.to_regex())
+                        .map_err(Box::new)?,
+                );
+            }
+        }
+        Ok(Self { regexes })
+    }
+
+    /// Extracts the captures of the given component values.
+    ///
+    /// Absent values don't yield captures, as they're only matched by empty
+    /// components, just like in the matcher.
+    pub fn extract(&self, index: usize, values: [Option<&str>; 5]) -> Match {
+        let mut captures =
+            self.regexes.iter().zip(values).map(|(regex, value)| {
+                match (regex, value) {
+                    (Some(regex), Some(value)) => capture(regex, value),
+                    _ => Vec::new(),
+                }
+            });
+
+        // Collect captures of all components
+        let mut next = || captures.next().unwrap_or_default();
+        Match {
+            index,
+            scheme: next(),
+            binding: next(),
+            context: next(),
+            path: next(),
+            fragment: next(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the values captured by the groups of the regular expression.
+///
+/// Values are extracted from bytes, which is why they might not be valid UTF-8
+/// when `?` matches a part of a multi-byte character, in which case invalid
+/// sequences are replaced. Groups that didn't participate are empty.
+fn capture(regex: &Regex, value: &str) -> Vec<String> {
+    let mut captures = regex.create_captures();
+    regex.captures(value.as_bytes(), &mut captures);
+    (1..captures.group_len())
+        .map(|group| {
+            captures.get_group(group).map_or_else(String::new, |span| {
+                let bytes = &value.as_bytes()[span.range()];
+                String::from_utf8_lossy(bytes).into_owned()
+            })
+        })
+        .collect()
+}
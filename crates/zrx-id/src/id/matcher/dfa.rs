@@ -0,0 +1,416 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! DFA matcher.
+
+use regex_automata::meta::Regex;
+use regex_automata::{Input, PatternSet};
+use std::str::FromStr;
+
+use crate::id::{Component, Id, ToId};
+
+use super::error::{Error, Result};
+
+mod builder;
+
+use builder::Builder;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Separator between components in the joined representation.
+///
+/// This is the ASCII unit separator, which is never matched by any wildcard,
+/// so patterns can't cross component boundaries.
+const SEPARATOR: u8 = 0x1F;
+
+/// Marker for absent values in the joined representation.
+///
+/// This byte never appears in valid UTF-8, and is never matched by wildcards,
+/// so absent values are only matched by empty components.
+const ABSENT: u8 = 0xFF;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// DFA matcher.
+///
+/// The [`DfaMatcher`] provides the same API as the [`Matcher`][], but instead
+/// of compiling a [`GlobSet`][] for each of the five components, it translates
+/// each selector into a single regular expression over all components, joined
+/// with a separator that is never matched by wildcards, and compiles all of
+/// them into one automaton. Thus, an identifier is matched against all the
+/// selectors in a single pass.
+///
+/// Which implementation is faster depends on the selectors. The [`Matcher`][]
+/// is faster to build, and wins when most selectors are literals or simple
+/// extension patterns, for which [`globset`][] uses specialized strategies.
+/// The [`DfaMatcher`] wins when there are many selectors with wildcards that
+/// need to be evaluated with regular expressions anyway, as it avoids probing
+/// five automata and intersecting the results.
+///
+/// Note that component values must not contain the ASCII unit separator, as
+/// it can't be told apart from the separators between components, which is
+/// why identifiers and selectors containing it are rejected with
+/// [`Error::Separator`].
+///
+/// [`GlobSet`]: globset::GlobSet
+/// [`Matcher`]: crate::Matcher
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::prelude::*;
+///
+/// // Create identifier
+/// let id = Id::new("file", "docs", "index.md")?;
+///
+/// // Create matcher builder and add selector
+/// let mut builder = DfaMatcher::builder();
+/// builder.add("zrs::::**/*.md:")?;
+///
+/// // Create matcher from builder
+/// let matcher = builder.build()?;
+///
+/// // Check if the id matches the selector
+/// assert!(matcher.is_match(&id)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DfaMatcher {
+    /// Regular expression over all selectors.
+    regex: Regex,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl DfaMatcher {
+    /// Creates a matcher builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder
+    /// let mut builder = DfaMatcher::builder();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Checks if one of the underlying selectors matches an identifier.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid, or
+    /// [`Error::Separator`], if a component contains the unit separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = DfaMatcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:git:::docs/**:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Check if identifiers match the selectors
+    /// assert!(matcher.is_match(&Id::new("file", "docs", "index.md")?)?);
+    /// assert!(matcher.is_match(&Id::new("git", "src", "docs/a.rs")?)?);
+    /// assert!(!matcher.is_match(&Id::new("file", "src", "docs/a.rs")?)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn is_match<I>(&self, id: I) -> Result<bool>
+    where
+        I: ToId,
+    {
+        let id = id.to_id()?;
+        Ok(self.regex.is_match(join(&id)?.as_slice()))
+    }
+
+    /// Returns the match set of the selectors that match an identifier.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid, or
+    /// [`Error::Separator`], if a component contains the unit separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier
+    /// let id = Id::new("file", "docs", "guide/index.md")?;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = DfaMatcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:::docs:guide/**:")?;
+    /// builder.add("zrs::::index.m?:")?;
+    /// builder.add("zrs::::guide/*.{md,txt}:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Obtain selectors matched by identifier
+    /// let matches = matcher.matches(&id)?;
+    /// assert_eq!(matches, [0, 1, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Absent values are only matched by empty components, so results are
+    /// identical to those of a [`Matcher`][]:
+    ///
+    /// [`Matcher`]: crate::Matcher
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers
+    /// let ids = [
+    ///     Id::new("file", "docs", "index.md")?,
+    ///     Id::new("file", "docs", "index.md")?.with_fragment("top")?,
+    ///     Id::new("git", "docs", "index.md")?.with_binding("main")?,
+    /// ];
+    ///
+    /// // Create matcher builders and add selectors
+    /// let mut builder = DfaMatcher::builder();
+    /// let mut expected = Matcher::builder();
+    /// for value in [
+    ///     "zrs::::**/*.md:",
+    ///     "zrs::::**/*.md:*",
+    ///     "zrs::::**/*.md:?*",
+    ///     "zrs::*::**/*.md:",
+    ///     "zrs::**:::",
+    ///     "zrs::::**/*.md:-",
+    ///     "zrs::-:::",
+    /// ] {
+    ///     builder.add(value)?;
+    ///     expected.add(value)?;
+    /// }
+    ///
+    /// // Ensure both matchers yield the same match sets
+    /// let matcher = builder.build()?;
+    /// let expected = expected.build()?;
+    /// for id in &ids {
+    ///     assert_eq!(matcher.matches(id)?, expected.matches(id)?, "{id}");
+    /// }
+    /// assert_eq!(matcher.matches(&ids[0])?, [0, 5, 6]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Results are identical to those of a [`Matcher`][] for random selectors
+    /// and identifiers, which are generated from a small alphabet, so they
+    /// frequently match:
+    ///
+    /// [`Matcher`]: crate::Matcher
+    ///
+    /// ```
+    /// # #[cfg(feature = "proptest")]
+    /// # fn main() {
+    /// use proptest::collection::vec;
+    /// use proptest::prelude::*;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create strategies for identifiers and selectors
+    /// let id = ("[ab]", "[ab]?", "[ab]{1,2}", "[abAB]{1,2}(/[ab]{1,2}){0,2}")
+    ///     .prop_flat_map(|parts| (Just(parts), "[ab]?"))
+    ///     .prop_map(|((scheme, binding, context, path), fragment)| {
+    ///         Id::new_full(scheme, binding, context, path, fragment).unwrap()
+    ///     });
+    /// let glob = r"(-|[ab*?]{1,2}|\{a,b\}|\[!a\])?";
+    /// let path = r"(\*\*|[abAB*?]{1,2})(/(\*\*|[ab*?]{1,2})){0,2}";
+    /// let selector = (glob, glob, glob, path, glob, any::<bool>())
+    ///     .prop_map(|(scheme, binding, context, path, fragment, flag)| {
+    ///         format!("zrs:{scheme}:{binding}:{context}:{path}:{fragment}")
+    ///             .parse::<Selector>()
+    ///             .unwrap()
+    ///             .with_case_insensitive(flag)
+    ///     });
+    ///
+    /// // Ensure both matchers yield the same match sets
+    /// proptest!(|(ids in vec(id, 1..8), selectors in vec(selector, 1..8))| {
+    ///     let mut builder = DfaMatcher::builder();
+    ///     let mut expected = Matcher::builder();
+    ///     for selector in &selectors {
+    ///         builder.add(selector).unwrap();
+    ///         expected.add(selector).unwrap();
+    ///     }
+    ///     let matcher = builder.build().unwrap();
+    ///     let expected = expected.build().unwrap();
+    ///     for id in &ids {
+    ///         let matches = matcher.matches(id).unwrap();
+    ///         prop_assert_eq!(matches, expected.matches(id).unwrap());
+    ///     }
+    /// });
+    /// # }
+    /// # #[cfg(not(feature = "proptest"))]
+    /// # fn main() {}
+    /// ```
+    ///
+    /// Identifiers and selectors containing the unit separator are rejected,
+    /// as it can't be told apart from the separators between components:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::Error;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher from builder
+    /// let matcher: DfaMatcher = "zrs::::**:".parse()?;
+    ///
+    /// // Ensure identifiers containing the unit separator are rejected
+    /// let id = Id::new("file", "docs", "a\u{1F}b.md")?;
+    /// let res = matcher.matches(&id);
+    /// assert!(matches!(res, Err(Error::Separator { name: "path" })));
+    /// let res = matcher.is_match(&id);
+    /// assert!(matches!(res, Err(Error::Separator { name: "path" })));
+    ///
+    /// // Ensure selectors containing the unit separator are rejected
+    /// let mut builder = DfaMatcher::builder();
+    /// let res = builder.add("zrs::::a\u{1F}*.md:");
+    /// assert!(matches!(res, Err(Error::Separator { name: "path" })));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn matches<I>(&self, id: I) -> Result<Vec<usize>>
+    where
+        I: ToId,
+    {
+        let id = id.to_id()?;
+        let value = join(&id)?;
+
+        // Obtain match set by collecting all patterns that match
+        let mut patterns = PatternSet::new(self.regex.pattern_len());
+        let input = Input::new(value.as_slice());
+        self.regex.which_overlapping_matches(&input, &mut patterns);
+        Ok(patterns.iter().map(|pattern| pattern.as_usize()).collect())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl FromStr for DfaMatcher {
+    type Err = Error;
+
+    /// Creates a matcher from a string.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the selector is invalid, or if one of
+    /// its components can't be compiled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher from string
+    /// let matcher: DfaMatcher = "zrs::::**/*.md:".parse()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn from_str(value: &str) -> Result<Self> {
+        let mut builder = DfaMatcher::builder();
+        builder.add(value)?;
+        builder.build()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Joins the components of an identifier with separators.
+///
+/// Absent values are joined as [`ABSENT`], which is only matched by empty
+/// components, just like in the [`Matcher`][], so that both implementations
+/// yield identical results.
+///
+/// [`Matcher`]: crate::Matcher
+///
+/// # Errors
+///
+/// If a value contains [`SEPARATOR`], [`Error::Separator`] is returned.
+fn join(id: &Id) -> Result<Vec<u8>> {
+    let view = id.view();
+    let values = [
+        Some(view.scheme),
+        view.binding,
+        Some(view.context),
+        Some(view.path),
+        view.fragment,
+    ];
+
+    // Compute required capacity and join values
+    let capacity = values
+        .iter()
+        .map(|value| value.as_ref().map_or(1, |value| value.len()) + 1)
+        .sum();
+    let mut buffer = Vec::with_capacity(capacity);
+    for (component, value) in Component::ALL.into_iter().zip(&values) {
+        if component != Component::Scheme {
+            buffer.push(SEPARATOR);
+        }
+        match value {
+            Some(value) if value.as_bytes().contains(&SEPARATOR) => {
+                let name = component.name();
+                return Err(Error::Separator { name });
+            }
+            Some(value) => buffer.extend_from_slice(value.as_bytes()),
+            None => buffer.push(ABSENT),
+        }
+    }
+    Ok(buffer)
+}
@@ -0,0 +1,260 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! DFA matcher builder.
+
+use globset::GlobBuilder;
+use regex_automata::meta::Regex;
+use regex_automata::MatchKind;
+use regex_syntax::hir::{
+    Class, ClassBytes, ClassBytesRange, ClassUnicode, ClassUnicodeRange, Hir,
+    HirKind, Look,
+};
+use regex_syntax::ParserBuilder;
+
+use crate::id::Component;
+
+use super::super::error::{Error, Result};
+use super::super::selector::ToSelector;
+use super::{DfaMatcher, ABSENT, SEPARATOR};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// DFA matcher builder.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    /// Regular expressions of selectors.
+    hirs: Vec<Hir>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Builder {
+    /// Creates a DFA matcher builder.
+    ///
+    /// Note that the canonical way to create a [`DfaMatcher`] is to invoke
+    /// the [`DfaMatcher::builder`] method, which creates an instance of
+    /// [`Builder`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder
+    /// let mut builder = DfaMatcher::builder();
+    /// ```
+    #[allow(clippy::new_without_default)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self { hirs: Vec::new() }
+    }
+
+    /// Adds a selector to the matcher.
+    ///
+    /// This method translates each component of the [`Selector`][] into a
+    /// regular expression with the same semantics as [`Glob`], and joins them
+    /// with a separator into a single regular expression for the selector.
+    /// If a component is empty, it is coerced to `**`, which additionally
+    /// matches absent values, as only empty components do. Case-insensitive
+    /// selectors are translated into case-insensitive regular expressions.
+    ///
+    /// [`Glob`]: globset::Glob
+    /// [`Selector`]: crate::Selector
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given selector is invalid, or if a
+    /// component cannot successfully be parsed into a valid [`Glob`]. Negated
+    /// selectors are not supported, and return [`Error::Negated`], and
+    /// components containing the unit separator return [`Error::Separator`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selector
+    /// let mut builder = DfaMatcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    ///
+    /// // Negated selectors are not supported
+    /// assert!(builder.add("!zrs::::drafts/**:").is_err());
+    ///
+    /// // Case-insensitive selectors are supported
+    /// let selector: Selector = "zrs::::docs/*.md:".parse()?;
+    /// builder.add(&selector.with_case_insensitive(true))?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    /// let id = Id::new("file", "docs", "Docs/INDEX.MD")?;
+    /// assert_eq!(matcher.matches(&id)?, [1]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn add<S>(&mut self, selector: S) -> Result<&mut Self>
+    where
+        S: ToSelector,
+    {
+        let selector = selector.to_selector()?;
+        if selector.is_negated() {
+            return Err(Error::Negated);
+        }
+        let components =
+            Component::ALL.map(|component| selector.glob(component));
+
+        // Translate each component of the given selector, and join them with
+        // separators, anchoring the resulting expression at both ends
+        let mut hirs = vec![Hir::look(Look::Start)];
+        for (component, glob) in Component::ALL.into_iter().zip(&components) {
+            if component != Component::Scheme {
+                hirs.push(Hir::literal([SEPARATOR]));
+            }
+            let separator = char::from(SEPARATOR);
+            if glob.as_deref().is_some_and(|glob| glob.contains(separator)) {
+                let name = component.name();
+                return Err(Error::Separator { name });
+            }
+            hirs.push(parse(glob.as_deref(), selector.is_case_insensitive())?);
+        }
+        hirs.push(Hir::look(Look::End));
+
+        // Add selector and return self for chaining
+        self.hirs.push(Hir::concat(hirs));
+        Ok(self)
+    }
+
+    /// Builds the matcher.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the automaton exceeds the size limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selector
+    /// let mut builder = DfaMatcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build(self) -> Result<DfaMatcher> {
+        let config = Regex::config()
+            .match_kind(MatchKind::All)
+            .utf8_empty(false)
+            .nfa_size_limit(Some(10 * (1 << 20)))
+            .hybrid_cache_capacity(10 * (1 << 20));
+
+        // Compile all selectors into a single automaton
+        let regex = Regex::builder()
+            .configure(config)
+            .build_many_from_hir(&self.hirs)
+            .map_err(Box::new)?;
+        Ok(DfaMatcher { regex })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Parses a component into a regular expression.
+///
+/// The component is first compiled into a [`Glob`] to obtain the very same
+/// regular expression that [`globset`] uses, which is then stripped of its
+/// anchors, and all classes are adjusted to not match the separator, or the
+/// marker for absent values, which only empty and empty-only components match.
+///
+/// [`Glob`]: globset::Glob
+fn parse(component: Option<&str>, case_insensitive: bool) -> Result<Hir> {
+    if component == Some("") {
+        return Ok(Hir::literal([ABSENT]));
+    }
+    let glob = GlobBuilder::new(component.unwrap_or("**"))
+        .case_insensitive(case_insensitive)
+        .build()?;
+    let hir = ParserBuilder::new()
+        .utf8(false)
+        .dot_matches_new_line(true)
+        .build()
+        .parse(glob.regex())
+        .map_err(Box::new)?;
+
+    // Return regular expression, matching absent values if empty
+    let hir = rewrite(hir);
+    if component.is_none() {
+        Ok(Hir::alternation(vec![hir, Hir::literal([ABSENT])]))
+    } else {
+        Ok(hir)
+    }
+}
+
+/// Rewrites a regular expression to be embeddable into a selector.
+fn rewrite(hir: Hir) -> Hir {
+    match hir.into_kind() {
+        HirKind::Look(_) => Hir::empty(),
+        HirKind::Class(Class::Bytes(mut class)) => {
+            class.difference(&ClassBytes::new([
+                ClassBytesRange::new(SEPARATOR, SEPARATOR),
+                ClassBytesRange::new(ABSENT, ABSENT),
+            ]));
+            Hir::class(Class::Bytes(class))
+        }
+        HirKind::Class(Class::Unicode(mut class)) => {
+            let char = char::from(SEPARATOR);
+            let range = ClassUnicodeRange::new(char, char);
+            class.difference(&ClassUnicode::new([range]));
+            Hir::class(Class::Unicode(class))
+        }
+        HirKind::Repetition(mut repetition) => {
+            repetition.sub = Box::new(rewrite(*repetition.sub));
+            Hir::repetition(repetition)
+        }
+        HirKind::Capture(mut capture) => {
+            capture.sub = Box::new(rewrite(*capture.sub));
+            Hir::capture(capture)
+        }
+        HirKind::Concat(hirs) => {
+            Hir::concat(hirs.into_iter().map(rewrite).collect())
+        }
+        HirKind::Alternation(hirs) => {
+            Hir::alternation(hirs.into_iter().map(rewrite).collect())
+        }
+        HirKind::Literal(literal) => Hir::literal(literal.0),
+        HirKind::Empty => Hir::empty(),
+    }
+}
@@ -0,0 +1,419 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Dispatcher.
+
+use std::cell::OnceCell;
+use std::fmt;
+
+use crate::id::Id;
+
+use super::error::Result;
+use super::selector::{Selector, ToSelector};
+use super::Matcher;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Dispatcher.
+///
+/// Dispatchers associate handlers with selectors, and invoke all handlers with
+/// matching selectors for a given identifier, passing a mutable context. The
+/// underlying [`Matcher`] is not rebuilt when subscribing, but only once before
+/// the next dispatch, so subscribing `n` handlers in a row compiles the glob
+/// sets once instead of `n` times. Unsubscribing is cheap, as the subscription
+/// is only marked as removed, and compacted on the next subscription.
+/// Dispatchers are meant to be used from a single thread, so no locks are
+/// involved.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::matcher::Dispatcher;
+/// use zrx_id::prelude::*;
+///
+/// // Create dispatcher and subscribe handlers
+/// let mut dispatcher = Dispatcher::new();
+/// dispatcher.subscribe("zrs::::**/*.md:", |id, log: &mut Vec<String>| {
+///     log.push(format!("markdown: {}", id.path()));
+/// })?;
+/// dispatcher.subscribe("zrs:::docs::", |id, log: &mut Vec<String>| {
+///     log.push(format!("docs: {}", id.path()));
+/// })?;
+///
+/// // Dispatch identifier to matching handlers
+/// let mut log = Vec::new();
+/// let id = Id::new("file", "docs", "index.md")?;
+/// assert_eq!(dispatcher.dispatch(&id, &mut log), 2);
+/// assert_eq!(log, ["markdown: index.md", "docs: index.md"]);
+///
+/// // Dispatch identifier without matching handlers
+/// let id = Id::new("file", "src", "lib.rs")?;
+/// assert_eq!(dispatcher.dispatch(&id, &mut log), 0);
+/// assert_eq!(log.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Dispatcher<Ctx> {
+    /// Subscriptions, with removed ones left empty until compaction.
+    subscriptions: Vec<Option<Subscription<Ctx>>>,
+    /// Matcher for subscriptions, built lazily before dispatching.
+    matcher: OnceCell<Option<Matcher>>,
+    /// Next subscription identifier.
+    next: u64,
+}
+
+/// Subscription identifier.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SubscriptionId(u64);
+
+// ----------------------------------------------------------------------------
+
+/// Subscription.
+struct Subscription<Ctx> {
+    /// Subscription identifier.
+    id: SubscriptionId,
+    /// Selector.
+    selector: Selector,
+    /// Handler.
+    handler: Handler<Ctx>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl<Ctx> Dispatcher<Ctx> {
+    /// Creates a dispatcher.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::matcher::Dispatcher;
+    ///
+    /// // Create dispatcher
+    /// let dispatcher = Dispatcher::<()>::new();
+    /// assert!(dispatcher.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            subscriptions: Vec::new(),
+            matcher: OnceCell::new(),
+            next: 0,
+        }
+    }
+
+    /// Subscribes a handler to a selector.
+    ///
+    /// This method compacts removed subscriptions and checks the selector, but
+    /// doesn't rebuild the matcher, which is deferred until the next dispatch.
+    /// Rebuilding compiles the glob sets of all subscriptions, which is linear
+    /// in their number, so subscribing many handlers before dispatching only
+    /// pays this cost once. If the selector is invalid, the dispatcher is left
+    /// untouched.
+    ///
+    /// Negated selectors, e.g., `!zrs::::drafts/**:`, exclude the identifiers
+    /// they match from all other subscriptions. As they never match anything
+    /// themselves, their handler is never invoked - it's only kept, so that
+    /// negated subscriptions can be created and removed like any other.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given selector is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::Dispatcher;
+    ///
+    /// // Create dispatcher and subscribe handler
+    /// let mut dispatcher = Dispatcher::new();
+    /// dispatcher.subscribe("zrs::::**/*.md:", |_, count: &mut usize| {
+    ///     *count += 1;
+    /// })?;
+    ///
+    /// // Subscribe handler with invalid selector
+    /// let res = dispatcher.subscribe("zrs::::docs/[a-z:", |_, _| {});
+    /// assert!(res.is_err());
+    /// assert_eq!(dispatcher.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn subscribe<S, F>(
+        &mut self, selector: S, handler: F,
+    ) -> Result<SubscriptionId>
+    where
+        S: ToSelector,
+        F: Fn(&Id, &mut Ctx) + 'static,
+    {
+        let selector = selector.to_selector()?.into_owned();
+
+        // Check the selector before changing anything, so we don't end up in
+        // an invalid state, but defer building the matcher until dispatching
+        Matcher::builder().add(&selector)?;
+
+        // Compact subscriptions, so they line up with the matcher's indexes
+        self.subscriptions.retain(Option::is_some);
+        let id = SubscriptionId(self.next);
+        self.subscriptions.push(Some(Subscription {
+            id,
+            selector,
+            handler: Box::new(handler),
+        }));
+
+        // Invalidate matcher and return subscription identifier
+        self.matcher.take();
+        self.next += 1;
+        Ok(id)
+    }
+
+    /// Unsubscribes a handler.
+    ///
+    /// Returns whether the subscription was found and removed. Removing a
+    /// negated subscription invalidates the matcher, since it still excludes
+    /// the identifiers the selector matches, so it's rebuilt before the next
+    /// dispatch, while other subscriptions are only marked as removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::Dispatcher;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create dispatcher and subscribe handlers
+    /// let mut dispatcher = Dispatcher::new();
+    /// let a = dispatcher.subscribe("zrs::::**/*.md:", |_, log: &mut Vec<_>| {
+    ///     log.push("a");
+    /// })?;
+    /// dispatcher.subscribe("zrs::::**:", |_, log: &mut Vec<_>| {
+    ///     log.push("b");
+    /// })?;
+    ///
+    /// // Unsubscribe handler
+    /// assert!(dispatcher.unsubscribe(a));
+    /// assert!(!dispatcher.unsubscribe(a));
+    ///
+    /// // Dispatch identifier to remaining handlers
+    /// let mut log = Vec::new();
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(dispatcher.dispatch(&id, &mut log), 1);
+    /// assert_eq!(log, ["b"]);
+    ///
+    /// // Subscribe handler, compacting removed subscriptions
+    /// let c = dispatcher.subscribe("zrs:::docs::", |_, log: &mut Vec<_>| {
+    ///     log.push("c");
+    /// })?;
+    /// assert_eq!(dispatcher.dispatch(&id, &mut log), 2);
+    /// assert_eq!(log, ["b", "b", "c"]);
+    ///
+    /// // Unsubscribe handler after dispatching
+    /// assert!(dispatcher.unsubscribe(c));
+    /// assert_eq!(dispatcher.dispatch(&id, &mut log), 1);
+    /// assert_eq!(log, ["b", "b", "c", "b"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Unsubscribing a negated handler stops excluding identifiers:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::Dispatcher;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create dispatcher and subscribe handlers
+    /// let mut dispatcher = Dispatcher::new();
+    /// dispatcher.subscribe("zrs::::**/*.md:", |_, count: &mut usize| {
+    ///     *count += 1;
+    /// })?;
+    /// let drafts = dispatcher.subscribe("!zrs::::drafts/**:", |_, _| {})?;
+    ///
+    /// // Dispatch identifier excluded by negated handler
+    /// let mut count = 0;
+    /// let id = Id::new("file", "docs", "drafts/a.md")?;
+    /// assert_eq!(dispatcher.dispatch(&id, &mut count), 0);
+    ///
+    /// // Unsubscribe negated handler and dispatch again
+    /// assert!(dispatcher.unsubscribe(drafts));
+    /// assert_eq!(dispatcher.dispatch(&id, &mut count), 1);
+    /// assert_eq!(count, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let slot = self.subscriptions.iter_mut().find(|slot| {
+            slot.as_ref()
+                .is_some_and(|subscription| subscription.id == id)
+        });
+        let Some(subscription) = slot.and_then(Option::take) else {
+            return false;
+        };
+
+        // Negated selectors exclude identifiers inside of the matcher, so it
+        // must be rebuilt without them, and not just skip their handlers
+        if subscription.selector.is_negated() {
+            self.matcher.take();
+        }
+
+        // Compact subscriptions right away if the matcher is yet to be built,
+        // so they line up with its indexes once it is
+        if self.matcher.get().is_none() {
+            self.subscriptions.retain(Option::is_some);
+        }
+        true
+    }
+
+    /// Dispatches an identifier to all matching handlers.
+    ///
+    /// Handlers are invoked in the order they were subscribed. Returns the
+    /// number of handlers that were invoked. If handlers were subscribed since
+    /// the last dispatch, the matcher is rebuilt first.
+    pub fn dispatch(&self, id: &Id, ctx: &mut Ctx) -> usize {
+        self.invoke(id, ctx, |matcher| matcher.matches(id))
+    }
+
+    /// Dispatches an identifier to all matching handlers, ordered by
+    /// descending specificity.
+    ///
+    /// Handlers are invoked in the order of [`Selector::specificity`], most
+    /// specific first, and handlers of equal specificity are invoked in the
+    /// order they were subscribed. Returns the number of handlers that were
+    /// invoked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::Dispatcher;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create dispatcher and subscribe overlapping handlers
+    /// let mut dispatcher = Dispatcher::new();
+    /// for selector in ["zrs::::**:", "zrs::::index.md:", "zrs::::*.md:"] {
+    ///     dispatcher.subscribe(selector, move |_, log: &mut Vec<_>| {
+    ///         log.push(selector);
+    ///     })?;
+    /// }
+    ///
+    /// // Dispatch identifier to matching handlers, most specific first
+    /// let mut log = Vec::new();
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(dispatcher.dispatch_by_specificity(&id, &mut log), 3);
+    /// assert_eq!(log, ["zrs::::index.md:", "zrs::::*.md:", "zrs::::**:"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dispatch_by_specificity(&self, id: &Id, ctx: &mut Ctx) -> usize {
+        self.invoke(id, ctx, |matcher| matcher.matches_by_specificity(id))
+    }
+
+    /// Invokes the handlers for the match set obtained from the given function.
+    fn invoke<F>(&self, id: &Id, ctx: &mut Ctx, f: F) -> usize
+    where
+        F: FnOnce(&Matcher) -> Result<Vec<usize>>,
+    {
+        let Some(matcher) = self.matcher() else {
+            return 0;
+        };
+
+        // Matching can't fail, since the identifier is already valid
+        let indexes = f(matcher).unwrap_or_default();
+        let mut count = 0;
+        for index in indexes {
+            if let Some(subscription) = &self.subscriptions[index] {
+                (subscription.handler)(id, ctx);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Returns the matcher, building it from all subscriptions if necessary.
+    ///
+    /// Building can't fail for selectors that were checked when subscribing,
+    /// unless the size limits of the compiled glob sets are exceeded, in which
+    /// case there's no matcher, and no handlers are invoked.
+    fn matcher(&self) -> Option<&Matcher> {
+        let matcher = self.matcher.get_or_init(|| {
+            let mut builder = Matcher::builder();
+            for subscription in self.subscriptions.iter().flatten() {
+                builder.add(&subscription.selector).ok()?;
+            }
+            builder.build().ok()
+        });
+        matcher.as_ref()
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl<Ctx> Dispatcher<Ctx> {
+    /// Returns the number of subscriptions.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.subscriptions.iter().flatten().count()
+    }
+
+    /// Returns whether there are any subscriptions.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<Ctx> Default for Dispatcher<Ctx> {
+    /// Creates a dispatcher.
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Ctx> fmt::Debug for Dispatcher<Ctx> {
+    /// Formats the dispatcher for debugging.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Dispatcher")
+            .field("subscriptions", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Type aliases
+// ----------------------------------------------------------------------------
+
+/// Handler.
+type Handler<Ctx> = Box<dyn Fn(&Id, &mut Ctx)>;
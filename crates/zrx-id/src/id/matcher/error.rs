@@ -35,9 +35,31 @@ use crate::{format, id, path};
 #[derive(Debug, Error)]
 pub enum Error {
     /// Globset error.
+    #[cfg(feature = "globset")]
     #[error(transparent)]
     Glob(#[from] globset::Error),
 
+    /// Invalid glob in a component.
+    #[cfg(feature = "globset")]
+    #[error("invalid {name} pattern: {source}")]
+    Pattern {
+        /// Component name.
+        name: &'static str,
+        /// Globset error.
+        #[source]
+        source: globset::Error,
+    },
+
+    /// Regular expression syntax error.
+    #[cfg(feature = "dfa")]
+    #[error(transparent)]
+    Syntax(#[from] Box<regex_syntax::Error>),
+
+    /// Regular expression build error.
+    #[cfg(any(feature = "dfa", feature = "captures"))]
+    #[error(transparent)]
+    Regex(#[from] Box<regex_automata::meta::BuildError>),
+
     /// Format error.
     #[error(transparent)]
     Format(#[from] format::Error),
@@ -53,6 +75,37 @@ pub enum Error {
     /// Invalid prefix.
     #[error("invalid prefix")]
     Prefix,
+
+    /// Unsupported format version.
+    #[error("unsupported version: {0}")]
+    Version(u32),
+
+    /// Unsupported pattern.
+    #[error("unsupported pattern: {0}")]
+    Unsupported(String),
+
+    /// Invalid rewrite template.
+    #[cfg(feature = "captures")]
+    #[error("invalid template: {0}")]
+    Template(String),
+
+    /// Unsupported unit separator in a component.
+    #[cfg(feature = "dfa")]
+    #[error("{name} must not contain the unit separator")]
+    Separator {
+        /// Component name.
+        name: &'static str,
+    },
+
+    /// Unsupported negated selector.
+    #[cfg(any(feature = "dfa", feature = "lite-matcher"))]
+    #[error("negated selectors are not supported")]
+    Negated,
+
+    /// Unsupported case-insensitive selector.
+    #[cfg(feature = "lite-matcher")]
+    #[error("case-insensitive selectors are not supported")]
+    CaseInsensitive,
 }
 
 // ----------------------------------------------------------------------------
@@ -0,0 +1,316 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Lightweight matcher.
+
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use crate::id::{Id, ToId};
+
+use super::error::{Error, Result};
+use super::pattern::Pattern;
+
+mod builder;
+
+use builder::Builder;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Lightweight matcher.
+///
+/// The [`LiteMatcher`] provides the same API as the [`Matcher`][], but doesn't
+/// depend on the [`globset`][] crate, which makes it suitable for targets where
+/// binary size matters, e.g., WebAssembly. It supports a subset of the glob
+/// syntax, which includes literals, `*`, `**` and `?`, and rejects character
+/// classes, alternations and escapes when selectors are added.
+///
+/// Supported patterns behave exactly like they do in the [`Matcher`][], since
+/// the same semantics as the default options of [`globset`][] are used, so `*`
+/// and `?` also match `/`. However, selectors are matched one after another,
+/// which means that matching is linear in the number of selectors, so this
+/// implementation is not recommended for large sets of selectors.
+///
+/// [`globset`]: https://crates.io/crates/globset
+/// [`Matcher`]: crate::Matcher
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::prelude::*;
+///
+/// // Create identifier
+/// let id = Id::new("file", "docs", "index.md")?;
+///
+/// // Create matcher builder and add selector
+/// let mut builder = LiteMatcher::builder();
+/// builder.add("zrs::::**/*.md:")?;
+///
+/// // Create matcher from builder
+/// let matcher = builder.build()?;
+///
+/// // Check if the id matches the selector
+/// assert!(matcher.is_match(&id)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct LiteMatcher {
+    /// Patterns for each component of each selector, if any.
+    patterns: Vec<[Option<Pattern>; 5]>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl LiteMatcher {
+    /// Creates a matcher builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder
+    /// let mut builder = LiteMatcher::builder();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Checks if one of the underlying selectors matches an identifier.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = LiteMatcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:git:::docs/**:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Check if identifiers match the selectors
+    /// assert!(matcher.is_match(&Id::new("file", "docs", "index.md")?)?);
+    /// assert!(matcher.is_match(&Id::new("git", "src", "docs/a.rs")?)?);
+    /// assert!(!matcher.is_match(&Id::new("file", "src", "docs/a.rs")?)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn is_match<I>(&self, id: I) -> Result<bool>
+    where
+        I: ToId,
+    {
+        let id = id.to_id()?;
+        let values = values(&id);
+
+        // Check selectors one after another
+        let mut iter = self.patterns.iter();
+        Ok(iter.any(|patterns| compare(patterns, &values)))
+    }
+
+    /// Returns the match set of the selectors that match an identifier.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier
+    /// let id = Id::new("file", "docs", "guide/index.md")?;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = LiteMatcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    /// builder.add("zrs:::docs:guide/**:")?;
+    /// builder.add("zrs::::index.m?:")?;
+    /// builder.add("zrs::::guide/index.m?:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Obtain selectors matched by identifier
+    /// let matches = matcher.matches(&id)?;
+    /// assert_eq!(matches, [0, 1, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Absent values are only matched by empty components, so results are
+    /// identical to those of a [`Matcher`][]:
+    ///
+    /// [`Matcher`]: crate::Matcher
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # #[cfg(feature = "globset")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers
+    /// let ids = [
+    ///     Id::new("file", "docs", "index.md")?,
+    ///     Id::new("file", "docs", "index.md")?.with_fragment("top")?,
+    ///     Id::new("git", "docs", "index.md")?.with_binding("main")?,
+    /// ];
+    ///
+    /// // Create matcher builders and add selectors
+    /// let mut builder = LiteMatcher::builder();
+    /// let mut expected = Matcher::builder();
+    /// for value in [
+    ///     "zrs::::**/*.md:",
+    ///     "zrs::::**/*.md:*",
+    ///     "zrs::::**/*.md:?*",
+    ///     "zrs::*::**/*.md:",
+    ///     "zrs::**:::",
+    ///     "zrs::::**/*.md:-",
+    ///     "zrs::-:::",
+    /// ] {
+    ///     builder.add(value)?;
+    ///     expected.add(value)?;
+    /// }
+    ///
+    /// // Ensure both matchers yield the same match sets
+    /// let matcher = builder.build()?;
+    /// let expected = expected.build()?;
+    /// for id in &ids {
+    ///     assert_eq!(matcher.matches(id)?, expected.matches(id)?, "{id}");
+    /// }
+    /// assert_eq!(matcher.matches(&ids[0])?, [0, 5, 6]);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "globset"))]
+    /// # fn main() {}
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn matches<I>(&self, id: I) -> Result<Vec<usize>>
+    where
+        I: ToId,
+    {
+        let id = id.to_id()?;
+        let values = values(&id);
+
+        // Obtain match set by collecting the indexes of all matching selectors
+        let iter = self.patterns.iter().enumerate();
+        Ok(iter
+            .filter_map(|(index, patterns)| {
+                compare(patterns, &values).then_some(index)
+            })
+            .collect())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl FromStr for LiteMatcher {
+    type Err = Error;
+
+    /// Creates a matcher from a string.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Unsupported`], if a component contains an
+    /// unsupported pattern, or an error if the selector is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher from string
+    /// let matcher: LiteMatcher = "zrs::::**/*.md:".parse()?;
+    ///
+    /// // Unsupported patterns are rejected
+    /// assert!("zrs::::**/*.{md,txt}:".parse::<LiteMatcher>().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn from_str(value: &str) -> Result<Self> {
+        let mut builder = LiteMatcher::builder();
+        builder.add(value)?;
+        builder.build()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the component values of an identifier.
+fn values(id: &Id) -> [Option<Cow<'_, str>>; 5] {
+    let view = id.view();
+    [
+        Some(view.scheme),
+        view.binding,
+        Some(view.context),
+        Some(view.path),
+        view.fragment,
+    ]
+}
+
+/// Compares the patterns of a selector against component values.
+///
+/// Components are compared in descending variability, starting with the path.
+/// Absent values are only matched by empty components and empty patterns of
+/// empty-only components, just like in the [`Matcher`][], so that both
+/// implementations yield identical results.
+///
+/// [`Matcher`]: crate::Matcher
+fn compare(
+    patterns: &[Option<Pattern>; 5], values: &[Option<Cow<str>>; 5],
+) -> bool {
+    [3, 2, 0, 1, 4].into_iter().all(|index| {
+        match (&patterns[index], values[index].as_deref()) {
+            (Some(pattern), Some(value)) => pattern.is_match(value),
+            (Some(pattern), None) => pattern.is_empty(),
+            (None, _) => true,
+        }
+    })
+}
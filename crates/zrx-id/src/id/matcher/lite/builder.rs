@@ -0,0 +1,169 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Lightweight matcher builder.
+
+use crate::id::Component;
+
+use super::super::error::{Error, Result};
+use super::super::pattern::Pattern;
+use super::super::selector::ToSelector;
+use super::LiteMatcher;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Lightweight matcher builder.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    /// Patterns for each component of each selector, if any.
+    patterns: Vec<[Option<Pattern>; 5]>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Builder {
+    /// Creates a matcher builder.
+    ///
+    /// Note that the canonical way to create a [`LiteMatcher`] is to invoke
+    /// the [`LiteMatcher::builder`] method, which creates an instance of
+    /// [`Builder`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder
+    /// let mut builder = LiteMatcher::builder();
+    /// ```
+    #[allow(clippy::new_without_default)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self { patterns: Vec::new() }
+    }
+
+    /// Adds a selector to the matcher.
+    ///
+    /// If a component is empty, it's a wildcard, which matches anything, and
+    /// is the only kind of component that matches absent values.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given selector is invalid, or if a
+    /// component contains an unsupported pattern. Negated selectors are not
+    /// supported, and return [`Error::Negated`], and neither are selectors
+    /// that are case-insensitive, which return [`Error::CaseInsensitive`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selector
+    /// let mut builder = LiteMatcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    ///
+    /// // Character classes are not supported
+    /// assert!(builder.add("zrs::::**/[a-z].md:").is_err());
+    ///
+    /// // Negated selectors are not supported
+    /// assert!(builder.add("!zrs::::drafts/**:").is_err());
+    ///
+    /// // Case-insensitive selectors are not supported
+    /// let selector: Selector = "zrs::::docs/*.md:".parse()?;
+    /// assert!(builder.add(&selector.with_case_insensitive(true)).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn add<S>(&mut self, selector: S) -> Result<&mut Self>
+    where
+        S: ToSelector,
+    {
+        let selector = selector.to_selector()?;
+        if selector.is_negated() {
+            return Err(Error::Negated);
+        }
+        if selector.is_case_insensitive() {
+            return Err(Error::CaseInsensitive);
+        }
+
+        // Compile each component of the given selector
+        let mut patterns = [None, None, None, None, None];
+        for (pattern, component) in patterns.iter_mut().zip(Component::ALL) {
+            *pattern = parse(selector.glob(component).as_deref())?;
+        }
+        self.patterns.push(patterns);
+
+        // Return self for chaining
+        Ok(self)
+    }
+
+    /// Builds the matcher.
+    ///
+    /// # Errors
+    ///
+    /// This method is infallible, but we're synchronizing the signature with
+    /// the fallible method of the [`Matcher`][] builder for consistency.
+    ///
+    /// [`Matcher`]: crate::Matcher
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selector
+    /// let mut builder = LiteMatcher::builder();
+    /// builder.add("zrs::::**/*.md:")?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn build(self) -> Result<LiteMatcher> {
+        Ok(LiteMatcher { patterns: self.patterns })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Parses a component into a pattern.
+///
+/// Note that wildcards are implicit, which means that empty components have
+/// no pattern, as they match anything, including absent values, while
+/// empty-only components have an empty pattern, only matching absent values.
+fn parse(component: Option<&str>) -> Result<Option<Pattern>> {
+    component.map(Pattern::new).transpose()
+}
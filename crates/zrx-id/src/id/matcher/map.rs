@@ -0,0 +1,259 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Selector map.
+
+use std::sync::Arc;
+
+use crate::id::ToId;
+
+use super::error::Result;
+use super::selector::Selector;
+use super::Matcher;
+
+mod builder;
+
+use builder::Builder;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Selector map.
+///
+/// Selector maps associate a value with each [`Selector`], e.g., a handler or
+/// configuration, and return the values of all selectors that match a given
+/// identifier, so callers don't need to map the indexes returned by a
+/// [`Matcher`] back to values themselves. Values are returned in the order
+/// in which they were inserted. Negated selectors exclude identifiers from
+/// all entries, which is why their values are never returned.
+///
+/// Values are shared, so cloning a selector map is cheap, and doesn't require
+/// the values to implement [`Clone`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::matcher::SelectorMap;
+/// use zrx_id::prelude::*;
+///
+/// // Create selector map builder and insert entries
+/// let mut builder = SelectorMap::builder();
+/// builder.insert("zrs::::**/*.md:", "markdown")?;
+/// builder.insert("zrs:::docs::", "docs")?;
+/// builder.insert("zrs::::**/*.png:", "image")?;
+///
+/// // Create selector map from builder
+/// let map = builder.build()?;
+///
+/// // Obtain values of matching selectors
+/// let id = Id::new("file", "docs", "index.md")?;
+/// assert_eq!(map.get_matches(&id)?, [&"markdown", &"docs"]);
+/// assert_eq!(map.first(&id)?, Some(&"markdown"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SelectorMap<T> {
+    /// Matcher for all selectors.
+    matcher: Matcher,
+    /// Values of selectors.
+    values: Arc<[T]>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl<T> SelectorMap<T> {
+    /// Creates a selector map builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::matcher::SelectorMap;
+    ///
+    /// // Create selector map builder
+    /// let mut builder = SelectorMap::<usize>::builder();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn builder() -> Builder<T> {
+        Builder::new()
+    }
+
+    /// Returns the values of the selectors that match an identifier.
+    ///
+    /// Values are returned in the order in which they were inserted, which is
+    /// the order of the match set returned by [`Matcher::matches`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::SelectorMap;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector map builder and insert entries
+    /// let mut builder = SelectorMap::builder();
+    /// builder.insert("zrs::::**/*.md:", 1)?;
+    /// builder.insert("zrs:::docs::", 2)?;
+    /// builder.insert("!zrs::::drafts/**:", 3)?;
+    ///
+    /// // Create selector map from builder
+    /// let map = builder.build()?;
+    ///
+    /// // Obtain values of matching selectors
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(map.get_matches(&id)?, [&1, &2]);
+    /// let id = Id::new("file", "docs", "drafts/index.md")?;
+    /// assert!(map.get_matches(&id)?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_matches<I>(&self, id: I) -> Result<Vec<&T>>
+    where
+        I: ToId,
+    {
+        let matches = self.matcher.matches(id)?;
+        Ok(matches
+            .into_iter()
+            .map(|index| &self.values[index])
+            .collect())
+    }
+
+    /// Returns the value of the first selector that matches an identifier.
+    ///
+    /// This method doesn't collect the match set, see [`Matcher::first_match`]
+    /// for details.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::SelectorMap;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector map builder and insert entries
+    /// let mut builder = SelectorMap::builder();
+    /// builder.insert("zrs::::blog/**:", "post")?;
+    /// builder.insert("zrs::::**/*.md:", "page")?;
+    ///
+    /// // Create selector map from builder
+    /// let map = builder.build()?;
+    ///
+    /// // Obtain value of first matching selector
+    /// let id = Id::new("file", "docs", "blog/hello.md")?;
+    /// assert_eq!(map.first(&id)?, Some(&"post"));
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(map.first(&id)?, Some(&"page"));
+    /// let id = Id::new("file", "docs", "logo.png")?;
+    /// assert_eq!(map.first(&id)?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn first<I>(&self, id: I) -> Result<Option<&T>>
+    where
+        I: ToId,
+    {
+        let first = self.matcher.first_match(id)?;
+        Ok(first.map(|(index, _)| &self.values[index]))
+    }
+
+    /// Creates an iterator over the entries of the selector map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::SelectorMap;
+    ///
+    /// // Create selector map builder and insert entries
+    /// let mut builder = SelectorMap::builder();
+    /// builder.insert("zrs::::**/*.md:", "markdown")?;
+    /// builder.insert("zrs::::**/*.png:", "image")?;
+    ///
+    /// // Create selector map from builder
+    /// let map = builder.build()?;
+    ///
+    /// // Create iterator over entries
+    /// for (selector, value) in map.iter() {
+    ///     println!("{selector}: {value}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&Selector, &T)> {
+        self.matcher.selectors().iter().zip(self.values.iter())
+    }
+
+    /// Returns the underlying matcher.
+    #[inline]
+    #[must_use]
+    pub fn matcher(&self) -> &Matcher {
+        &self.matcher
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl<T> SelectorMap<T> {
+    /// Returns the number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns whether there are any entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<T> Clone for SelectorMap<T> {
+    /// Clones the selector map, sharing the values.
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            matcher: self.matcher.clone(),
+            values: Arc::clone(&self.values),
+        }
+    }
+}
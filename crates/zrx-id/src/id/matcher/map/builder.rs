@@ -0,0 +1,142 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Selector map builder.
+
+use super::super::builder::Builder as MatcherBuilder;
+use super::super::error::Result;
+use super::super::selector::ToSelector;
+use super::super::Matcher;
+use super::SelectorMap;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Selector map builder.
+#[derive(Clone, Debug)]
+pub struct Builder<T> {
+    /// Matcher builder.
+    matcher: MatcherBuilder,
+    /// Values of selectors.
+    values: Vec<T>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl<T> Builder<T> {
+    /// Creates a selector map builder.
+    ///
+    /// Note that the canonical way to create a [`SelectorMap`] is to invoke
+    /// the [`SelectorMap::builder`] method, which creates an instance of
+    /// [`Builder`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::matcher::SelectorMap;
+    ///
+    /// // Create selector map builder
+    /// let mut builder = SelectorMap::<usize>::builder();
+    /// ```
+    #[allow(clippy::new_without_default)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            matcher: Matcher::builder(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Inserts a selector with an associated value.
+    ///
+    /// Selectors are not deduplicated, so inserting the same selector twice
+    /// creates two entries, and both values are returned for identifiers the
+    /// selector matches.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as the [`Matcher`] builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::SelectorMap;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector map builder and insert entries
+    /// let mut builder = SelectorMap::builder();
+    /// builder.insert("zrs::::**/*.md:", "a")?;
+    /// builder.insert("zrs::::**/*.md:", "b")?;
+    ///
+    /// // Create selector map from builder
+    /// let map = builder.build()?;
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert_eq!(map.get_matches(&id)?, [&"a", &"b"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn insert<S>(&mut self, selector: S, value: T) -> Result<&mut Self>
+    where
+        S: ToSelector,
+    {
+        self.matcher.add(selector)?;
+        self.values.push(value);
+
+        // Return self for chaining
+        Ok(self)
+    }
+
+    /// Builds the selector map.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as the [`Matcher`] builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::SelectorMap;
+    ///
+    /// // Create selector map builder and insert entry
+    /// let mut builder = SelectorMap::builder();
+    /// builder.insert("zrs::::**/*.md:", "markdown")?;
+    ///
+    /// // Create selector map from builder
+    /// let map = builder.build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build(self) -> Result<SelectorMap<T>> {
+        Ok(SelectorMap {
+            matcher: self.matcher.build()?,
+            values: self.values.into(),
+        })
+    }
+}
@@ -0,0 +1,358 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Lightweight pattern.
+
+#[cfg(feature = "captures")]
+use std::fmt::Write;
+use std::slice;
+
+use super::error::{Error, Result};
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Pattern token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token {
+    /// Literal byte.
+    Literal(u8),
+    /// Any single byte, i.e., `?`.
+    Any,
+    /// Zero or more bytes, i.e., `*`.
+    ZeroOrMore,
+    /// Zero or more leading directories, i.e., `**/`.
+    RecursivePrefix,
+    /// One or more trailing bytes after a separator, i.e., `/**`.
+    RecursiveSuffix,
+    /// Zero or more directories in between separators, i.e., `/**/`.
+    RecursiveZeroOrMore,
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Lightweight pattern.
+///
+/// Patterns implement the subset of glob syntax that is supported without the
+/// [`globset`][] crate, which includes literals, `*`, `**` and `?`, with the
+/// exact same semantics as the default options of [`globset`][], so `*` and
+/// `?` also match `/` and line breaks, and `**` is only recursive as an entire
+/// segment.
+///
+/// Matching is implemented by simulating all possible positions within the
+/// input at once, which has a worst case of quadratic time in relation to the
+/// length of the input, but is immune to exponential backtracking. Besides
+/// matching, which is used by the [`LiteMatcher`][], patterns can be compared
+/// structurally, which is used to check whether selectors cover each other.
+///
+/// [`LiteMatcher`]: crate::matcher::LiteMatcher
+///
+/// [`globset`]: https://crates.io/crates/globset
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    /// Sequence of tokens.
+    tokens: Vec<Token>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Pattern {
+    /// Creates a pattern from a glob.
+    ///
+    /// # Errors
+    ///
+    /// If the glob contains character classes, alternations or escapes, which
+    /// are not supported, [`Error::Unsupported`] is returned.
+    pub fn new(glob: &str) -> Result<Self> {
+        let bytes = glob.as_bytes();
+        let mut tokens = Vec::with_capacity(bytes.len());
+
+        // Tokenize glob, following the same rules as globset for recursive
+        // wildcards, which are only recursive when spanning entire segments
+        let mut index = 0;
+        while index < bytes.len() {
+            match bytes[index] {
+                b'?' => tokens.push(Token::Any),
+                b'*' if bytes.get(index + 1) != Some(&b'*') => {
+                    tokens.push(Token::ZeroOrMore);
+                }
+                b'*' => {
+                    index += 1;
+                    parse_recursive(bytes, &mut index, &mut tokens);
+                }
+                b'[' | b']' | b'{' | b'}' | b'\\' => {
+                    return Err(Error::Unsupported(glob.to_string()));
+                }
+                byte => tokens.push(Token::Literal(byte)),
+            }
+            index += 1;
+        }
+
+        // If the entire glob is just `**`, it should match everything
+        if tokens == [Token::RecursivePrefix] {
+            tokens = vec![Token::ZeroOrMore];
+        }
+
+        // Return pattern
+        Ok(Self { tokens })
+    }
+
+    /// Returns whether the pattern is empty, i.e., only matches empty values.
+    #[cfg(feature = "lite-matcher")]
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Returns whether the pattern matches the given value.
+    #[cfg(feature = "lite-matcher")]
+    pub fn is_match(&self, value: &str) -> bool {
+        let input = value.as_bytes();
+
+        // Initialize set of reachable positions, starting at the beginning
+        let mut curr = vec![false; input.len() + 1];
+        let mut next = vec![false; input.len() + 1];
+        curr[0] = true;
+
+        // Advance the set of reachable positions token by token
+        for token in &self.tokens {
+            next.fill(false);
+            match *token {
+                Token::Literal(byte) => literal(input, &curr, &mut next, byte),
+                Token::Any => next[1..].copy_from_slice(&curr[..input.len()]),
+                Token::ZeroOrMore => zero_or_more(&curr, &mut next),
+                Token::RecursivePrefix => recursive(input, &curr, &mut next),
+                Token::RecursiveSuffix => {
+                    literal(input, &curr, &mut next, b'/');
+                    curr.copy_from_slice(&next);
+                    next.fill(false);
+                    zero_or_more(&curr, &mut next);
+                }
+                Token::RecursiveZeroOrMore => {
+                    literal(input, &curr, &mut next, b'/');
+                    curr.copy_from_slice(&next);
+                    next.fill(false);
+                    recursive(input, &curr, &mut next);
+                }
+            }
+
+            // Short-circuit, if no position is reachable anymore
+            if !next.contains(&true) {
+                return false;
+            }
+            std::mem::swap(&mut curr, &mut next);
+        }
+
+        // Pattern matches, if the end of the input is reachable
+        curr[input.len()]
+    }
+
+    /// Returns whether the pattern matches everything the other pattern does.
+    ///
+    /// This is a conservative check, which tries to align the tokens of the
+    /// other pattern with the tokens of this pattern, where each token must
+    /// match everything its aligned tokens match, e.g., `*` can be aligned
+    /// with any sequence of tokens. If an alignment exists, the pattern covers
+    /// the other pattern, but if it doesn't, it might still do, so `false`
+    /// means that it can't be proven, not that it doesn't.
+    pub fn covers(&self, other: &Self) -> bool {
+        let p = expand(&self.tokens);
+        let q = expand(&other.tokens);
+
+        // Compute whether the tokens of this pattern starting at `i` cover the
+        // tokens of the other pattern starting at `j`, working backwards
+        let mut table = vec![vec![false; q.len() + 1]; p.len() + 1];
+        table[p.len()][q.len()] = true;
+        for i in (0..p.len()).rev() {
+            for j in (0..=q.len()).rev() {
+                table[i][j] = match p[i] {
+                    Token::Literal(byte) => {
+                        q.get(j) == Some(&Token::Literal(byte))
+                            && table[i + 1][j + 1]
+                    }
+                    Token::Any => {
+                        matches!(q.get(j), Some(Token::Literal(_) | Token::Any))
+                            && table[i + 1][j + 1]
+                    }
+                    Token::ZeroOrMore => {
+                        table[i + 1][j] || (j < q.len() && table[i][j + 1])
+                    }
+
+                    // Zero or more directories cover nothing, themselves, or
+                    // any sequence of tokens that ends with a separator
+                    _ => {
+                        table[i + 1][j]
+                            || (q.get(j) == Some(&Token::RecursivePrefix)
+                                && table[i + 1][j + 1])
+                            || (j + 1..=q.len()).any(|k| {
+                                q[k - 1] == Token::Literal(b'/')
+                                    && table[i + 1][k]
+                            })
+                    }
+                };
+            }
+        }
+
+        // Pattern covers other pattern, if all tokens could be aligned
+        table[0][0]
+    }
+
+    /// Returns the number of wildcards in the pattern.
+    #[cfg(feature = "captures")]
+    pub fn wildcards(&self) -> usize {
+        let iter = self.tokens.iter();
+        iter.filter(|token| !matches!(token, Token::Literal(_)))
+            .count()
+    }
+
+    /// Returns a regular expression capturing the value of each wildcard.
+    ///
+    /// The regular expression operates on bytes, just like [`globset`][], and
+    /// contains one capture group for each wildcard in order. Recursive
+    /// wildcards capture the directories they match without the separators
+    /// surrounding them, so `**/*.md` captures `a/b` and `c` for `a/b/c.md`.
+    ///
+    /// [`globset`]: https://crates.io/crates/globset
+    #[cfg(feature = "captures")]
+    pub fn to_regex(&self) -> String {
+        let mut regex = String::from("(?s-u)^");
+        for token in &self.tokens {
+            match *token {
+                Token::Literal(byte) if byte.is_ascii() => {
+                    regex_syntax::escape_into(
+                        char::from(byte).encode_utf8(&mut [0; 4]),
+                        &mut regex,
+                    );
+                }
+                Token::Literal(byte) => {
+                    let _ = write!(regex, "\\x{byte:02X}");
+                }
+                Token::Any => regex.push_str("(.)"),
+                Token::ZeroOrMore => regex.push_str("(.*)"),
+                Token::RecursivePrefix => regex.push_str("(?:(.*)/)?"),
+                Token::RecursiveSuffix => regex.push_str("/(.*)"),
+                Token::RecursiveZeroOrMore => regex.push_str("/(?:(.*)/)?"),
+            }
+        }
+        regex.push('$');
+        regex
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Parses a recursive wildcard, after the second `*` was encountered.
+///
+/// This mirrors the rules of [`globset`][], which only considers `**` to be a
+/// recursive wildcard, if it's surrounded by separators or the start or end
+/// of the glob. Otherwise, it's equivalent to two consecutive `*`.
+///
+/// [`globset`]: https://crates.io/crates/globset
+fn parse_recursive(bytes: &[u8], index: &mut usize, tokens: &mut Vec<Token>) {
+    let peek = bytes.get(*index + 1).copied();
+
+    // Handle recursive wildcard at the start of the glob
+    if tokens.is_empty() {
+        if is_boundary(peek) {
+            tokens.push(Token::RecursivePrefix);
+            *index += usize::from(peek.is_some());
+        } else {
+            tokens.extend([Token::ZeroOrMore, Token::ZeroOrMore]);
+        }
+        return;
+    }
+
+    // Handle recursive wildcard after a separator, which must be followed by
+    // either the end of the glob, or another separator
+    let prev = bytes[*index - 2];
+    if prev != b'/' || !is_boundary(peek) {
+        tokens.extend([Token::ZeroOrMore, Token::ZeroOrMore]);
+        return;
+    }
+
+    // Replace the preceding separator with the recursive wildcard, which is
+    // only necessary if it isn't already a recursive wildcard itself
+    *index += usize::from(peek.is_some());
+    match tokens.pop() {
+        Some(token @ (Token::RecursivePrefix | Token::RecursiveSuffix)) => {
+            tokens.push(token);
+        }
+        _ if peek.is_none() => tokens.push(Token::RecursiveSuffix),
+        _ => tokens.push(Token::RecursiveZeroOrMore),
+    }
+}
+
+/// Expands recursive wildcards after separators into their constituents.
+///
+/// This leaves only literals, `?`, `*` and `**/`, as `/**` is equivalent to
+/// `/` followed by `*`, and `/**/` is equivalent to `/` followed by `**/`.
+fn expand(tokens: &[Token]) -> Vec<Token> {
+    let iter = tokens.iter().flat_map(|token| match token {
+        Token::RecursiveSuffix => &[Token::Literal(b'/'), Token::ZeroOrMore],
+        Token::RecursiveZeroOrMore => {
+            &[Token::Literal(b'/'), Token::RecursivePrefix]
+        }
+        token => slice::from_ref(token),
+    });
+    iter.copied().collect()
+}
+
+/// Returns whether the byte is absent or a separator.
+#[inline]
+fn is_boundary(byte: Option<u8>) -> bool {
+    matches!(byte, None | Some(b'/'))
+}
+
+/// Advances all reachable positions over the given literal byte.
+#[cfg(feature = "lite-matcher")]
+fn literal(input: &[u8], curr: &[bool], next: &mut [bool], byte: u8) {
+    for (i, &value) in input.iter().enumerate() {
+        next[i + 1] |= curr[i] && value == byte;
+    }
+}
+
+/// Advances all reachable positions over zero or more bytes.
+#[cfg(feature = "lite-matcher")]
+fn zero_or_more(curr: &[bool], next: &mut [bool]) {
+    let mut carry = false;
+    for (i, reachable) in next.iter_mut().enumerate() {
+        carry |= curr[i];
+        *reachable |= carry;
+    }
+}
+
+/// Advances all reachable positions over zero or more directories.
+#[cfg(feature = "lite-matcher")]
+fn recursive(input: &[u8], curr: &[bool], next: &mut [bool]) {
+    let mut carry = false;
+    next.copy_from_slice(curr);
+    for (i, &value) in input.iter().enumerate() {
+        carry |= curr[i];
+        next[i + 1] |= carry && value == b'/';
+    }
+}
@@ -0,0 +1,427 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Persistent matcher.
+
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, result};
+
+use super::error::{Error, Result};
+use super::selector::Selector;
+use super::{EmptyBehavior, Matcher};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Names of fields.
+const FIELDS: &[&str] = &[
+    "version",
+    "case_insensitive",
+    "match_all_if_empty",
+    "selectors",
+    "case_insensitive_selectors",
+];
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Persistent matcher.
+///
+/// Compiled glob sets can't be serialized, which is why the persistent form
+/// of a [`Matcher`] captures its exact configuration instead, i.e., the
+/// canonical strings of all selectors in order, and the options of the
+/// builder, including which selectors are case-insensitive. Since selectors
+/// are validated when the matcher is built, reloading a matcher skips the
+/// parsing and validation of configuration files, and ensures that indexes
+/// returned by [`Matcher::matches`] are stable across processes.
+///
+/// Persistent matchers are versioned, and [`Matcher::from_persistent`] rejects
+/// all versions other than [`Persistent::VERSION`], so changes to the layout
+/// or semantics can never be silently misinterpreted.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::matcher::{EmptyBehavior, Persistent};
+/// use zrx_id::prelude::*;
+///
+/// // Create identifiers
+/// let ids = [
+///     Id::new("file", "docs", "index.md")?,
+///     Id::new("file", "docs", "Docs/INDEX.MD")?,
+///     Id::new("git", "docs", "drafts/index.md")?,
+///     Id::new("file", "docs", "image.png")?,
+/// ];
+///
+/// // Create matcher builder and add selectors
+/// let mut builder = Matcher::builder();
+/// builder.add("zrs::::**/*.md:")?;
+/// builder.add("!zrs::::drafts/**:")?;
+/// builder.add("zrs:git::::")?;
+/// builder.add(
+///     &"zrs::::docs/*.md:"
+///         .parse::<Selector>()?
+///         .with_case_insensitive(true),
+/// )?;
+/// let matcher = builder.build()?;
+///
+/// // Round-trip matcher through JSON
+/// let json = serde_json::to_string(&matcher.to_persistent())?;
+/// let persistent: Persistent = serde_json::from_str(&json)?;
+/// let a = Matcher::from_persistent(&persistent)?;
+///
+/// // Round-trip matcher through postcard
+/// let bytes = postcard::to_allocvec(&matcher.to_persistent())?;
+/// let persistent: Persistent = postcard::from_bytes(&bytes)?;
+/// let b = Matcher::from_persistent(&persistent)?;
+///
+/// // Ensure matching behavior is identical
+/// for id in &ids {
+///     let expected = matcher.matches(id)?;
+///     assert_eq!(a.matches(id)?, expected, "{id}");
+///     assert_eq!(b.matches(id)?, expected, "{id}");
+/// }
+/// assert_eq!(a.selectors(), matcher.selectors());
+///
+/// // Round-trip matcher without selectors, matching everything
+/// let mut builder = Matcher::builder();
+/// builder.on_empty(EmptyBehavior::MatchAll);
+/// let persistent = builder.build()?.to_persistent();
+/// let matcher = Matcher::from_persistent(&persistent)?;
+/// assert!(matcher.is_match(&ids[0])?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Persistent {
+    /// Format version.
+    version: u32,
+    /// Whether matching is case-insensitive for all selectors.
+    case_insensitive: bool,
+    /// Whether all identifiers are matched if there are no selectors.
+    match_all_if_empty: bool,
+    /// Canonical strings of selectors.
+    selectors: Vec<String>,
+    /// Indexes of case-insensitive selectors, in ascending order.
+    case_insensitive_selectors: Vec<usize>,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Visitor for persistent matchers.
+struct PersistentVisitor;
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Persistent {
+    /// Format version.
+    ///
+    /// This version must be incremented whenever the layout or semantics of
+    /// the persistent form change, so outdated data is rejected.
+    pub const VERSION: u32 = 2;
+
+    /// Returns the format version.
+    #[inline]
+    #[must_use]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Matcher {
+    /// Returns the persistent form of the matcher.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::Persistent;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher and obtain persistent form
+    /// let matcher: Matcher = "zrs::::**/*.md:".parse()?;
+    /// let persistent = matcher.to_persistent();
+    /// assert_eq!(persistent.version(), Persistent::VERSION);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn to_persistent(&self) -> Persistent {
+        let selectors = self.selectors();
+        Persistent {
+            version: Persistent::VERSION,
+            case_insensitive: self.case_insensitive,
+            match_all_if_empty: self.on_empty == EmptyBehavior::MatchAll,
+            selectors: selectors.iter().map(ToString::to_string).collect(),
+            case_insensitive_selectors: (0..selectors.len())
+                .filter(|&index| selectors[index].is_case_insensitive())
+                .collect(),
+        }
+    }
+
+    /// Creates a matcher from its persistent form.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Version`], if the version of the given
+    /// persistent form is not [`Persistent::VERSION`], or the same errors as
+    /// the [`Matcher`] builder, if a selector is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::Persistent;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher from persistent form
+    /// let json = r#"{
+    ///     "version": 2,
+    ///     "case_insensitive": true,
+    ///     "match_all_if_empty": false,
+    ///     "selectors": ["zrs::::**/*.md:"],
+    ///     "case_insensitive_selectors": []
+    /// }"#;
+    /// let persistent: Persistent = serde_json::from_str(json)?;
+    /// let matcher = Matcher::from_persistent(&persistent)?;
+    /// assert!(matcher.is_match(&Id::new("file", "docs", "INDEX.MD")?)?);
+    ///
+    /// // Mismatched versions are rejected
+    /// let json = json.replace(r#""version": 2"#, r#""version": 1"#);
+    /// let persistent: Persistent = serde_json::from_str(&json)?;
+    /// assert!(Matcher::from_persistent(&persistent).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_persistent(persistent: &Persistent) -> Result<Self> {
+        if persistent.version != Persistent::VERSION {
+            return Err(Error::Version(persistent.version));
+        }
+
+        // Parse and add selectors, restoring case-insensitivity
+        let mut builder = Matcher::builder();
+        builder.case_insensitive(persistent.case_insensitive);
+        if persistent.match_all_if_empty {
+            builder.on_empty(EmptyBehavior::MatchAll);
+        }
+        for (index, value) in persistent.selectors.iter().enumerate() {
+            let case_insensitive = persistent
+                .case_insensitive_selectors
+                .binary_search(&index)
+                .is_ok();
+            let selector = value
+                .parse::<Selector>()?
+                .with_case_insensitive(case_insensitive);
+            builder.add(&selector)?;
+        }
+
+        // Build matcher
+        builder.build()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Serialize for Persistent {
+    /// Serializes the persistent matcher as a struct.
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Persistent", 5)?;
+        state.serialize_field(FIELDS[0], &self.version)?;
+        state.serialize_field(FIELDS[1], &self.case_insensitive)?;
+        state.serialize_field(FIELDS[2], &self.match_all_if_empty)?;
+        state.serialize_field(FIELDS[3], &self.selectors)?;
+        state.serialize_field(FIELDS[4], &self.case_insensitive_selectors)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Persistent {
+    /// Deserializes a persistent matcher from a map or sequence.
+    ///
+    /// All fields are required, and unknown and duplicate fields, as well as
+    /// indexes of case-insensitive selectors that are out of range or not in
+    /// ascending order, are rejected. Note that the version is not checked,
+    /// so [`Matcher::from_persistent`] can report mismatched versions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::Persistent;
+    ///
+    /// // Deserialize persistent matcher, missing field
+    /// let json = r#"{ "version": 2, "selectors": [] }"#;
+    /// let res = serde_json::from_str::<Persistent>(json);
+    /// assert!(res.is_err());
+    ///
+    /// // Deserialize persistent matcher, index out of range
+    /// let json = r#"{
+    ///     "version": 2,
+    ///     "case_insensitive": false,
+    ///     "match_all_if_empty": false,
+    ///     "selectors": ["zrs::::**/*.md:"],
+    ///     "case_insensitive_selectors": [1]
+    /// }"#;
+    /// let res = serde_json::from_str::<Persistent>(json);
+    /// assert!(res.is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn deserialize<D>(deserializer: D) -> result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("Persistent", FIELDS, PersistentVisitor)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<'de> Visitor<'de> for PersistentVisitor {
+    type Value = Persistent;
+
+    /// Formats the expected value.
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a persistent matcher")
+    }
+
+    /// Visits a map, creating a persistent matcher from its fields.
+    fn visit_map<A>(self, mut map: A) -> result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut version = None;
+        let mut case_insensitive = None;
+        let mut match_all_if_empty = None;
+        let mut selectors = None;
+        let mut case_insensitive_selectors = None;
+        while let Some(key) = map.next_key::<String>()? {
+            let Some(index) = FIELDS.iter().position(|name| *name == key)
+            else {
+                return Err(de::Error::unknown_field(&key, FIELDS));
+            };
+
+            // Ensure each field is only given once
+            let seen = match index {
+                0 => version.is_some(),
+                1 => case_insensitive.is_some(),
+                2 => match_all_if_empty.is_some(),
+                3 => selectors.is_some(),
+                _ => case_insensitive_selectors.is_some(),
+            };
+            if seen {
+                return Err(de::Error::duplicate_field(FIELDS[index]));
+            }
+
+            // Assign value to field
+            match index {
+                0 => version = Some(map.next_value()?),
+                1 => case_insensitive = Some(map.next_value()?),
+                2 => match_all_if_empty = Some(map.next_value()?),
+                3 => selectors = Some(map.next_value()?),
+                _ => case_insensitive_selectors = Some(map.next_value()?),
+            }
+        }
+
+        // Ensure all fields are given
+        validate(Persistent {
+            version: version
+                .ok_or_else(|| de::Error::missing_field(FIELDS[0]))?,
+            case_insensitive: case_insensitive
+                .ok_or_else(|| de::Error::missing_field(FIELDS[1]))?,
+            match_all_if_empty: match_all_if_empty
+                .ok_or_else(|| de::Error::missing_field(FIELDS[2]))?,
+            selectors: selectors
+                .ok_or_else(|| de::Error::missing_field(FIELDS[3]))?,
+            case_insensitive_selectors: case_insensitive_selectors
+                .ok_or_else(|| de::Error::missing_field(FIELDS[4]))?,
+        })
+    }
+
+    /// Visits a sequence, creating a persistent matcher from its elements,
+    /// which is how non-self-describing formats represent structs.
+    fn visit_seq<A>(self, mut seq: A) -> result::Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let version = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let case_insensitive = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let match_all_if_empty = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        let selectors = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+        let case_insensitive_selectors = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(4, &self))?;
+        validate(Persistent {
+            version,
+            case_insensitive,
+            match_all_if_empty,
+            selectors,
+            case_insensitive_selectors,
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Validates the indexes of case-insensitive selectors.
+fn validate<E>(persistent: Persistent) -> result::Result<Persistent, E>
+where
+    E: de::Error,
+{
+    let len = persistent.selectors.len();
+    let mut prev = None;
+    for &index in &persistent.case_insensitive_selectors {
+        if index >= len || prev.is_some_and(|prev| prev >= index) {
+            return Err(E::custom(format!("invalid selector index: {index}")));
+        }
+        prev = Some(index);
+    }
+    Ok(persistent)
+}
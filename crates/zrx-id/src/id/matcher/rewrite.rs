@@ -0,0 +1,342 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Rewrite rules.
+
+use std::sync::Arc;
+
+use crate::id::{Id, ToId};
+
+use super::error::Result;
+use super::selector::{Selector, ToSelector};
+use super::Matcher;
+
+mod builder;
+mod template;
+
+use builder::Builder;
+use template::Template;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Rewrite rule.
+///
+/// Rewrite rules pair a [`Selector`] with a template, which is a formatted
+/// identifier containing placeholders, in order to derive identifiers from the
+/// identifiers the selector matches. The following placeholders are supported:
+///
+/// - `{scheme}`, `{binding}`, `{context}`, `{path}`, `{fragment}`: value of
+///   the component of the matched identifier, or empty, if absent.
+/// - `{parent}`, `{file_name}`, `{file_stem}`, `{extension}`: parts of the
+///   `path` component, mirroring the methods of [`Id`] of the same name.
+/// - `{<component>.<n>}`, e.g., `{path.0}`: value matched by the wildcard at
+///   position `n` of the component of the selector, see [`Match`][].
+///
+/// Literal braces are escaped by doubling them, i.e., `{{` and `}}`. Templates
+/// are parsed when the rule is created, so errors are never deferred until
+/// identifiers are rewritten. After substitution, `context` and `path` are
+/// normalized like [`Id::new_normalized`] does, ignoring leading slashes, so
+/// placeholders that are substituted with empty values, like `{parent}` for
+/// identifiers at the root, don't leave empty segments behind.
+///
+/// [`Match`]: crate::matcher::Match
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::matcher::Rewrite;
+/// use zrx_id::prelude::*;
+///
+/// // Create rewrite rule
+/// let rewrite = Rewrite::new(
+///     "zrs:file::src:**/*.md:",
+///     "zri:file::out:{parent}/{file_stem}.html:",
+/// )?;
+///
+/// // Rewrite identifiers
+/// let id = Id::new("file", "src", "blog/post.md")?;
+/// let id = rewrite.apply(&id)?.unwrap();
+/// assert_eq!(id.to_string(), "zri:file::out:blog/post.html:");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rewrite {
+    /// Selector.
+    selector: Selector,
+    /// Template.
+    template: Template,
+    /// Matcher for the selector.
+    matcher: Matcher,
+}
+
+/// Rewrite rule set.
+///
+/// Rule sets combine multiple [`Rewrite`] rules into a single [`Matcher`], so
+/// that all rules are matched at once, and the identifier is rewritten with
+/// the first matching rule in the order they were added. Identifiers can be
+/// excluded from all rules with negated selectors.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::matcher::RewriteSet;
+/// use zrx_id::prelude::*;
+///
+/// // Create rewrite rule set builder and add rules
+/// let mut builder = RewriteSet::builder();
+/// builder.exclude("zrs::::drafts/**:")?;
+/// builder.add(
+///     "zrs::::blog/*/index.md:",
+///     "zri:file::out:posts/{path.0}.html:",
+/// )?;
+/// builder.add("zrs::::**/*.md:", "zri:file::out:{parent}/{file_stem}.html:")?;
+///
+/// // Create rewrite rule set from builder
+/// let rewrites = builder.build()?;
+///
+/// // Rewrite identifiers with the first matching rule
+/// for (path, expected) in [
+///     ("blog/hello/index.md", Some("zri:file::out:posts/hello.html:")),
+///     ("blog/hello/about.md", Some("zri:file::out:blog/hello/about.html:")),
+///     ("index.md", Some("zri:file::out:index.html:")),
+///     ("drafts/index.md", None),
+///     ("logo.png", None),
+/// ] {
+///     let id = Id::new("file", "src", path)?;
+///     let id = rewrites.rewrite(&id)?;
+///     assert_eq!(id.map(|id| id.to_string()).as_deref(), expected);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RewriteSet {
+    /// Matcher for all selectors.
+    matcher: Matcher,
+    /// Templates of rewrite rules.
+    templates: Arc<[Template]>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Rewrite {
+    /// Creates a rewrite rule.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Template`][], if the template is invalid,
+    /// e.g., if it contains an unknown placeholder, or a capture that doesn't
+    /// refer to a wildcard of the selector, and the errors returned by the
+    /// [`Matcher`] builder, if the selector is invalid.
+    ///
+    /// [`Error::Template`]: crate::matcher::Error::Template
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::Rewrite;
+    ///
+    /// // Create rewrite rule
+    /// let rewrite = Rewrite::new(
+    ///     "zrs:file::src:blog/*/index.md:",
+    ///     "zri:file::out:posts/{path.0}.html:",
+    /// )?;
+    ///
+    /// // Create rewrite rules with invalid templates
+    /// let selector = "zrs:file::src:blog/*/index.md:";
+    /// for template in [
+    ///     "zri:file::out:{path.1}.html:",
+    ///     "zri:file::out:{context.0}.html:",
+    ///     "zri:file::out:{stem}.html:",
+    ///     "zri:file::out:{path.0.html:",
+    ///     "zri:file::out:path.0}.html:",
+    ///     "zrs:file::out:{path.0}.html:",
+    /// ] {
+    ///     assert!(Rewrite::new(selector, template).is_err(), "{template}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn new<S>(selector: S, template: &str) -> Result<Self>
+    where
+        S: ToSelector,
+    {
+        let selector = selector.to_selector()?.into_owned();
+        let template = Template::new(template, &selector)?;
+
+        // Create matcher for the selector
+        let mut builder = Matcher::builder();
+        builder.add(&selector)?;
+        Ok(Self {
+            matcher: builder.build()?,
+            selector,
+            template,
+        })
+    }
+
+    /// Applies the rewrite rule to an identifier.
+    ///
+    /// If the selector matches the identifier, the template is rendered with
+    /// the values of the identifier and the captures of the selector, and the
+    /// derived identifier is returned. Otherwise, [`None`] is returned.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid, or if
+    /// the rendered template is not a valid identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::Rewrite;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create rewrite rule
+    /// let rewrite = Rewrite::new(
+    ///     "zrs:file::src:blog/*/index.md:",
+    ///     "zri:file::out:posts/{path.0}.html:",
+    /// )?;
+    ///
+    /// // Rewrite identifiers
+    /// let id = Id::new("file", "src", "blog/hello-world/index.md")?;
+    /// let id = rewrite.apply(&id)?.unwrap();
+    /// assert_eq!(id.to_string(), "zri:file::out:posts/hello-world.html:");
+    /// assert!(rewrite.apply(&Id::new("file", "src", "index.md")?)?.is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn apply<I>(&self, id: I) -> Result<Option<Id>>
+    where
+        I: ToId,
+    {
+        let id = id.to_id()?;
+        let mut matches = self.matcher.matches_with_captures(id.as_ref())?;
+        matches
+            .pop()
+            .map(|captures| self.template.render(&id, &captures))
+            .transpose()
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl Rewrite {
+    /// Returns the selector.
+    #[inline]
+    pub fn selector(&self) -> &Selector {
+        &self.selector
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl RewriteSet {
+    /// Creates a rewrite rule set builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::matcher::RewriteSet;
+    ///
+    /// // Create rewrite rule set builder
+    /// let mut builder = RewriteSet::builder();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Rewrites an identifier with the first matching rule.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given identifier is invalid, or if
+    /// the rendered template is not a valid identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::RewriteSet;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create rewrite rule set builder and add rule
+    /// let mut builder = RewriteSet::builder();
+    /// builder.add("zrs::::**/*.md:", "zri:{scheme}::{context}:{path}.bak:")?;
+    ///
+    /// // Create rewrite rule set from builder
+    /// let rewrites = builder.build()?;
+    ///
+    /// // Rewrite identifier
+    /// let id = Id::new("file", "docs", "a:b.md")?;
+    /// let id = rewrites.rewrite(&id)?.unwrap();
+    /// assert_eq!(id.to_string(), "zri:file::docs:a%3Ab.md.bak:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn rewrite<I>(&self, id: I) -> Result<Option<Id>>
+    where
+        I: ToId,
+    {
+        let id = id.to_id()?;
+        let matches = self.matcher.matches_with_captures(id.as_ref())?;
+        matches
+            .first()
+            .map(|captures| {
+                let template = &self.templates[captures.index];
+                template.render(&id, captures)
+            })
+            .transpose()
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl RewriteSet {
+    /// Returns the number of rewrite rules.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Returns whether there are any rewrite rules.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+}
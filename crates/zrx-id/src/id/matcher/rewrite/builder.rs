@@ -0,0 +1,190 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Rewrite rule set builder.
+
+use super::super::builder::Builder as MatcherBuilder;
+use super::super::error::Result;
+use super::super::selector::ToSelector;
+use super::super::Matcher;
+use super::template::Template;
+use super::RewriteSet;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Rewrite rule set builder.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    /// Matcher builder.
+    matcher: MatcherBuilder,
+    /// Templates of rewrite rules.
+    templates: Vec<Template>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Builder {
+    /// Creates a rewrite rule set builder.
+    ///
+    /// Note that the canonical way to create a [`RewriteSet`] is to invoke
+    /// the [`RewriteSet::builder`] method, which creates an instance of
+    /// [`Builder`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::matcher::RewriteSet;
+    ///
+    /// // Create rewrite rule set builder
+    /// let mut builder = RewriteSet::builder();
+    /// ```
+    #[allow(clippy::new_without_default)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            matcher: Matcher::builder(),
+            templates: Vec::new(),
+        }
+    }
+
+    /// Adds a rewrite rule to the rule set.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Rewrite::new`][].
+    ///
+    /// [`Rewrite::new`]: crate::matcher::Rewrite::new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::RewriteSet;
+    ///
+    /// // Create rewrite rule set builder and add rule
+    /// let mut builder = RewriteSet::builder();
+    /// builder.add(
+    ///     "zrs::::**/*.md:",
+    ///     "zri:file::out:{path.0}/{path.1}.html:",
+    /// )?;
+    ///
+    /// // Templates are checked when adding rules
+    /// let res = builder.add("zrs::::*.md:", "zri:file::out:{path.1}:");
+    /// assert!(res.is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn add<S>(&mut self, selector: S, template: &str) -> Result<&mut Self>
+    where
+        S: ToSelector,
+    {
+        let selector = selector.to_selector()?;
+        let template = Template::new(template, &selector)?;
+
+        // Add selector to matcher, and template at the same index
+        self.matcher.add(selector.as_ref())?;
+        self.templates.push(template);
+
+        // Return self for chaining
+        Ok(self)
+    }
+
+    /// Excludes identifiers from all rewrite rules.
+    ///
+    /// The selector is added to the underlying [`Matcher`] in negated form, so
+    /// that identifiers matching it are never rewritten, regardless of the
+    /// order in which rules are added.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as the [`Matcher`] builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::RewriteSet;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create rewrite rule set builder and add rule and exclusion
+    /// let mut builder = RewriteSet::builder();
+    /// builder.add("zrs::::**/*.md:", "zri:file::out:{path}:")?;
+    /// builder.exclude("zrs::::drafts/**:")?;
+    ///
+    /// // Create rewrite rule set from builder
+    /// let rewrites = builder.build()?;
+    /// let id = Id::new("file", "docs", "drafts/index.md")?;
+    /// assert!(rewrites.rewrite(&id)?.is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn exclude<S>(&mut self, selector: S) -> Result<&mut Self>
+    where
+        S: ToSelector,
+    {
+        let selector = selector.to_selector()?;
+        self.matcher
+            .add(&selector.into_owned().with_negated(true)?)?;
+
+        // Negated selectors never match, so their template is never rendered
+        self.templates.push(Template::default());
+        Ok(self)
+    }
+
+    /// Builds the rewrite rule set.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as the [`Matcher`] builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::RewriteSet;
+    ///
+    /// // Create rewrite rule set builder and add rule
+    /// let mut builder = RewriteSet::builder();
+    /// builder.add("zrs::::**/*.md:", "zri:file::out:{path}:")?;
+    ///
+    /// // Create rewrite rule set from builder
+    /// let rewrites = builder.build()?;
+    /// assert_eq!(rewrites.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build(self) -> Result<RewriteSet> {
+        Ok(RewriteSet {
+            matcher: self.matcher.build()?,
+            templates: self.templates.into(),
+        })
+    }
+}
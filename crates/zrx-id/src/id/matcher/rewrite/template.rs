@@ -0,0 +1,263 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Rewrite template.
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use crate::format::encoding::encode;
+use crate::id::{Component, Id};
+use crate::path::parent;
+
+use super::super::capture::Match;
+use super::super::error::{Error, Result};
+use super::super::pattern::Pattern;
+use super::super::selector::Selector;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Template token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    /// Literal text.
+    Literal(String),
+    /// Value of a component, e.g., `{path}`.
+    Component(Component),
+    /// Parent of the `path` component, i.e., `{parent}`.
+    Parent,
+    /// Final segment of the `path` component, i.e., `{file_name}`.
+    FileName,
+    /// Final segment of the `path` component without extension, i.e.,
+    /// `{file_stem}`.
+    FileStem,
+    /// Extension of the `path` component, i.e., `{extension}`.
+    Extension,
+    /// Capture of a wildcard of a component, e.g., `{path.0}`.
+    Capture(Component, usize),
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Rewrite template.
+///
+/// Templates are formatted identifiers, i.e., `zri:...`, which may contain
+/// placeholders in braces, which are substituted with values of the matched
+/// identifier and the captures of the selector's wildcards. Literal braces
+/// must be escaped by doubling them, i.e., `{{` and `}}`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Template {
+    /// Sequence of tokens.
+    tokens: Vec<Token>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Template {
+    /// Parses a template for the given selector.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Template`], if the template contains an
+    /// unknown or unclosed placeholder, a capture that doesn't refer to one of
+    /// the wildcards of the selector, or isn't a formatted identifier.
+    pub fn new(template: &str, selector: &Selector) -> Result<Self> {
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+
+        // Tokenize template, collecting literal text in between placeholders,
+        // and unescaping doubled braces
+        let mut chars = template.chars().peekable();
+        while let Some(char) = chars.next() {
+            match char {
+                '{' if chars.next_if_eq(&'{').is_some() => literal.push(char),
+                '}' if chars.next_if_eq(&'}').is_some() => literal.push(char),
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for char in chars.by_ref() {
+                        closed = char == '}';
+                        if closed {
+                            break;
+                        }
+                        name.push(char);
+                    }
+                    if !closed {
+                        Err(invalid(format!(
+                            "unclosed placeholder `{{{name}`"
+                        )))?;
+                    }
+                    if !literal.is_empty() {
+                        tokens
+                            .push(Token::Literal(std::mem::take(&mut literal)));
+                    }
+                    tokens.push(placeholder(&name, selector)?);
+                }
+                '}' => Err(invalid("unmatched `}`".into()))?,
+                _ => literal.push(char),
+            }
+        }
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(literal));
+        }
+
+        // Ensure the template is a formatted identifier, substituting all
+        // placeholders with a non-empty value, as the scheme must be present
+        let template = Self { tokens };
+        let mut value = String::new();
+        for token in &template.tokens {
+            match token {
+                Token::Literal(literal) => value.push_str(literal),
+                _ => value.push('_'),
+            }
+        }
+        value
+            .parse::<Id>()
+            .map_err(|err| invalid(format!("`{value}` is not valid: {err}")))?;
+
+        // Return template
+        Ok(template)
+    }
+
+    /// Renders the template for the given identifier and captures.
+    ///
+    /// Substituted values are percent-encoded, so they can never introduce
+    /// new components. Afterwards, the `context` and `path` components are
+    /// normalized, after stripping leading slashes, which are left behind by
+    /// placeholders that are substituted with empty values, e.g., `{parent}`
+    /// for identifiers at the root.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Id`], if the rendered template is not a
+    /// valid identifier, e.g., because the `scheme` is empty.
+    pub fn render(&self, id: &Id, captures: &Match) -> Result<Id> {
+        let path = id.path();
+
+        // Substitute placeholders with the values they refer to
+        let mut value = String::new();
+        for token in &self.tokens {
+            let substitute = match token {
+                Token::Literal(literal) => {
+                    value.push_str(literal);
+                    continue;
+                }
+                Token::Component(component) => component_of(id, *component),
+                Token::Parent => parent(&path).map(Cow::Borrowed),
+                Token::FileName => id.file_name(),
+                Token::FileStem => id.file_stem(),
+                Token::Extension => id.extension(),
+                Token::Capture(component, index) => captures
+                    .captures(*component)
+                    .get(*index)
+                    .map(|capture| Cow::Borrowed(capture.as_str())),
+            };
+            if let Some(substitute) = substitute {
+                let _ = write!(value, "{}", encode(substitute.as_bytes()));
+            }
+        }
+
+        // Parse identifier, and normalize context and path
+        let mut id: Id = value.parse()?;
+        let context = id.context().trim_start_matches('/').to_string();
+        let path = id.path().trim_start_matches('/').to_string();
+        id.set_context_normalized(context)?;
+        id.set_path_normalized(path)?;
+        Ok(id)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Parses a placeholder, ensuring that captures refer to wildcards.
+fn placeholder(name: &str, selector: &Selector) -> Result<Token> {
+    let token = match name {
+        "scheme" => Token::Component(Component::Scheme),
+        "binding" => Token::Component(Component::Binding),
+        "context" => Token::Component(Component::Context),
+        "path" => Token::Component(Component::Path),
+        "fragment" => Token::Component(Component::Fragment),
+        "parent" => Token::Parent,
+        "file_name" => Token::FileName,
+        "file_stem" => Token::FileStem,
+        "extension" => Token::Extension,
+        _ => {
+            let capture = name.split_once('.').and_then(|(name, index)| {
+                let mut iter = Component::ALL.into_iter();
+                let component = iter.find(|c| c.name() == name)?;
+                Some((component, index.parse::<usize>().ok()?))
+            });
+            let Some((component, index)) = capture else {
+                return Err(invalid(format!(
+                    "unknown placeholder `{{{name}}}`"
+                )));
+            };
+
+            // Ensure the capture refers to a wildcard of the component
+            if index >= wildcards(selector, component) {
+                return Err(invalid(format!(
+                    "placeholder `{{{name}}}` doesn't refer to a wildcard"
+                )));
+            }
+            Token::Capture(component, index)
+        }
+    };
+    Ok(token)
+}
+
+/// Returns the number of wildcards with captures in a component.
+fn wildcards(selector: &Selector, component: Component) -> usize {
+    let value = match component {
+        Component::Scheme => selector.scheme(),
+        Component::Binding => selector.binding(),
+        Component::Context => selector.context(),
+        Component::Path => selector.path(),
+        Component::Fragment => selector.fragment(),
+    };
+    value
+        .and_then(|value| Pattern::new(&value).ok())
+        .map_or(0, |pattern| pattern.wildcards())
+}
+
+/// Returns the value of a component of an identifier.
+fn component_of(id: &Id, component: Component) -> Option<Cow<'_, str>> {
+    match component {
+        Component::Scheme => Some(id.scheme()),
+        Component::Binding => id.binding(),
+        Component::Context => Some(id.context()),
+        Component::Path => Some(id.path()),
+        Component::Fragment => id.fragment(),
+    }
+}
+
+/// Creates a template error.
+fn invalid(message: String) -> Error {
+    Error::Template(message)
+}
@@ -22,14 +22,39 @@
 
 //! Selector.
 
+#[cfg(feature = "globset")]
+use globset::{Glob, GlobBuilder, GlobMatcher};
 use std::borrow::Cow;
 use std::fmt;
+use std::io;
 use std::str::FromStr;
 
 use crate::format::Format;
-use crate::path::validate;
+use crate::id::{version, Component};
+use crate::path::{join, split_segments, validate, validate_byte, Validation};
+use crate::Id;
 
 use super::error::{Error, Result};
+use super::escape;
+use super::pattern::Pattern;
+
+mod builder;
+mod cache;
+#[cfg(feature = "proptest")]
+mod proptest;
+#[cfg(feature = "serde")]
+mod record;
+#[cfg(feature = "serde")]
+mod serde;
+mod set;
+mod view;
+
+pub use builder::SelectorBuilder;
+use cache::Cache;
+#[cfg(feature = "serde")]
+pub use record::SelectorRecord;
+pub use set::SelectorSet;
+pub use view::SelectorView;
 
 // ----------------------------------------------------------------------------
 // Traits
@@ -42,9 +67,17 @@ use super::error::{Error, Result};
 pub trait ToSelector {
     /// Creates a selector.
     #[allow(clippy::missing_errors_doc)]
-    fn to_selector(&self) -> Result<Cow<Selector>>;
+    fn to_selector(&self) -> Result<Cow<'_, Selector>>;
 }
 
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Labels of components for alternate display, omitting the prefix.
+const LABELS: [&str; 6] =
+    ["", "scheme", "binding", "context", "path", "fragment"];
+
 // ----------------------------------------------------------------------------
 // Structs
 // ----------------------------------------------------------------------------
@@ -61,7 +94,9 @@ pub trait ToSelector {
 /// to unify behavior among different operating system, ensuring that caches
 /// are portable. This is also why every method that takes a value will return
 /// [`Error::Backslash`][] if a backslash is found. Thus, the caller should
-/// use a library like [`path-slash`][] for consistent normalization.
+/// use a library like [`path-slash`][] for consistent normalization. NUL
+/// bytes are rejected with [`Error::Nul`][] for the same reason, as they'd
+/// truncate values when passed to C functions.
 ///
 /// Selectors are no means to an end, but rather a building block to associate
 /// data or functions to identifiers via the construction of a [`Matcher`][],
@@ -75,10 +110,31 @@ pub trait ToSelector {
 /// zrs:<scheme>:<binding>:<context>:<path>:<fragment>
 /// ```
 ///
+/// Selectors can be negated by prefixing them with `!`, i.e., `!zrs:...`, which
+/// turns them into exclusions when added to a [`Matcher`][].
+///
+/// Since empty components are wildcards, the optional `binding` and `fragment`
+/// components reserve [`Selector::EMPTY`], i.e., `-`, to only match identifiers
+/// where the component is absent, e.g., `zrs::::**/*.md:-` matches Markdown
+/// files without a fragment. Identifiers never have empty values for those
+/// components, as [`Id::binding`] and [`Id::fragment`] return [`None`] for
+/// empty strings, so absent and empty are the same. In all other components,
+/// `-` is a literal.
+///
+/// Selectors can also be marked as case-insensitive, which is a construction
+/// option for matchers, and thus not part of the string representation. Note
+/// that it's not preserved when selectors are parsed from strings.
+///
 /// The decision to use a structured string representation as a data model was
 /// made to allow for blazing fast cloning and derivation of new selectors.
+/// Just like identifiers, selectors are limited to [`Format::MAX_LEN`][]
+/// bytes, and longer selectors are rejected with [`Error::TooLong`][].
 ///
+/// [`Error::TooLong`]: crate::format::Error::TooLong
+/// [`Format::MAX_LEN`]: crate::format::Format::MAX_LEN
+/// [`Matcher`]: crate::Matcher
 /// [`Error::Backslash`]: crate::path::Error::Backslash
+/// [`Error::Nul`]: crate::path::Error::Nul
 /// [`path-slash`]: https://crates.io/crates/path-slash
 ///
 /// # Examples
@@ -88,7 +144,7 @@ pub trait ToSelector {
 /// ```
 /// # use std::error::Error;
 /// # fn main() -> Result<(), Box<dyn Error>> {
-/// use zrx_id::Selector;
+/// use zrx_id::prelude::*;
 ///
 /// // Create selector and set path
 /// let mut selector = Selector::new()?;
@@ -102,7 +158,7 @@ pub trait ToSelector {
 /// ```
 /// # use std::error::Error;
 /// # fn main() -> Result<(), Box<dyn Error>> {
-/// use zrx_id::Selector;
+/// use zrx_id::prelude::*;
 ///
 /// // Create selector from string
 /// let selector: Selector = "zrs::::**/*.md:".parse()?;
@@ -113,6 +169,10 @@ pub trait ToSelector {
 pub struct Selector {
     /// Formatted string.
     format: Format<6>,
+    /// Whether matching is case-insensitive.
+    case_insensitive: bool,
+    /// Compiled globs for matching.
+    cache: Cache,
 }
 
 // ----------------------------------------------------------------------------
@@ -134,16 +194,178 @@ impl Selector {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Selector;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create selector and set path
     /// let mut selector = Selector::new()?;
+    /// assert_eq!(selector, "zrs:::::".parse()?);
     /// selector.set_path("**/*.md")?;
     /// # Ok(())
     /// # }
     /// ```
     pub fn new() -> Result<Self> {
-        Ok(Self { format: "zrs:::::".parse()? })
+        let parts: [&[u8]; 6] = [b"zrs", b"", b"", b"", b"", b""];
+        Ok(Self {
+            format: Format::from_parts(parts)?,
+            case_insensitive: false,
+            cache: Cache::default(),
+        })
+    }
+
+    /// Creates a selector that matches the given identifier literally.
+    ///
+    /// All components of the identifier are copied into the selector, and all
+    /// glob metacharacters, i.e., `*`, `?`, `[`, `]`, `{` and `}`, are escaped
+    /// by wrapping them in character classes with [`escape`][super::escape],
+    /// so the selector matches exactly the given identifier. Absent `binding`
+    /// and `fragment` components are set to [`Selector::EMPTY`], so they only
+    /// match identifiers where they're absent as well. Note that character
+    /// classes are not supported by the lightweight matcher.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Format`], if the selector would exceed the
+    /// length limit, since escaping metacharacters makes components longer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector from identifier with glob metacharacters
+    /// let id = Id::new("file", "docs", "a*[b].md")?;
+    /// let selector = Selector::literal(&id)?;
+    /// assert_eq!(selector.path().as_deref(), Some("a[*][[]b[]].md"));
+    /// assert_eq!(selector.to_string(), "zrs:file:-:docs:a[*][[]b[]].md:-");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The selector matches the identifier, but no other identifiers the
+    /// unescaped globs would match:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # #[cfg(feature = "globset")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher from identifier with glob metacharacters
+    /// let id = Id::new_full("fi?e", "", "{docs}", "a*[b].md", "")?;
+    /// let mut builder = Matcher::builder();
+    /// builder.add(&Selector::literal(&id)?)?;
+    /// let matcher = builder.build()?;
+    ///
+    /// // Ensure only the identifier itself is matched
+    /// assert!(matcher.is_match(&id)?);
+    /// for other in [
+    ///     Id::new_full("fi?e", "", "{docs}", "aX[b].md", "")?,
+    ///     Id::new_full("fi?e", "", "{docs}", "a*b.md", "")?,
+    ///     Id::new_full("file", "", "{docs}", "a*[b].md", "")?,
+    ///     Id::new_full("fi?e", "", "docs", "a*[b].md", "")?,
+    ///     Id::new_full("fi?e", "main", "{docs}", "a*[b].md", "")?,
+    ///     Id::new_full("fi?e", "", "{docs}", "a*[b].md", "top")?,
+    /// ] {
+    ///     assert!(!matcher.is_match(&other)?, "{other}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "globset"))]
+    /// # fn main() {}
+    /// ```
+    pub fn literal(id: &Id) -> Result<Self> {
+        let view = id.view();
+        let parts = [
+            Cow::Borrowed("zrs"),
+            escape(&view.scheme),
+            view.binding
+                .as_deref()
+                .map_or(Cow::Borrowed(Self::EMPTY), escape),
+            escape(&view.context),
+            escape(&view.path),
+            view.fragment
+                .as_deref()
+                .map_or(Cow::Borrowed(Self::EMPTY), escape),
+        ];
+        Ok(Self {
+            format: Format::from_parts(
+                parts.each_ref().map(|part| part.as_bytes()),
+            )?,
+            case_insensitive: false,
+            cache: Cache::default(),
+        })
+    }
+
+    /// Creates a selector from bytes.
+    ///
+    /// This is the equivalent of [`FromStr`] for bytes, which validates the
+    /// bytes as UTF-8 in the same pass, and applies the same checks.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Format`] if the bytes are not valid UTF-8,
+    /// and otherwise, the same errors as [`Selector::from_str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector from bytes
+    /// let selector = Selector::from_bytes(b"zrs::::**/*.md:")?;
+    /// assert_eq!(selector.path().as_deref(), Some("**/*.md"));
+    ///
+    /// // Create selector from bytes that are not valid UTF-8
+    /// assert!(Selector::from_bytes(b"zrs::::\xFF:").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_bytes(value: &[u8]) -> Result<Self> {
+        let format = Format::from_bytes_with(value, 6, |offset, byte| {
+            validate_byte(offset, byte).map_err(Error::from)
+        })?;
+        from_format(format)
+    }
+
+    /// Creates a selector from a string with the given validation policy.
+    ///
+    /// This is the variant of [`Selector::from_str`] that applies the given
+    /// [`Validation`] policy to the string before parsing it, exactly like in
+    /// [`Id::parse_with`], so percent-encoded sequences are left untouched.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Selector::from_str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::path::Validation;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector from string with backslashes
+    /// let policy = Validation::NormalizeBackslashes;
+    /// let selector = Selector::parse_with(policy, r"zrs:::docs:**\*.md:")?;
+    /// assert_eq!(selector.path().as_deref(), Some("**/*.md"));
+    ///
+    /// // Percent-encoded backslashes are never normalized
+    /// let selector = Selector::parse_with(policy, "zrs:::docs:a%5Cb.md:")?;
+    /// assert_eq!(selector.path().as_deref(), Some(r"a\b.md"));
+    ///
+    /// // Backslashes are rejected by default
+    /// let policy = Validation::default();
+    /// assert!(Selector::parse_with(policy, r"zrs:::docs:**\*.md:").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_with(policy: Validation, value: &str) -> Result<Self> {
+        policy.apply(value).parse()
     }
 
     /// Updates the `scheme` component.
@@ -158,7 +380,7 @@ impl Selector {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Selector;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create selector and set scheme
     /// let mut selector = Selector::new()?;
@@ -171,6 +393,7 @@ impl Selector {
     where
         S: AsRef<[u8]>,
     {
+        self.cache.clear();
         self.format
             .set(1, validate(scheme)?)
             .map_err(Into::into)
@@ -189,7 +412,7 @@ impl Selector {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Selector;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create selector and set binding
     /// let mut selector = Selector::new()?;
@@ -202,6 +425,7 @@ impl Selector {
     where
         S: AsRef<[u8]>,
     {
+        self.cache.clear();
         self.format
             .set(2, validate(binding)?)
             .map_err(Into::into)
@@ -220,7 +444,7 @@ impl Selector {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Selector;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create selector and set context
     /// let mut selector = Selector::new()?;
@@ -233,6 +457,7 @@ impl Selector {
     where
         S: AsRef<[u8]>,
     {
+        self.cache.clear();
         self.format
             .set(3, validate(context)?)
             .map_err(Into::into)
@@ -251,7 +476,7 @@ impl Selector {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Selector;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create selector and set path
     /// let mut selector = Selector::new()?;
@@ -264,12 +489,79 @@ impl Selector {
     where
         S: AsRef<[u8]>,
     {
+        self.cache.clear();
         self.format
             .set(4, validate(path)?)
             .map_err(Into::into)
             .map(|()| self)
     }
 
+    /// Appends a segment to the `path` component.
+    ///
+    /// See [`Id::push_path_segment`] for details. If the `path` component is
+    /// empty, i.e., a wildcard, the segment becomes the path.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the segment contains a
+    /// backslash or is absolute, or [`Error::Format`], if the format is
+    /// invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector and append path segments
+    /// let mut selector = Selector::new()?;
+    /// selector.push_path_segment("docs/")?;
+    /// selector.push_path_segment("**/*.md")?;
+    /// assert_eq!(selector.path().as_deref(), Some("docs/**/*.md"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn push_path_segment<S>(&mut self, segment: S) -> Result<&mut Self>
+    where
+        S: AsRef<str>,
+    {
+        let path = join(&self.format.get(4), segment.as_ref())?;
+        self.set_path(path)
+    }
+
+    /// Returns a derived selector with a segment appended to the `path`.
+    ///
+    /// This is the non-mutating variant of [`Selector::push_path_segment`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Selector::push_path_segment`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector and derive selector
+    /// let selector: Selector = "zrs:::docs:guide:".parse()?;
+    /// let derived = selector.join("*.md")?;
+    /// assert_eq!(derived.to_string(), "zrs:::docs:guide/*.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn join<S>(&self, segment: S) -> Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        let mut selector = self.clone();
+        selector.push_path_segment(segment)?;
+        Ok(selector)
+    }
+
     /// Updates the `fragment` component.
     ///
     /// # Errors
@@ -282,7 +574,7 @@ impl Selector {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Selector;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create selector and set fragment
     /// let mut selector = Selector::new()?;
@@ -295,64 +587,183 @@ impl Selector {
     where
         S: AsRef<[u8]>,
     {
+        self.cache.clear();
         self.format
             .set(5, validate(fragment)?)
             .map_err(Into::into)
             .map(|()| self)
     }
-}
-
-#[allow(clippy::must_use_candidate)]
-impl Selector {
-    /// Returns the `scheme` component, if any.
-    #[inline]
-    pub fn scheme(&self) -> Option<Cow<str>> {
-        Some(self.format.get(1)).filter(|value| !value.is_empty())
-    }
 
-    /// Returns the `binding` component, if any.
+    /// Updates whether the selector is negated.
+    ///
+    /// Negated selectors are written with a leading `!`, i.e., as `!zrs:...`,
+    /// and exclude otherwise matching identifiers when added to a
+    /// [`Matcher`][]. Note that [`Selector::is_match`] ignores negation, as it
+    /// only checks whether the components match.
+    ///
+    /// [`Matcher`]: crate::Matcher
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector and negate it
+    /// let mut selector: Selector = "zrs::::drafts/**:".parse()?;
+    /// selector.set_negated(true)?;
+    /// assert_eq!(selector.to_string(), "!zrs::::drafts/**:");
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline]
-    pub fn binding(&self) -> Option<Cow<str>> {
-        Some(self.format.get(2)).filter(|value| !value.is_empty())
+    pub fn set_negated(&mut self, negated: bool) -> Result<&mut Self> {
+        let prefix: &[u8] = if negated { b"!zrs" } else { b"zrs" };
+        self.format
+            .set(0, prefix)
+            .map_err(Into::into)
+            .map(|()| self)
     }
 
-    /// Returns the `context` component, if any.
+    /// Updates whether the selector is case-insensitive.
+    ///
+    /// Case-insensitive selectors match identifiers regardless of the casing
+    /// of ASCII characters in all components, e.g., `**/*.md` matches the path
+    /// `Docs/INDEX.MD`. The flag is not part of the string representation, as
+    /// it's an option for constructing matchers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector and make it case-insensitive
+    /// let mut selector: Selector = "zrs::::docs/**/*.md:".parse()?;
+    /// selector.set_case_insensitive(true);
+    /// assert_eq!(selector.to_string(), "zrs::::docs/**/*.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline]
-    pub fn context(&self) -> Option<Cow<str>> {
-        Some(self.format.get(3)).filter(|value| !value.is_empty())
+    pub fn set_case_insensitive(
+        &mut self, case_insensitive: bool,
+    ) -> &mut Self {
+        self.case_insensitive = case_insensitive;
+        self.cache.clear();
+        self
     }
 
-    /// Returns the `path` component, if any.
+    /// Returns the selector with the `scheme` component updated.
+    ///
+    /// This is the consuming variant of [`Selector::set_scheme`], which allows
+    /// to derive selectors in expression position.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector with scheme
+    /// let selector = Selector::new()?.with_scheme("git")?;
+    /// assert_eq!(selector.to_string(), "zrs:git::::");
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline]
-    pub fn path(&self) -> Option<Cow<str>> {
-        Some(self.format.get(4)).filter(|value| !value.is_empty())
+    pub fn with_scheme<S>(mut self, scheme: S) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_scheme(scheme)?;
+        Ok(self)
     }
 
-    /// Returns the `fragment` component, if any.
+    /// Returns the selector with the `binding` component updated.
+    ///
+    /// This is the consuming variant of [`Selector::set_binding`], which allows
+    /// to derive selectors in expression position.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector with binding
+    /// let selector = Selector::new()?.with_binding("master")?;
+    /// assert_eq!(selector.to_string(), "zrs::master:::");
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline]
-    pub fn fragment(&self) -> Option<Cow<str>> {
-        Some(self.format.get(5)).filter(|value| !value.is_empty())
+    pub fn with_binding<S>(mut self, binding: S) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_binding(binding)?;
+        Ok(self)
     }
-}
-
-// ----------------------------------------------------------------------------
-// Trait implementations
-// ----------------------------------------------------------------------------
 
-impl ToSelector for &Selector {
-    /// Creates a selector from a reference.
+    /// Returns the selector with the `context` component updated.
+    ///
+    /// This is the consuming variant of [`Selector::set_context`], which allows
+    /// to derive selectors in expression position.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector with context
+    /// let selector = Selector::new()?.with_context("site")?;
+    /// assert_eq!(selector.to_string(), "zrs:::site::");
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline]
-    fn to_selector(&self) -> Result<Cow<Selector>> {
-        Ok(Cow::Borrowed(self))
+    pub fn with_context<S>(mut self, context: S) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_context(context)?;
+        Ok(self)
     }
-}
 
-impl ToSelector for &str {
-    /// Creates a selector from a string.
+    /// Returns the selector with the `path` component updated.
+    ///
+    /// This is the consuming variant of [`Selector::set_path`], which allows to
+    /// derive selectors in expression position.
     ///
     /// # Errors
     ///
-    /// This method returns [`Error::Path`], if a component value contains a
+    /// This method returns [`Error::Path`], if the component value contains a
     /// backslash, or [`Error::Format`], if the format is invalid.
     ///
     /// # Examples
@@ -360,36 +771,31 @@ impl ToSelector for &str {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::{Selector, ToSelector};
+    /// use zrx_id::prelude::*;
     ///
-    /// // Create selector from string
-    /// let selector = "zrs::::**/*.md:".to_selector()?;
+    /// // Create selector with path
+    /// let selector = Selector::new()?.with_path("**/*.md")?;
+    /// assert_eq!(selector.to_string(), "zrs::::**/*.md:");
     /// # Ok(())
     /// # }
     /// ```
     #[inline]
-    fn to_selector(&self) -> Result<Cow<Selector>> {
-        self.parse().map(Cow::Owned)
+    pub fn with_path<S>(mut self, path: S) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_path(path)?;
+        Ok(self)
     }
-}
-
-// ----------------------------------------------------------------------------
-
-impl FromStr for Selector {
-    type Err = Error;
 
-    /// Creates a selector from a string.
-    ///
-    /// The string must adhere to the following format and include exactly five
-    /// `:` separators, even if some components are empty.
+    /// Returns the selector with the `fragment` component updated.
     ///
-    /// ``` text
-    /// zrs:<scheme>:<binding>:<context>:<path>:<fragment>
-    /// ```
+    /// This is the consuming variant of [`Selector::set_fragment`], which
+    /// allows to derive selectors in expression position.
     ///
     /// # Errors
     ///
-    /// This method returns [`Error::Path`], if a component value contains a
+    /// This method returns [`Error::Path`], if the component value contains a
     /// backslash, or [`Error::Format`], if the format is invalid.
     ///
     /// # Examples
@@ -397,39 +803,1063 @@ impl FromStr for Selector {
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
-    /// use zrx_id::Selector;
+    /// use zrx_id::prelude::*;
     ///
-    /// // Create selector from string
-    /// let selector: Selector = "zrs::::**/*.md:".parse()?;
+    /// // Create selector with fragment
+    /// let selector = Selector::new()?.with_fragment("anchor")?;
+    /// assert_eq!(selector.to_string(), "zrs:::::anchor");
     /// # Ok(())
     /// # }
     /// ```
-    fn from_str(value: &str) -> Result<Self> {
-        let format = Format::from_str(validate(value)?)?;
-
-        // Ensure prefix is valid
-        if format.get(0) != "zrs" {
-            Err(Error::Prefix)?;
-        }
-
-        // No errors occurred
-        Ok(Self { format })
+    #[inline]
+    pub fn with_fragment<S>(mut self, fragment: S) -> Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.set_fragment(fragment)?;
+        Ok(self)
     }
-}
-
-// ----------------------------------------------------------------------------
 
-impl fmt::Display for Selector {
-    /// Formats the selector for display.
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.format.fmt(f)
-    }
-}
+    /// Returns the selector with negation updated.
+    ///
+    /// This is the consuming variant of [`Selector::set_negated`], which allows
+    /// to derive selectors in expression position.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create negated selector
+    /// let selector = Selector::new()?
+    ///     .with_path("drafts/**")?
+    ///     .with_negated(true)?;
+    /// assert_eq!(selector.to_string(), "!zrs::::drafts/**:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_negated(mut self, negated: bool) -> Result<Self> {
+        self.set_negated(negated)?;
+        Ok(self)
+    }
+
+    /// Returns the selector with case-insensitivity updated.
+    ///
+    /// This is the consuming variant of [`Selector::set_case_insensitive`],
+    /// which allows to derive selectors in expression position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create case-insensitive selector
+    /// let selector = Selector::new()?
+    ///     .with_path("docs/**/*.md")?
+    ///     .with_case_insensitive(true);
+    /// assert!(selector.is_case_insensitive());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.set_case_insensitive(case_insensitive);
+        self
+    }
+
+    /// Normalizes the selector.
+    ///
+    /// Persisted selectors tend to accumulate redundant glob constructs, which
+    /// makes deduplication by equality fail, and bloats compiled glob sets.
+    /// This method rewrites each component into its normal form, so selectors
+    /// can be deduplicated by comparing their normalized forms, which is
+    /// obtained by applying the following rules in order:
+    ///
+    /// 1. Empty alternation groups, i.e., `{}`, are removed.
+    /// 2. Duplicate slashes are collapsed into a single slash.
+    /// 3. Leading `./` segments are stripped.
+    /// 4. Consecutive `**` segments are collapsed into a single `**`.
+    ///
+    /// Character classes are retained as is, and case is significant, so it
+    /// is never changed. If a component would become empty, which would turn
+    /// it into a wildcard, it's retained as is. Removing empty groups and
+    /// collapsing `**` segments never changes which identifiers are matched.
+    /// Duplicate slashes and leading `./` segments, however, can only match
+    /// identifiers that aren't canonical, i.e., that weren't created with
+    /// [`Id::new_normalized`], so they are rewritten to what was intended.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selectors with redundant glob constructs and normalize them
+    /// let selector: Selector = "zrs::::**/*.md:".parse()?;
+    /// for value in [
+    ///     "zrs::::**/**/*.md:",
+    ///     "zrs::::./**/*.md:",
+    ///     "zrs::::**//*.md:",
+    ///     "zrs::::**/*{}.md:",
+    ///     "zrs::::././**//**/{}*.md:",
+    /// ] {
+    ///     let mut other: Selector = value.parse()?;
+    ///     other.normalize()?;
+    ///     assert_eq!(other, selector);
+    /// }
+    ///
+    /// // Character classes and case are retained
+    /// let mut selector: Selector = "zrs::::Docs/[{}]//*.md:".parse()?;
+    /// selector.normalize()?;
+    /// assert_eq!(selector.to_string(), "zrs::::Docs/[{}]/*.md:");
+    ///
+    /// // Components that would become wildcards are retained
+    /// let mut selector: Selector = "zrs::::{}:./".parse()?;
+    /// selector.normalize()?;
+    /// assert_eq!(selector.to_string(), "zrs::::{}:./");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn normalize(&mut self) -> Result<&mut Self> {
+        self.cache.clear();
+        for index in 1..6 {
+            let value = match normalize(&self.format.get(index)) {
+                Cow::Borrowed(_) => continue,
+                Cow::Owned(value) => value,
+            };
+            self.format.set(index, value)?;
+        }
+        Ok(self)
+    }
+
+    /// Returns the normalized selector.
+    ///
+    /// This is the non-mutating variant of [`Selector::normalize`], which
+    /// leaves the original selector untouched.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selectors and ensure they normalize identically
+    /// let a: Selector = "zrs::::docs/**/*.md:".parse()?;
+    /// let b: Selector = "zrs::::./docs//**/**/*.md:".parse()?;
+    /// assert_ne!(a, b);
+    /// assert_eq!(a.normalized()?, b.normalized()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Normalized selectors match the same identifiers as the originals:
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "globset", feature = "proptest"))]
+    /// # fn main() {
+    /// use proptest::collection::vec;
+    /// use proptest::prelude::*;
+    /// use proptest::sample::select;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Ensure normalization retains the set of matched identifiers
+    /// proptest!(|(selector: Selector, id: Id)| {
+    ///     let normalized = selector.normalized().unwrap();
+    ///     prop_assert_eq!(
+    ///         normalized.is_match(&id).unwrap(),
+    ///         selector.is_match(&id).unwrap()
+    ///     );
+    /// });
+    ///
+    /// // Ensure redundant glob constructs don't change matched identifiers,
+    /// // drawing segments from small sets so that many identifiers match
+    /// let globs = select(vec!["a", "b", "*", "**", "*.md", "a*"]);
+    /// let paths = select(vec!["a", "b", "c.md", "ab.md"]);
+    /// proptest!(|(globs in vec(globs, 1..=4), path in vec(paths, 1..=4))| {
+    ///     let glob = globs.join("/");
+    ///     let redundant = format!("{}{{}}", glob.replace("**", "**/**"));
+    ///     let noisy = format!("./{}", redundant.replace('/', "//"));
+    ///
+    ///     // Create selectors and identifier
+    ///     let a = Selector::new().unwrap().with_path(glob).unwrap();
+    ///     let b = Selector::new().unwrap().with_path(redundant).unwrap();
+    ///     let c = Selector::new().unwrap().with_path(noisy).unwrap();
+    ///     let id = Id::new("file", "docs", path.join("/")).unwrap();
+    ///
+    ///     // Ensure selectors normalize identically
+    ///     let normalized = a.normalized().unwrap();
+    ///     prop_assert_eq!(&b.normalized().unwrap(), &normalized);
+    ///     prop_assert_eq!(&c.normalized().unwrap(), &normalized);
+    ///
+    ///     // Ensure selectors match identically, if they're equivalent
+    ///     let expected = a.is_match(&id).unwrap();
+    ///     prop_assert_eq!(b.is_match(&id).unwrap(), expected);
+    ///     prop_assert_eq!(normalized.is_match(&id).unwrap(), expected);
+    /// });
+    /// # }
+    /// # #[cfg(not(all(feature = "globset", feature = "proptest")))]
+    /// # fn main() {}
+    /// ```
+    pub fn normalized(&self) -> Result<Self> {
+        let mut selector = self.clone();
+        selector.normalize()?;
+        Ok(selector)
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl Selector {
+    /// Reserved value of optional components, which only matches identifiers
+    /// where the component is absent.
+    pub const EMPTY: &'static str = "-";
+
+    /// Returns the `scheme` component, if any.
+    #[inline]
+    pub fn scheme(&self) -> Option<Cow<'_, str>> {
+        (!self.format.is_component_empty(1)).then(|| self.format.get(1))
+    }
+
+    /// Returns the `binding` component, if any.
+    #[inline]
+    pub fn binding(&self) -> Option<Cow<'_, str>> {
+        (!self.format.is_component_empty(2)).then(|| self.format.get(2))
+    }
+
+    /// Returns the `context` component, if any.
+    #[inline]
+    pub fn context(&self) -> Option<Cow<'_, str>> {
+        (!self.format.is_component_empty(3)).then(|| self.format.get(3))
+    }
+
+    /// Returns the `path` component, if any.
+    #[inline]
+    pub fn path(&self) -> Option<Cow<'_, str>> {
+        (!self.format.is_component_empty(4)).then(|| self.format.get(4))
+    }
+
+    /// Returns an iterator over the segments of the `path` component.
+    ///
+    /// Segments are decoded, and empty segments are skipped, exactly like in
+    /// [`Id::path_segments`]. If there's no `path` component, the iterator is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector and obtain path segments
+    /// let selector: Selector = "zrs:::docs:**/a%3A*.md/:".parse()?;
+    /// let segments: Vec<_> = selector.path_segments().collect();
+    /// assert_eq!(segments, ["**", "a:*.md"]);
+    /// assert_eq!(selector.path_segment_count(), 2);
+    ///
+    /// // Create selector without path
+    /// let selector: Selector = "zrs:::docs::".parse()?;
+    /// assert_eq!(selector.path_segment_count(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn path_segments(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        self.path().into_iter().flat_map(split_segments)
+    }
+
+    /// Returns the number of segments of the `path` component.
+    ///
+    /// This counts the segments yielded by [`Selector::path_segments`].
+    #[inline]
+    pub fn path_segment_count(&self) -> usize {
+        self.path_segments().count()
+    }
+
+    /// Returns the `fragment` component, if any.
+    #[inline]
+    pub fn fragment(&self) -> Option<Cow<'_, str>> {
+        (!self.format.is_component_empty(5)).then(|| self.format.get(5))
+    }
+
+    /// Returns whether the selector is negated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selectors and check negation
+    /// let a: Selector = "zrs::::**/*.md:".parse()?;
+    /// let b: Selector = "!zrs::::drafts/**:".parse()?;
+    /// assert!(!a.is_negated());
+    /// assert!(b.is_negated());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_negated(&self) -> bool {
+        self.format.as_str().starts_with('!')
+    }
+
+    /// Returns whether the selector is case-insensitive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selectors and check case-insensitivity
+    /// let a: Selector = "zrs::::**/*.md:".parse()?;
+    /// let b = a.clone().with_case_insensitive(true);
+    /// assert!(!a.is_case_insensitive());
+    /// assert!(b.is_case_insensitive());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Returns whether the given component only matches absent values.
+    ///
+    /// This is only the case for the optional `binding` and `fragment`
+    /// components, if they're set to [`Selector::EMPTY`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector and check which components only match absent values
+    /// let selector: Selector = "zrs:-:-:::-".parse()?;
+    /// assert!(!selector.is_empty_only(Component::Scheme));
+    /// assert!(selector.is_empty_only(Component::Binding));
+    /// assert!(selector.is_empty_only(Component::Fragment));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_empty_only(&self, component: Component) -> bool {
+        component.is_optional()
+            && self.format.get(component.index()) == Self::EMPTY
+    }
+
+    /// Returns the glob of the given component, if any.
+    ///
+    /// Components that only match absent values are returned as the empty
+    /// glob, which only matches the empty string, and thus no value of an
+    /// optional component, since those are never empty.
+    pub(crate) fn glob(&self, component: Component) -> Option<Cow<'_, str>> {
+        if self.is_empty_only(component) {
+            return Some(Cow::Borrowed(""));
+        }
+        let index = component.index();
+        (!self.format.is_component_empty(index)).then(|| self.format.get(index))
+    }
+
+    /// Writes the string representation to the given writer.
+    ///
+    /// See [`Format::write_to`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the writer are propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector and write it
+    /// let selector = "zrs:::docs:*%3A*.md:".parse::<Selector>()?;
+    /// let mut buffer = Vec::new();
+    /// selector.write_to(&mut buffer)?;
+    /// assert_eq!(buffer, selector.to_string().as_bytes());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn write_to<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.format.write_to(writer)
+    }
+
+    /// Writes the string representation to the given formatter.
+    ///
+    /// See [`Format::fmt_to`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the writer are propagated.
+    #[inline]
+    pub fn fmt_to<W>(&self, writer: &mut W) -> fmt::Result
+    where
+        W: fmt::Write,
+    {
+        self.format.fmt_to(writer)
+    }
+
+    /// Writes the decoded components to the given writer.
+    ///
+    /// See [`Format::write_decoded_to`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the writer are propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector and write decoded components
+    /// let selector = "zrs:::docs:*%3A*.md:".parse::<Selector>()?;
+    /// let mut buffer = Vec::new();
+    /// selector.write_decoded_to(&mut buffer)?;
+    /// assert_eq!(buffer, b"zrs:::docs:*\\:*.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn write_decoded_to<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.format.write_decoded_to(writer)
+    }
+
+    /// Returns the fingerprint of the selector.
+    ///
+    /// See [`Id::fingerprint`] for details on stability guarantees, which
+    /// allows subscription registries to key on selectors.
+    ///
+    /// [`Id::fingerprint`]: crate::Id::fingerprint
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Define selectors and expected fingerprints
+    /// let cases = [
+    ///     ("zrs::::**/*.md:", 0x5dcf_7254_1196_e0fc),
+    ///     ("zrs:file:::**:", 0x1174_ba0b_eb43_92f2),
+    ///     ("zrs:::docs:*.md:", 0xd6dd_5a20_bacc_7afc),
+    /// ];
+    ///
+    /// // Ensure that each fingerprint matches
+    /// for (value, fingerprint) in cases {
+    ///     let selector: Selector = value.parse()?;
+    ///     assert_eq!(selector.fingerprint(), fingerprint, "{value}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn fingerprint(&self) -> u64 {
+        self.format.fingerprint()
+    }
+
+    /// Checks if the selector matches an identifier.
+    ///
+    /// This is a convenience for checks against a single selector, which
+    /// compiles the globs of all present components on the first call, and
+    /// caches them in the selector, so repeated checks only match them. The
+    /// cache is shared between clones, and cleared when the selector is
+    /// updated. Components are compared in the same order as the
+    /// [`Matcher`][] does, so both yield identical results. Empty components
+    /// are wildcards, so they're skipped without compilation, and empty-only
+    /// components only match absent values. When checking identifiers against
+    /// many selectors, build a [`Matcher`][] instead.
+    ///
+    /// Negation is ignored, so a negated selector like `!zrs::::drafts/**:`
+    /// matches the same identifiers as `zrs::::drafts/**:`, as negation only
+    /// affects how matchers combine selectors. Case-insensitivity is honored.
+    ///
+    /// [`Matcher`]: crate::Matcher
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Glob`], if a present component is not a
+    /// valid glob.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and selectors
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// let a: Selector = "zrs:::docs:**/*.md:".parse()?;
+    /// let b: Selector = "zrs:git::::".parse()?;
+    ///
+    /// // Check if the selectors match the identifier
+    /// assert!(a.is_match(&id)?);
+    /// assert!(!b.is_match(&id)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Empty-only components match identifiers where the component is absent:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers with and without fragment
+    /// let a = Id::new("file", "docs", "index.md")?;
+    /// let b = a.clone().with_fragment("top")?;
+    ///
+    /// // Check empty-only and non-empty selectors against both
+    /// let empty: Selector = "zrs::::**/*.md:-".parse()?;
+    /// let other: Selector = "zrs::::**/*.md:*".parse()?;
+    /// assert!(empty.is_match(&a)?);
+    /// assert!(!empty.is_match(&b)?);
+    /// assert!(!other.is_match(&a)?);
+    /// assert!(other.is_match(&b)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Updating a selector clears the compiled globs:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and selector, and check if it matches
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// let mut selector: Selector = "zrs::::**/*.md:".parse()?;
+    /// assert!(selector.is_match(&id)?);
+    ///
+    /// // Update selector, and check again
+    /// selector.set_path("**/*.rs")?;
+    /// assert!(!selector.is_match(&id)?);
+    /// selector.set_path("**/*.MD")?.set_case_insensitive(true);
+    /// assert!(selector.is_match(&id)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Negated selectors match like their positive counterparts:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and negated selector
+    /// let id = Id::new("file", "docs", "drafts/a.md")?;
+    /// let selector: Selector = "!zrs::::drafts/**:".parse()?;
+    ///
+    /// // Check if the selector matches the identifier, ignoring negation
+    /// assert!(selector.is_negated());
+    /// assert!(selector.is_match(&id)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Results are identical to those of a [`Matcher`][] built from the same
+    /// selector:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers
+    /// let ids = [
+    ///     Id::new("file", "docs", "index.md")?,
+    ///     Id::new("file", "docs", "a/b.md")?.with_fragment("top")?,
+    ///     Id::new("git", "docs", "index.md")?.with_binding("main")?,
+    /// ];
+    ///
+    /// // Ensure each selector yields the same result as a matcher
+    /// for value in [
+    ///     "zrs:::::",
+    ///     "zrs::::*.md:",
+    ///     "zrs::::**/*.md:",
+    ///     "zrs:file::docs::",
+    ///     "zrs::*:::",
+    ///     "zrs::main:::",
+    ///     "zrs:::::top",
+    ///     "zrs:::::?*",
+    ///     "zrs:::::-",
+    ///     "zrs::-:::",
+    ///     "zrs:-::::",
+    /// ] {
+    ///     let selector: Selector = value.parse()?;
+    ///     let matcher: Matcher = value.parse()?;
+    ///     for id in &ids {
+    ///         let expected = matcher.is_match(id)?;
+    ///         assert_eq!(selector.is_match(id)?, expected, "{value} {id}");
+    ///         assert_eq!(!matcher.matches(id)?.is_empty(), expected);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "globset")]
+    pub fn is_match(&self, id: &Id) -> Result<bool> {
+        let globs = self.cache.globs(|| self.compile())?;
+        let view = id.view();
+
+        // Compare components in descending variability, and only match absent
+        // values with empty or empty-only components, like the matcher does
+        for (component, value) in [
+            (Component::Path, Some(view.path)),
+            (Component::Context, Some(view.context)),
+            (Component::Scheme, Some(view.scheme)),
+            (Component::Binding, view.binding),
+            (Component::Fragment, view.fragment),
+        ] {
+            if let Some(glob) = &globs[component.index() - 1] {
+                let matched = match value {
+                    Some(value) => glob.is_match(value.as_ref()),
+                    None => glob.glob().glob().is_empty(),
+                };
+                if !matched {
+                    return Ok(false);
+                }
+            }
+        }
+
+        // All components matched
+        Ok(true)
+    }
+
+    /// Compiles the globs of all present components, in the order of
+    /// components.
+    #[cfg(feature = "globset")]
+    fn compile(&self) -> Result<[Option<GlobMatcher>; 5]> {
+        let mut globs = [None, None, None, None, None];
+        for component in Component::ALL {
+            if let Some(pattern) = self.glob(component) {
+                let glob = GlobBuilder::new(&pattern)
+                    .case_insensitive(self.case_insensitive)
+                    .build()?;
+                globs[component.index() - 1] = Some(glob.compile_matcher());
+            }
+        }
+        Ok(globs)
+    }
+
+    /// Returns whether the selector covers another selector.
+    ///
+    /// A selector covers another selector, if it matches every identifier the
+    /// other selector matches, e.g., `zrs::::docs/**:` covers the selector
+    /// `zrs::::docs/api/*.md:`, which is useful for detecting selectors that
+    /// are shadowed by others. Empty components cover everything, but are only
+    /// covered by empty components, as they're the only ones matching all
+    /// absent values, and empty-only components, i.e., [`Selector::EMPTY`],
+    /// are only covered by themselves and empty components. Negation is
+    /// ignored, as it only affects how matchers combine selectors.
+    ///
+    /// The check is conservative, and compares the globs of both selectors
+    /// structurally, component by component. It supports literals, `*`, `**`
+    /// and `?`, and thus suffix patterns like `*.md`. Components with other
+    /// syntax, e.g., character classes or alternations, only cover identical
+    /// components. Thus, if this method returns `true`, the selector is
+    /// guaranteed to cover the other selector, but if it returns `false`, it
+    /// might still do, as it just couldn't be proven.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Glob`], if a component that needs to be
+    /// compared is not a valid glob.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selectors
+    /// let a: Selector = "zrs::::docs/**:".parse()?;
+    /// let b: Selector = "zrs::::docs/api/*.md:".parse()?;
+    ///
+    /// // Check if the selectors cover each other
+    /// assert!(a.covers(&b)?);
+    /// assert!(!b.covers(&a)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Selectors are only reported to cover others, if it can be proven:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Define pairs of selectors, and whether the first covers the second
+    /// for (a, b, expected) in [
+    ///     ("zrs:::::", "zrs:file::docs:**/*.md:", true),
+    ///     ("zrs::::**:", "zrs:::::", true),
+    ///     ("zrs::::*.md:", "zrs::::**/*.md:", true),
+    ///     ("zrs::::**/*.md:", "zrs::::index.md:", true),
+    ///     ("zrs::::**/*.md:", "zrs::::docs/**/a?.md:", true),
+    ///     ("zrs::::docs/*.md:", "zrs::::docs/**/*.md:", true),
+    ///     ("zrs::::?.md:", "zrs::::a.md:", true),
+    ///     ("zrs:file:::**:", "zrs:file::docs:a.md:", true),
+    ///     ("zrs::::*.{md,txt}:", "zrs::::*.{md,txt}:", true),
+    ///     ("zrs::::docs/**:", "zrs::::docs:", false),
+    ///     ("zrs::::docs/**:", "zrs::::**/*.md:", false),
+    ///     ("zrs::::**/*.md:", "zrs:::::", false),
+    ///     ("zrs::::?.md:", "zrs::::ab.md:", false),
+    ///     ("zrs:file::::", "zrs::::docs/**:", false),
+    ///     ("zrs:::::anchor", "zrs:::::", false),
+    ///     ("zrs::::*.{md,txt}:", "zrs::::a.md:", false),
+    ///     ("zrs:::::*", "zrs:::::", false),
+    ///     ("zrs::**:::", "zrs:::::", false),
+    ///     ("zrs:::::", "zrs:::::-", true),
+    ///     ("zrs:::::-", "zrs:::::-", true),
+    ///     ("zrs:::::*", "zrs:::::-", false),
+    ///     ("zrs:::::-", "zrs:::::", false),
+    ///     ("zrs:::::-", "zrs:::::top", false),
+    /// ] {
+    ///     let a: Selector = a.parse()?;
+    ///     let b: Selector = b.parse()?;
+    ///     assert_eq!(a.covers(&b)?, expected, "{a} {b}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Whenever a selector is reported to cover another selector, matchers
+    /// agree, i.e., every identifier matched by the other is matched as well:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifiers
+    /// let ids = [
+    ///     Id::new("file", "docs", "index.md")?,
+    ///     Id::new("file", "docs", "docs/a.md")?,
+    ///     Id::new("file", "docs", "docs/api/b.md")?.with_fragment("top")?,
+    ///     Id::new("git", "site", "docs/api/b.txt")?.with_binding("main")?,
+    /// ];
+    ///
+    /// // Ensure covering is consistent with matching
+    /// let values = [
+    ///     "zrs:::::",
+    ///     "zrs::::*.md:",
+    ///     "zrs::::**/*.md:",
+    ///     "zrs::::docs/**:",
+    ///     "zrs::::docs/api/*.md:",
+    ///     "zrs::::docs/?.md:",
+    ///     "zrs:file::docs::",
+    ///     "zrs::main:::",
+    ///     "zrs:::::top",
+    ///     "zrs:::::*",
+    ///     "zrs::**:::",
+    ///     "zrs:::::-",
+    ///     "zrs::-:::",
+    /// ];
+    /// # #[cfg(feature = "globset")]
+    /// for a in values {
+    ///     for b in values {
+    ///         let a: Selector = a.parse()?;
+    ///         let b: Selector = b.parse()?;
+    ///         if a.covers(&b)? {
+    ///             for id in &ids {
+    ///                 assert!(!b.is_match(id)? || a.is_match(id)?, "{a} {b}");
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn covers(&self, other: &Selector) -> Result<bool> {
+        // Case-insensitive selectors match identifiers that case-sensitive
+        // selectors don't, so only absent components can cover them
+        let strict = other.case_insensitive && !self.case_insensitive;
+
+        // Identifiers always have a scheme, context and path, so absent values
+        // in those components match the same as `**`, while absent binding or
+        // fragment values also match identifiers without them
+        for component in [
+            Component::Path,
+            Component::Context,
+            Component::Scheme,
+            Component::Binding,
+            Component::Fragment,
+        ] {
+            let pattern = self.glob(component);
+            let value = other.glob(component);
+            let value = value
+                .as_deref()
+                .or((!component.is_optional()).then_some("**"));
+            if !covers(pattern.as_deref(), value)?
+                || strict && pattern.is_some()
+            {
+                return Ok(false);
+            }
+        }
+
+        // All components covered
+        Ok(true)
+    }
+
+    /// Returns the specificity of the selector.
+    ///
+    /// Specificity is a pair of the number of present components, and the
+    /// number of literal characters in all components, i.e., characters that
+    /// are not glob metacharacters. Pairs are compared lexicographically, so
+    /// a selector constraining more components is always more specific.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selectors and compare specificity
+    /// let a: Selector = "zrs::::**/*.md:".parse()?;
+    /// let b: Selector = "zrs::::docs/*.md:".parse()?;
+    /// let c: Selector = "zrs:file:::**:".parse()?;
+    /// assert_eq!(a.specificity(), (1, 4));
+    /// assert!(a.specificity() < b.specificity());
+    /// assert!(b.specificity() < c.specificity());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn specificity(&self) -> (usize, usize) {
+        [
+            self.scheme(),
+            self.binding(),
+            self.context(),
+            self.path(),
+            self.fragment(),
+        ]
+        .iter()
+        .flatten()
+        .fold((0, 0), |(components, literals), value| {
+            let iter = value.chars().filter(|char| !"*?[]{}".contains(*char));
+            (components + 1, literals + iter.count())
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl ToSelector for &Selector {
+    /// Creates a selector from a reference.
+    #[inline]
+    fn to_selector(&self) -> Result<Cow<'_, Selector>> {
+        Ok(Cow::Borrowed(self))
+    }
+}
+
+impl ToSelector for &str {
+    /// Creates a selector from a string.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if a component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector from string
+    /// let selector = "zrs::::**/*.md:".to_selector()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn to_selector(&self) -> Result<Cow<'_, Selector>> {
+        self.parse().map(Cow::Owned)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl TryFrom<&Id> for Selector {
+    type Error = Error;
+
+    /// Attempts to create a selector that matches the given identifier.
+    ///
+    /// This is equivalent to [`Selector::literal`], see there for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # #[cfg(feature = "globset")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher from identifiers with glob metacharacters
+    /// let a = Id::new("file", "docs", "what?.md")?;
+    /// let b = Id::new("file", "docs", "[draft] notes.md")?;
+    /// let mut builder = Matcher::builder();
+    /// builder.add(&Selector::try_from(&a)?)?;
+    /// builder.add(&Selector::try_from(&b)?)?;
+    /// let matcher = builder.build()?;
+    ///
+    /// // Ensure only the identifiers themselves are matched
+    /// assert!(matcher.is_match(&a)?);
+    /// assert!(matcher.is_match(&b)?);
+    /// for other in [
+    ///     Id::new("file", "docs", "whatX.md")?,
+    ///     Id::new("file", "docs", "d notes.md")?,
+    ///     Id::new("file", "docs", "t notes.md")?,
+    /// ] {
+    ///     assert!(!matcher.is_match(&other)?, "{other}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "globset"))]
+    /// # fn main() {}
+    /// ```
+    #[inline]
+    fn try_from(id: &Id) -> Result<Self> {
+        Self::literal(id)
+    }
+}
+
+impl FromStr for Selector {
+    type Err = Error;
+
+    /// Creates a selector from a string.
+    ///
+    /// The string must adhere to the following format and include exactly five
+    /// `:` separators, even if some components are empty. The versioned `zrs1`
+    /// prefix is accepted as an alias for `zrs`, and a leading `!` negates the
+    /// selector, e.g., `!zrs::::drafts/**:`.
+    ///
+    /// ``` text
+    /// zrs:<scheme>:<binding>:<context>:<path>:<fragment>
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if a component value contains a
+    /// backslash, [`Error::Version`], if the format version is unsupported, or
+    /// [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector from string
+    /// let selector: Selector = "zrs::::**/*.md:".parse()?;
+    ///
+    /// // Create selector from string with versioned prefix
+    /// let versioned: Selector = "zrs1::::**/*.md:".parse()?;
+    /// assert_eq!(versioned, selector);
+    /// assert!("zrs9::::**/*.md:".parse::<Selector>().is_err());
+    ///
+    /// // Create negated selector from string, which round-trips
+    /// let negated: Selector = "!zrs1::::drafts/**:".parse()?;
+    /// assert_eq!(negated.to_string(), "!zrs::::drafts/**:");
+    /// assert_eq!(negated.to_string().parse::<Selector>()?, negated);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from_str(value: &str) -> Result<Self> {
+        let format = Format::from_str_with(value, 6, |offset, byte| {
+            validate_byte(offset, byte).map_err(Error::from)
+        })?;
+
+        from_format(format)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Selector {
+    /// Formats the selector for display.
+    ///
+    /// In alternate mode, i.e., with `{:#}`, the components are decoded and
+    /// labeled for human-readable output, omitting empty components, which is
+    /// not canonical, and thus should not be used for logs or serialization.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector and format it for humans
+    /// let selector: Selector = "zrs:::docs:**/*.md:".parse()?;
+    /// assert_eq!(format!("{selector:#}"), "context=docs path=**/*.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            if self.is_negated() {
+                f.write_str("!")?;
+            }
+            self.format.fmt_components(f, LABELS)
+        } else {
+            self.format.fmt(f)
+        }
+    }
+}
 
 impl fmt::Debug for Selector {
     /// Formats the selector for debugging.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Selector")
+            .field("negated", &self.is_negated())
+            .field("case_insensitive", &self.is_case_insensitive())
             .field("scheme", &self.scheme())
             .field("binding", &self.binding())
             .field("context", &self.context())
@@ -438,3 +1868,160 @@ impl fmt::Debug for Selector {
             .finish()
     }
 }
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Creates a selector from a parsed formatted string.
+///
+/// This function is shared by [`Selector::from_str`] and
+/// [`Selector::from_bytes`], and checks the prefix.
+fn from_format(mut format: Format<6>) -> Result<Selector> {
+    // Ensure prefix is valid, comparing the raw bytes first, and only
+    // decoding the prefix if it doesn't match, as it might be encoded. A
+    // leading `!` marks the selector as negated, and is stripped first
+    let prefix = &format.as_str()[format.span(0)];
+    let (negated, version) = parse_prefix(prefix)
+        .or_else(|_| parse_prefix(&format.get(0)))
+        .map_err(|_| Error::Prefix)?;
+
+    // Ensure version is supported, as selectors share the identifier's
+    // format version, and thus must be kept in sync
+    if version != Id::FORMAT_VERSION {
+        Err(Error::Version(version))?;
+    }
+
+    // Normalize prefix if it's versioned or encoded, so selectors are
+    // always emitted unversioned, and compare equal regardless of prefix
+    let prefix: &[u8] = if negated { b"!zrs" } else { b"zrs" };
+    if format.span(0).len() != prefix.len() {
+        format.set(0, prefix)?;
+    }
+
+    // No errors occurred
+    Ok(Selector {
+        format,
+        case_insensitive: false,
+        cache: Cache::default(),
+    })
+}
+
+/// Normalizes the value of a component.
+///
+/// See [`Selector::normalize`] for the rules that are applied, which retain
+/// character classes, as all characters inside of them are literals.
+fn normalize(value: &str) -> Cow<'_, str> {
+    let mut buffer = String::with_capacity(value.len());
+
+    // Remove empty alternation groups, copying character classes verbatim,
+    // while also accounting for negation and `]` in first position
+    let mut chars = value.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '[' => {
+                buffer.push(char);
+                if let Some(char) = chars.next_if(|&c| c == '!' || c == '^') {
+                    buffer.push(char);
+                }
+                if let Some(char) = chars.next_if_eq(&']') {
+                    buffer.push(char);
+                }
+                for char in chars.by_ref() {
+                    buffer.push(char);
+                    if char == ']' {
+                        break;
+                    }
+                }
+            }
+            '{' if chars.next_if_eq(&'}').is_some() => {
+                // Retain group, if removing it would join two `*` into `**`,
+                // which might turn them into a recursive wildcard
+                if buffer.ends_with('*') && chars.peek() == Some(&'*') {
+                    buffer.push_str("{}");
+                }
+            }
+            _ => buffer.push(char),
+        }
+    }
+
+    // Collapse duplicate slashes by dropping empty segments, except for the
+    // first and last segment, which denote leading and trailing slashes
+    let mut segments: Vec<&str> = Vec::new();
+    let last = buffer.split('/').count() - 1;
+    for (index, segment) in buffer.split('/').enumerate() {
+        if segment.is_empty() && index != 0 && index != last {
+            continue;
+        }
+
+        // Drop leading `.` segments and consecutive `**` segments
+        match (segments.last(), segment) {
+            (None, ".") if index != last => {}
+            (Some(&"**"), "**") => {}
+            _ => segments.push(segment),
+        }
+    }
+
+    // Return value as is, if nothing changed, or if it would become empty,
+    // since empty components are wildcards
+    let normalized = segments.join("/");
+    if normalized == value || normalized.is_empty() {
+        Cow::Borrowed(value)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+/// Returns whether a component covers another component.
+///
+/// Absent components are wildcards, so they cover everything when given as
+/// the pattern, and are only covered by absent components, as they're the
+/// only ones matching all absent values. Empty globs denote empty-only
+/// components, which are only covered by wildcards and themselves.
+#[cfg_attr(not(feature = "globset"), allow(clippy::unnecessary_wraps))]
+fn covers(pattern: Option<&str>, value: Option<&str>) -> Result<bool> {
+    let Some(pattern) = pattern else {
+        return Ok(true);
+    };
+
+    // Ensure both components are valid globs, so the result is consistent
+    // with errors reported by the matcher when building it
+    #[cfg(feature = "globset")]
+    for glob in [Some(pattern), value].into_iter().flatten() {
+        Glob::new(glob)?;
+    }
+
+    // Identical components trivially cover each other, which also includes
+    // syntax that isn't supported by patterns, e.g., character classes
+    if Some(pattern) == value {
+        return Ok(true);
+    }
+
+    // Empty components also match absent values, which only empty components
+    // do, so they can't be covered by any pattern. Empty-only components are
+    // given as empty globs, and only match absent values, so they can neither
+    // be covered by nor cover other patterns
+    let Some(value) = value else {
+        return Ok(false);
+    };
+    if pattern.is_empty() || value.is_empty() {
+        return Ok(false);
+    }
+
+    // Compare patterns structurally, if both are supported
+    match (Pattern::new(pattern), Pattern::new(value)) {
+        (Ok(pattern), Ok(value)) => Ok(pattern.covers(&value)),
+        _ => Ok(false),
+    }
+}
+
+/// Parses the prefix of a selector, returning negation and version.
+fn parse_prefix(prefix: &str) -> Result<(bool, u32)> {
+    let (negated, prefix) = match prefix.strip_prefix('!') {
+        Some(prefix) => (true, prefix),
+        None => (false, prefix),
+    };
+    version::parse(prefix, "zrs")
+        .map(|version| (negated, version))
+        .map_err(|_| Error::Prefix)
+}
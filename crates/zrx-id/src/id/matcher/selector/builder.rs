@@ -0,0 +1,574 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Selector builder.
+
+#[cfg(feature = "globset")]
+use globset::Glob;
+use std::borrow::Cow;
+
+use crate::format::Format;
+use crate::id::Component;
+use crate::path::validate;
+
+use super::cache::Cache;
+#[cfg(feature = "globset")]
+use super::Error;
+use super::{Result, Selector};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Selector builder.
+///
+/// Other than the [`IdBuilder`][], which defers validation to the build step,
+/// selector builders validate each component right away, including its glob
+/// syntax, so errors like unbalanced `[` or `{` are reported where the bad
+/// pattern is introduced, and not only when a [`Matcher`][] is built. The
+/// [`SelectorBuilder::build`] method writes all components to the formatted
+/// string in a single pass.
+///
+/// Each component can also be set from a list of patterns, e.g., with
+/// [`SelectorBuilder::path_any`], which joins them into a single alternation
+/// group, so callers don't need to write raw glob syntax to target multiple
+/// extensions or directories.
+///
+/// [`IdBuilder`]: crate::IdBuilder
+/// [`Matcher`]: crate::Matcher
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::prelude::*;
+///
+/// // Create selector from builder
+/// let selector = Selector::builder()
+///     .scheme("file")?
+///     .context("docs")?
+///     .path("**/*.{md,txt}")?
+///     .build()?;
+/// assert_eq!(selector.to_string(), "zrs:file::docs:**/*.{md,txt}:");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SelectorBuilder<'a> {
+    /// Scheme component.
+    scheme: Cow<'a, str>,
+    /// Binding component.
+    binding: Cow<'a, str>,
+    /// Context component.
+    context: Cow<'a, str>,
+    /// Path component.
+    path: Cow<'a, str>,
+    /// Fragment component.
+    fragment: Cow<'a, str>,
+    /// Whether matching is case-insensitive.
+    case_insensitive: bool,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Selector {
+    /// Creates a selector builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector builder
+    /// let builder = Selector::builder();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn builder<'a>() -> SelectorBuilder<'a> {
+        SelectorBuilder::default()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<'a> SelectorBuilder<'a> {
+    /// Sets the `scheme` component.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`SelectorBuilder::path`].
+    #[inline]
+    pub fn scheme(mut self, scheme: &'a str) -> Result<Self> {
+        self.scheme = check(Component::Scheme, scheme.into())?;
+        Ok(self)
+    }
+
+    /// Sets the `scheme` component to match any of the given patterns.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`SelectorBuilder::path`].
+    #[inline]
+    pub fn scheme_any<I>(mut self, patterns: I) -> Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let value = alternate(patterns);
+        self.scheme = check(Component::Scheme, value.into())?;
+        Ok(self)
+    }
+
+    /// Sets the `binding` component.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`SelectorBuilder::path`].
+    #[inline]
+    pub fn binding(mut self, binding: &'a str) -> Result<Self> {
+        self.binding = check(Component::Binding, binding.into())?;
+        Ok(self)
+    }
+
+    /// Sets the `binding` component to match any of the given patterns.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`SelectorBuilder::path`].
+    #[inline]
+    pub fn binding_any<I>(mut self, patterns: I) -> Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let value = alternate(patterns);
+        self.binding = check(Component::Binding, value.into())?;
+        Ok(self)
+    }
+
+    /// Sets the `binding` component to only match absent bindings.
+    ///
+    /// This sets the component to [`Selector::EMPTY`], so the selector only
+    /// matches identifiers without a binding, while an empty component would
+    /// match identifiers regardless of their binding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # #[cfg(feature = "globset")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector matching identifiers without binding
+    /// let selector = Selector::builder().binding_empty().build()?;
+    /// assert_eq!(selector.to_string(), "zrs::-:::");
+    ///
+    /// // Check selector against identifiers with and without binding
+    /// let id = Id::new("git", "docs", "index.md")?;
+    /// assert!(selector.is_match(&id)?);
+    /// assert!(!selector.is_match(&id.with_binding("main")?)?);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "globset"))]
+    /// # fn main() {}
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn binding_empty(mut self) -> Self {
+        self.binding = Cow::Borrowed(Selector::EMPTY);
+        self
+    }
+
+    /// Sets the `context` component.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`SelectorBuilder::path`].
+    #[inline]
+    pub fn context(mut self, context: &'a str) -> Result<Self> {
+        self.context = check(Component::Context, context.into())?;
+        Ok(self)
+    }
+
+    /// Sets the `context` component to match any of the given patterns.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`SelectorBuilder::path`].
+    #[inline]
+    pub fn context_any<I>(mut self, patterns: I) -> Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let value = alternate(patterns);
+        self.context = check(Component::Context, value.into())?;
+        Ok(self)
+    }
+
+    /// Sets the `path` component.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if the component value contains a
+    /// backslash, or [`Error::Pattern`], if it's not a valid glob, carrying
+    /// the name of the component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # #[cfg(feature = "globset")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector builder and set path
+    /// let builder = Selector::builder().path("**/*.md")?;
+    ///
+    /// // Create selector builder and set invalid path
+    /// let err = Selector::builder().path("docs/[a-z").unwrap_err();
+    /// assert!(err.to_string().starts_with("invalid path pattern: "));
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "globset"))]
+    /// # fn main() {}
+    /// ```
+    #[inline]
+    pub fn path(mut self, path: &'a str) -> Result<Self> {
+        self.path = check(Component::Path, path.into())?;
+        Ok(self)
+    }
+
+    /// Sets the `path` component to match any of the given patterns.
+    ///
+    /// The patterns are joined into a single alternation group, i.e., `{a,b}`,
+    /// which is stored as is, so [`Selector::path`] returns the group, and
+    /// matchers behave exactly as if the group was written by hand. Commas and
+    /// braces inside of patterns are escaped as character classes, so they are
+    /// matched literally, while all other glob syntax is retained. Note that
+    /// an empty list of patterns matches nothing, not everything.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`SelectorBuilder::path`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # #[cfg(feature = "globset")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector matching multiple extensions
+    /// let selector = Selector::builder()
+    ///     .path_any(["**/*.md", "**/*.markdown"])?
+    ///     .build()?;
+    /// assert_eq!(selector.path().as_deref(), Some("{**/*.md,**/*.markdown}"));
+    /// assert!(selector.is_match(&Id::new("file", "docs", "a/b.markdown")?)?);
+    /// assert!(!selector.is_match(&Id::new("file", "docs", "a/b.txt")?)?);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "globset"))]
+    /// # fn main() {}
+    /// ```
+    ///
+    /// Commas and braces inside of patterns are matched literally:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # #[cfg(feature = "globset")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector from patterns with commas and braces
+    /// let selector = Selector::builder()
+    ///     .path_any(["a,b.md", "{c}.md", "[,]d.md"])?
+    ///     .build()?;
+    /// assert_eq!(
+    ///     selector.path().as_deref(),
+    ///     Some("{a[,]b.md,[{]c[}].md,[,]d.md}")
+    /// );
+    ///
+    /// // Ensure patterns match literally, and aren't split
+    /// for (path, expected) in [
+    ///     ("a,b.md", true),
+    ///     ("{c}.md", true),
+    ///     (",d.md", true),
+    ///     ("a", false),
+    ///     ("b.md", false),
+    ///     ("c.md", false),
+    /// ] {
+    ///     let id = Id::new("file", "docs", path)?;
+    ///     assert_eq!(selector.is_match(&id)?, expected, "{path}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "globset"))]
+    /// # fn main() {}
+    /// ```
+    #[inline]
+    pub fn path_any<I>(mut self, patterns: I) -> Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let value = alternate(patterns);
+        self.path = check(Component::Path, value.into())?;
+        Ok(self)
+    }
+
+    /// Sets the `fragment` component.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`SelectorBuilder::path`].
+    #[inline]
+    pub fn fragment(mut self, fragment: &'a str) -> Result<Self> {
+        self.fragment = check(Component::Fragment, fragment.into())?;
+        Ok(self)
+    }
+
+    /// Sets the `fragment` component to match any of the given patterns.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`SelectorBuilder::path`].
+    #[inline]
+    pub fn fragment_any<I>(mut self, patterns: I) -> Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let value = alternate(patterns);
+        self.fragment = check(Component::Fragment, value.into())?;
+        Ok(self)
+    }
+
+    /// Sets the `fragment` component to only match absent fragments.
+    ///
+    /// This sets the component to [`Selector::EMPTY`], so the selector only
+    /// matches identifiers without a fragment, while an empty component would
+    /// match identifiers regardless of their fragment. The reserved value is
+    /// part of the string representation, so it round-trips.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector matching Markdown files without fragment
+    /// let selector = Selector::builder()
+    ///     .path("**/*.md")?
+    ///     .fragment_empty()
+    ///     .build()?;
+    /// assert_eq!(selector.to_string(), "zrs::::**/*.md:-");
+    /// assert_eq!(selector.to_string().parse::<Selector>()?, selector);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Empty-only and non-empty components against identifiers with absent
+    /// and present components:
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # #[cfg(feature = "globset")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create matcher builder and add selectors
+    /// let mut builder = Matcher::builder();
+    /// builder.add(&Selector::builder().fragment_empty().build()?)?;
+    /// builder.add(&Selector::builder().fragment("*")?.build()?)?;
+    ///
+    /// // Create matcher from builder
+    /// let matcher = builder.build()?;
+    ///
+    /// // Create identifiers with and without fragment
+    /// let a = Id::new("file", "docs", "index.md")?;
+    /// let b = a.clone().with_fragment("top")?;
+    ///
+    /// // Obtain match sets for both identifiers
+    /// assert_eq!(matcher.matches(&a)?, [0]);
+    /// assert_eq!(matcher.matches(&b)?, [1]);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "globset"))]
+    /// # fn main() {}
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn fragment_empty(mut self) -> Self {
+        self.fragment = Cow::Borrowed(Selector::EMPTY);
+        self
+    }
+
+    /// Sets whether the selector is case-insensitive.
+    ///
+    /// The flag is preserved when the selector is added to a [`Matcher`][],
+    /// but is not part of the string representation of the selector, see
+    /// [`Selector::set_case_insensitive`] for details.
+    ///
+    /// [`Matcher`]: crate::Matcher
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create case-insensitive selector from builder
+    /// let selector = Selector::builder()
+    ///     .path("docs/**/*.md")?
+    ///     .case_insensitive(true)
+    ///     .build()?;
+    /// assert!(selector.is_case_insensitive());
+    /// assert_eq!(selector.to_string(), "zrs::::docs/**/*.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Builds the selector.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create selector from builder
+    /// let selector = Selector::builder().path("a:*.md")?.build()?;
+    /// assert_eq!(selector.to_string(), "zrs::::a%3A*.md:");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build(self) -> Result<Selector> {
+        let parts = [
+            "zrs",
+            &self.scheme,
+            &self.binding,
+            &self.context,
+            &self.path,
+            &self.fragment,
+        ];
+        Ok(Selector {
+            format: Format::from_parts(parts)?,
+            case_insensitive: self.case_insensitive,
+            cache: Cache::default(),
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Checks the value of a component, including its glob syntax.
+///
+/// Glob syntax is only checked if the `globset` feature is enabled, as it's
+/// the only way to obtain the exact errors that [`Matcher`][] would report.
+///
+/// [`Matcher`]: crate::Matcher
+#[cfg_attr(not(feature = "globset"), allow(clippy::unnecessary_wraps))]
+fn check(component: Component, value: Cow<'_, str>) -> Result<Cow<'_, str>> {
+    validate(value.as_ref())?;
+    #[cfg(feature = "globset")]
+    if let Err(source) = Glob::new(&value) {
+        let name = component.name();
+        return Err(Error::Pattern { name, source });
+    }
+    #[cfg(not(feature = "globset"))]
+    let _ = component;
+    Ok(value)
+}
+
+/// Joins patterns into a single alternation group.
+///
+/// Commas and braces outside of character classes are escaped by wrapping
+/// them in character classes, since they would otherwise be interpreted as
+/// separators or nested groups, which [`Glob`] doesn't support. Character
+/// classes are copied verbatim, as all characters inside are literals.
+fn alternate<I>(patterns: I) -> String
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut value = String::from("{");
+    for (index, pattern) in patterns.into_iter().enumerate() {
+        if index > 0 {
+            value.push(',');
+        }
+
+        // Escape each character, copying character classes verbatim, while
+        // also accounting for negation and `]` in first position
+        let mut chars = pattern.as_ref().chars().peekable();
+        while let Some(char) = chars.next() {
+            match char {
+                '[' => {
+                    value.push(char);
+                    if let Some(char) = chars.next_if(|&c| c == '!' || c == '^')
+                    {
+                        value.push(char);
+                    }
+                    if let Some(char) = chars.next_if_eq(&']') {
+                        value.push(char);
+                    }
+                    for char in chars.by_ref() {
+                        value.push(char);
+                        if char == ']' {
+                            break;
+                        }
+                    }
+                }
+                ',' | '{' | '}' => {
+                    value.push('[');
+                    value.push(char);
+                    value.push(']');
+                }
+                _ => value.push(char),
+            }
+        }
+    }
+
+    // Close alternation group and return it
+    value.push('}');
+    value
+}
@@ -0,0 +1,123 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Selector cache.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+#[cfg(feature = "globset")]
+use std::sync::{Arc, OnceLock};
+
+#[cfg(feature = "globset")]
+use globset::GlobMatcher;
+
+#[cfg(feature = "globset")]
+use super::Result;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Selector cache.
+///
+/// The cache holds the compiled globs of all components of a selector, which
+/// are compiled when the selector is matched for the first time, and shared
+/// between clones. Selectors must clear the cache when they're updated. The
+/// cache is ignored for comparison and hashing, so selectors compare equal,
+/// regardless of whether they were matched before.
+#[derive(Clone, Default)]
+pub struct Cache {
+    /// Compiled globs, in the order of components.
+    #[cfg(feature = "globset")]
+    globs: OnceLock<Arc<[Option<GlobMatcher>; 5]>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Cache {
+    /// Returns the compiled globs, compiling them with the given function if
+    /// they're not cached yet.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the error of the given function, in which case the
+    /// cache is left empty.
+    #[cfg(feature = "globset")]
+    pub fn globs<F>(&self, f: F) -> Result<&[Option<GlobMatcher>; 5]>
+    where
+        F: FnOnce() -> Result<[Option<GlobMatcher>; 5]>,
+    {
+        if let Some(globs) = self.globs.get() {
+            return Ok(globs);
+        }
+
+        // Compile globs, and if another thread was faster, use its globs
+        let globs = Arc::new(f()?);
+        Ok(self.globs.get_or_init(|| globs))
+    }
+
+    /// Clears the cache.
+    #[cfg_attr(not(feature = "globset"), allow(clippy::unused_self))]
+    #[inline]
+    pub fn clear(&mut self) {
+        #[cfg(feature = "globset")]
+        self.globs.take();
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl PartialEq for Cache {
+    /// Compares two caches for equality, which is always the case.
+    #[inline]
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Cache {}
+
+impl PartialOrd for Cache {
+    /// Compares two caches, which are always equal.
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cache {
+    /// Compares two caches, which are always equal.
+    #[inline]
+    fn cmp(&self, _: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl Hash for Cache {
+    /// Hashes the cache, which contributes nothing to the hash.
+    #[inline]
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
@@ -0,0 +1,112 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Property testing strategies for selectors.
+
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prop_oneof;
+use proptest::strategy::{BoxedStrategy, Just, Strategy};
+
+use crate::id::proptest::VALUE;
+
+use super::Selector;
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Arbitrary for Selector {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Creates a strategy for generating valid selectors.
+    ///
+    /// All components are optional, and are either literal values, which are
+    /// generated like the components of identifiers, or globs, where paths
+    /// consist of up to four segments. Selectors shrink towards absent
+    /// components and shorter paths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use proptest::prelude::*;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Ensure selectors round trip through their string representation
+    /// proptest!(|(selector: Selector)| {
+    ///     let parsed: Selector = selector.to_string().parse().unwrap();
+    ///     prop_assert_eq!(parsed.path(), selector.path());
+    ///     prop_assert_eq!(parsed, selector);
+    /// });
+    /// ```
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        (
+            option::of(glob()),
+            option::of(glob()),
+            option::of(glob()),
+            option::of(path()),
+            option::of(glob()),
+        )
+            .prop_filter_map(
+                "selector must be valid",
+                |(scheme, binding, context, path, fragment)| {
+                    let mut selector = Selector::new().ok()?;
+                    selector
+                        .set_scheme(scheme.unwrap_or_default())
+                        .and_then(|s| {
+                            s.set_binding(binding.unwrap_or_default())
+                        })
+                        .and_then(|s| {
+                            s.set_context(context.unwrap_or_default())
+                        })
+                        .and_then(|s| s.set_path(path.unwrap_or_default()))
+                        .and_then(|s| {
+                            s.set_fragment(fragment.unwrap_or_default())
+                        })
+                        .ok()?;
+                    Some(selector)
+                },
+            )
+            .boxed()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Creates a strategy for generating globs or literal values.
+fn glob() -> impl Strategy<Value = String> {
+    prop_oneof![
+        VALUE.prop_map(String::from),
+        Just(String::from("*")),
+        VALUE.prop_map(|value| format!("{value}*")),
+    ]
+}
+
+/// Creates a strategy for generating paths of one to four segments.
+fn path() -> impl Strategy<Value = String> {
+    let segment = prop_oneof![glob(), Just(String::from("**"))];
+    vec(segment, 1..=4).prop_map(|segments| segments.join("/"))
+}
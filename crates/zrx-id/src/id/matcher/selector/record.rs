@@ -0,0 +1,425 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Selector record.
+
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, result};
+
+use crate::matcher::{Error, Result};
+
+use super::{Selector, SelectorSet};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Names of fields in record form.
+const FIELDS: &[&str] = &["scheme", "binding", "context", "path", "fragment"];
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Selector record.
+///
+/// Records are the structured form of selectors, meant for exchange with other
+/// systems, e.g., web dashboards, which prefer objects over opaque strings. All
+/// fields are optional, as absent components are wildcards, and are skipped
+/// when serializing. Keeping the record in this crate ensures that field names
+/// and semantics can't drift from those of the [`Selector`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use serde::de::value::{Error as ValueError, MapDeserializer};
+/// use serde::Deserialize;
+/// use zrx_id::matcher::SelectorRecord;
+/// use zrx_id::prelude::*;
+///
+/// // Deserialize selector record from map
+/// let map = [("scheme", "file"), ("path", "**/*.md")];
+/// let record = SelectorRecord::deserialize(
+///     MapDeserializer::<_, ValueError>::new(map.into_iter()),
+/// )?;
+///
+/// // Convert record into selector
+/// let selector: Selector = record.try_into()?;
+/// assert_eq!(selector, "zrs:file:::**/*.md:".parse()?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SelectorRecord {
+    /// Scheme component.
+    pub scheme: Option<String>,
+    /// Binding component.
+    pub binding: Option<String>,
+    /// Context component.
+    pub context: Option<String>,
+    /// Path component.
+    pub path: Option<String>,
+    /// Fragment component.
+    pub fragment: Option<String>,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Visitor for selector records.
+struct SelectorRecordVisitor;
+
+/// Visitor for selector sets.
+struct SelectorSetVisitor;
+
+/// Optional field value.
+///
+/// This is equivalent to `Option<String>`, but also accepts a plain string for
+/// deserializers that don't distinguish between absent and present values.
+struct Optional(Option<String>);
+
+/// Visitor for optional field values.
+struct OptionalVisitor;
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl From<&Selector> for SelectorRecord {
+    /// Creates a selector record from a selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::matcher::SelectorRecord;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Define selectors with all components present and absent
+    /// let selectors = [
+    ///     "zrs:::::",
+    ///     "zrs:file:::**/*.md:",
+    ///     "zrs:file:master:docs:**/*.md:anchor",
+    ///     "zrs::master:::anchor",
+    /// ];
+    ///
+    /// // Ensure selectors round-trip through records
+    /// for value in selectors {
+    ///     let selector: Selector = value.parse()?;
+    ///     let record = SelectorRecord::from(&selector);
+    ///     assert_eq!(Selector::try_from(record)?, selector);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from(selector: &Selector) -> Self {
+        Self {
+            scheme: selector.scheme().map(Into::into),
+            binding: selector.binding().map(Into::into),
+            context: selector.context().map(Into::into),
+            path: selector.path().map(Into::into),
+            fragment: selector.fragment().map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<SelectorRecord> for Selector {
+    type Error = Error;
+
+    /// Attempts to create a selector from a selector record.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if a component value contains a
+    /// backslash, or [`Error::Format`], if the format is invalid.
+    fn try_from(record: SelectorRecord) -> Result<Self> {
+        let mut selector = Selector::new()?;
+        if let Some(scheme) = record.scheme {
+            selector.set_scheme(scheme)?;
+        }
+        if let Some(binding) = record.binding {
+            selector.set_binding(binding)?;
+        }
+        if let Some(context) = record.context {
+            selector.set_context(context)?;
+        }
+        if let Some(path) = record.path {
+            selector.set_path(path)?;
+        }
+        if let Some(fragment) = record.fragment {
+            selector.set_fragment(fragment)?;
+        }
+        Ok(selector)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Serialize for SelectorRecord {
+    /// Serializes the selector record as a struct, skipping absent fields.
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let fields = [
+            &self.scheme,
+            &self.binding,
+            &self.context,
+            &self.path,
+            &self.fragment,
+        ];
+        let len = fields.iter().filter(|value| value.is_some()).count();
+        let mut state = serializer.serialize_struct("SelectorRecord", len)?;
+        for (name, value) in FIELDS.iter().zip(fields) {
+            match value {
+                Some(value) => state.serialize_field(name, value)?,
+                None => state.skip_field(name)?,
+            }
+        }
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SelectorRecord {
+    /// Deserializes a selector record from a map.
+    ///
+    /// All fields are optional, and `null` values are treated as absent, but
+    /// unknown and duplicate fields are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use serde::de::value::{Error as ValueError, MapDeserializer};
+    /// use serde::Deserialize;
+    /// use zrx_id::matcher::SelectorRecord;
+    ///
+    /// // Deserialize selector record from map, unknown key
+    /// let map = [("scheme", "file"), ("file", "a.md")];
+    /// let res = SelectorRecord::deserialize(
+    ///     MapDeserializer::<_, ValueError>::new(map.into_iter()),
+    /// );
+    /// let err = res.unwrap_err().to_string();
+    /// assert!(err.starts_with("unknown field `file`"));
+    ///
+    /// // Deserialize selector record from map, duplicate key
+    /// let map = [("scheme", "file"), ("scheme", "git")];
+    /// let res = SelectorRecord::deserialize(
+    ///     MapDeserializer::<_, ValueError>::new(map.into_iter()),
+    /// );
+    /// assert_eq!(res.unwrap_err().to_string(), "duplicate field `scheme`");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn deserialize<D>(deserializer: D) -> result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "SelectorRecord",
+            FIELDS,
+            SelectorRecordVisitor,
+        )
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Serialize for SelectorSet {
+    /// Serializes the selector set as an array of selector records.
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.len()))?;
+        for selector in self {
+            state.serialize_element(&SelectorRecord::from(selector))?;
+        }
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SelectorSet {
+    /// Deserializes a selector set from an array of selector records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use serde::de::value::{Error as ValueError, SeqDeserializer};
+    /// use serde::Deserialize;
+    /// use std::collections::BTreeMap;
+    /// use zrx_id::matcher::SelectorSet;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Deserialize selector set from array of maps
+    /// let records = vec![
+    ///     BTreeMap::from([("path", "**/*.md")]),
+    ///     BTreeMap::from([("scheme", "file"), ("fragment", "anchor")]),
+    /// ];
+    /// let selectors = SelectorSet::deserialize(
+    ///     SeqDeserializer::<_, ValueError>::new(records.into_iter()),
+    /// )?;
+    ///
+    /// // Ensure selectors are kept in order
+    /// let expected: SelectorSet = [
+    ///     "zrs::::**/*.md:".parse::<Selector>()?,
+    ///     "zrs:file::::anchor".parse::<Selector>()?,
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    /// assert_eq!(selectors, expected);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn deserialize<D>(deserializer: D) -> result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SelectorSetVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Optional {
+    /// Deserializes an optional field value.
+    fn deserialize<D>(deserializer: D) -> result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_option(OptionalVisitor)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<'de> Visitor<'de> for SelectorRecordVisitor {
+    type Value = SelectorRecord;
+
+    /// Formats the expected value.
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of selector components")
+    }
+
+    /// Visits a map, creating a selector record from its fields.
+    fn visit_map<A>(self, mut map: A) -> result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut record = SelectorRecord::default();
+        let mut seen = [false; 5];
+        while let Some(key) = map.next_key::<String>()? {
+            let Some(index) = FIELDS.iter().position(|name| *name == key)
+            else {
+                return Err(de::Error::unknown_field(&key, FIELDS));
+            };
+
+            // Ensure each field is only given once
+            if seen[index] {
+                return Err(de::Error::duplicate_field(FIELDS[index]));
+            }
+            seen[index] = true;
+
+            // Assign value to field, treating `null` values as absent
+            let Optional(value) = map.next_value()?;
+            *match index {
+                0 => &mut record.scheme,
+                1 => &mut record.binding,
+                2 => &mut record.context,
+                3 => &mut record.path,
+                _ => &mut record.fragment,
+            } = value;
+        }
+
+        // No errors occurred
+        Ok(record)
+    }
+}
+
+impl<'de> Visitor<'de> for SelectorSetVisitor {
+    type Value = SelectorSet;
+
+    /// Formats the expected value.
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of selector records")
+    }
+
+    /// Visits a sequence, creating a selector set from its records.
+    fn visit_seq<A>(self, mut seq: A) -> result::Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut selectors = SelectorSet::new();
+        while let Some(record) = seq.next_element::<SelectorRecord>()? {
+            selectors.push(record.try_into().map_err(de::Error::custom)?);
+        }
+        Ok(selectors)
+    }
+}
+
+impl<'de> Visitor<'de> for OptionalVisitor {
+    type Value = Optional;
+
+    /// Formats the expected value.
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an optional string")
+    }
+
+    /// Visits an absent value.
+    fn visit_none<E>(self) -> result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Optional(None))
+    }
+
+    /// Visits a unit value, which is treated as absent.
+    fn visit_unit<E>(self) -> result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Optional(None))
+    }
+
+    /// Visits a present value.
+    fn visit_some<D>(
+        self, deserializer: D,
+    ) -> result::Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(|value| Optional(Some(value)))
+    }
+
+    /// Visits a plain string.
+    fn visit_str<E>(self, value: &str) -> result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Optional(Some(value.to_owned())))
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Serialization and deserialization of selectors.
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+use super::Selector;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Visitor for selectors.
+struct SelectorVisitor;
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Serialize for Selector {
+    /// Serializes the selector as its canonical string.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.format.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Selector {
+    /// Deserializes a selector from a string.
+    ///
+    /// Selectors are accepted in their canonical string form, and validated
+    /// just like with [`Selector::from_str`][]. Borrowed strings are parsed
+    /// without an intermediate allocation. Use [`SelectorRecord`][] for the
+    /// structured form.
+    ///
+    /// [`Selector::from_str`]: std::str::FromStr::from_str
+    /// [`SelectorRecord`]: crate::matcher::SelectorRecord
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use serde::de::value::{BorrowedStrDeserializer, Error as ValueError};
+    /// use serde::de::Deserialize;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Deserialize selector from string
+    /// let value =
+    ///     BorrowedStrDeserializer::<ValueError>::new("zrs::::**/*.md:");
+    /// let selector = Selector::deserialize(value)?;
+    /// assert_eq!(selector, "zrs::::**/*.md:".parse()?);
+    ///
+    /// // Deserialize selector from string, invalid prefix
+    /// let value =
+    ///     BorrowedStrDeserializer::<ValueError>::new("zri::::**/*.md:");
+    /// let res = Selector::deserialize(value);
+    /// assert_eq!(res.unwrap_err().to_string(), "invalid prefix");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(SelectorVisitor)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Visitor<'_> for SelectorVisitor {
+    type Value = Selector;
+
+    /// Formats the expected value.
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a selector string")
+    }
+
+    /// Visits a string, parsing it into a selector.
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value.parse().map_err(E::custom)
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Selector set.
+
+use std::slice::Iter;
+use std::vec::IntoIter;
+
+use super::Selector;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Selector set.
+///
+/// Selector sets are ordered collections of selectors, e.g., for the active
+/// subscriptions of a system. Selectors are kept in insertion order, so their
+/// indices correspond with the indices reported by a [`Matcher`][] that is
+/// built from the set. When the `serde` feature is enabled, selector sets are
+/// serialized as an array of [`SelectorRecord`][] values.
+///
+/// [`Matcher`]: crate::Matcher
+/// [`SelectorRecord`]: crate::matcher::SelectorRecord
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::matcher::SelectorSet;
+/// use zrx_id::prelude::*;
+///
+/// // Create selector set
+/// let mut selectors = SelectorSet::new();
+/// selectors.push("zrs::::**/*.md:".parse()?);
+/// selectors.push("zrs::::**/*.rs:".parse()?);
+/// assert_eq!(selectors.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SelectorSet {
+    /// Selectors.
+    selectors: Vec<Selector>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl SelectorSet {
+    /// Creates a selector set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a selector to the selector set.
+    #[inline]
+    pub fn push(&mut self, selector: Selector) {
+        self.selectors.push(selector);
+    }
+
+    /// Creates an iterator over the selector set.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, Selector> {
+        self.selectors.iter()
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+impl SelectorSet {
+    /// Returns the number of selectors.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.selectors.len()
+    }
+
+    /// Returns whether there are any selectors.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.selectors.is_empty()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl From<Vec<Selector>> for SelectorSet {
+    /// Creates a selector set from a vector of selectors.
+    #[inline]
+    fn from(selectors: Vec<Selector>) -> Self {
+        Self { selectors }
+    }
+}
+
+impl FromIterator<Selector> for SelectorSet {
+    /// Creates a selector set from an iterator.
+    #[inline]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = Selector>,
+    {
+        Self {
+            selectors: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for SelectorSet {
+    type Item = Selector;
+    type IntoIter = IntoIter<Selector>;
+
+    /// Creates a consuming iterator over the selector set.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.selectors.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a SelectorSet {
+    type Item = &'a Selector;
+    type IntoIter = Iter<'a, Selector>;
+
+    /// Creates an iterator over the selector set.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
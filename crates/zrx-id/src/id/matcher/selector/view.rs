@@ -0,0 +1,126 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Selector view.
+
+use std::borrow::Cow;
+
+use crate::format::Format;
+use crate::path::validate;
+
+use super::cache::Cache;
+use super::{Result, Selector, ToSelector};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Selector view.
+///
+/// Views borrow all components of a [`Selector`] at once, which are extracted
+/// from the underlying formatted string in a single pass. Absent components
+/// are wildcards. Views can be converted back into selectors with
+/// [`ToSelector::to_selector`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::matcher::SelectorView;
+/// use zrx_id::prelude::*;
+///
+/// // Create selector and obtain view
+/// let selector: Selector = "zrs:file:::**/*.md:".parse()?;
+/// let view = selector.view();
+/// assert_eq!(view.scheme.as_deref(), Some("file"));
+/// assert_eq!(view.path.as_deref(), Some("**/*.md"));
+/// assert_eq!(view.context, None);
+///
+/// // Convert view back into selector
+/// assert_eq!(view.to_selector()?.as_ref(), &selector);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SelectorView<'a> {
+    /// Scheme component, if any.
+    pub scheme: Option<Cow<'a, str>>,
+    /// Binding component, if any.
+    pub binding: Option<Cow<'a, str>>,
+    /// Context component, if any.
+    pub context: Option<Cow<'a, str>>,
+    /// Path component, if any.
+    pub path: Option<Cow<'a, str>>,
+    /// Fragment component, if any.
+    pub fragment: Option<Cow<'a, str>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Selector {
+    /// Returns a view of all components.
+    #[inline]
+    #[must_use]
+    pub fn view(&self) -> SelectorView<'_> {
+        SelectorView {
+            scheme: self.scheme(),
+            binding: self.binding(),
+            context: self.context(),
+            path: self.path(),
+            fragment: self.fragment(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl ToSelector for SelectorView<'_> {
+    /// Creates a selector from a view.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`][], if a component value contains a
+    /// backslash, or [`Error::Format`][], if the format is invalid.
+    ///
+    /// [`Error::Format`]: crate::matcher::Error::Format
+    /// [`Error::Path`]: crate::matcher::Error::Path
+    fn to_selector(&self) -> Result<Cow<'_, Selector>> {
+        let parts = [
+            "zrs",
+            validate(self.scheme.as_deref().unwrap_or_default())?,
+            validate(self.binding.as_deref().unwrap_or_default())?,
+            validate(self.context.as_deref().unwrap_or_default())?,
+            validate(self.path.as_deref().unwrap_or_default())?,
+            validate(self.fragment.as_deref().unwrap_or_default())?,
+        ];
+        Ok(Cow::Owned(Selector {
+            format: Format::from_parts(parts)?,
+            case_insensitive: false,
+            cache: Cache::default(),
+        }))
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Identifier parts.
+
+use std::borrow::Cow;
+
+use super::view::IdView;
+use super::{Error, Id, Result, ToId};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Identifier parts.
+///
+/// Parts own all decoded components of an [`Id`], which makes them suitable
+/// for systems that expect discrete fields, e.g., database rows. Other than
+/// an [`IdView`], parts don't borrow from the identifier, and can be converted
+/// back into identifiers with [`Id::try_from`], applying the same validation.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::prelude::*;
+/// use zrx_id::IdParts;
+///
+/// // Create identifier and destructure it into parts
+/// let id = Id::new("file", "docs", "a:b.md")?.with_fragment("c%d")?;
+/// let parts = id.to_parts();
+/// assert_eq!(parts.path, "a:b.md");
+/// assert_eq!(parts.binding, None);
+/// assert_eq!(parts.fragment.as_deref(), Some("c%d"));
+///
+/// // Convert parts back into identifier
+/// assert_eq!(Id::try_from(parts)?, id);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IdParts {
+    /// Scheme component.
+    pub scheme: String,
+    /// Binding component, if any.
+    pub binding: Option<String>,
+    /// Context component.
+    pub context: String,
+    /// Path component.
+    pub path: String,
+    /// Fragment component, if any.
+    pub fragment: Option<String>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Id {
+    /// Destructures the identifier into its parts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and destructure it into parts
+    /// let id: Id = "zri:git:master:docs:a%3Ab.md:top".parse()?;
+    /// let parts = id.into_parts();
+    /// assert_eq!(parts.binding.as_deref(), Some("master"));
+    /// assert_eq!(parts.path, "a:b.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn into_parts(self) -> IdParts {
+        self.to_parts()
+    }
+
+    /// Returns the parts of the identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain parts
+    /// let id: Id = "zri:git:master:docs:a%3Ab.md:top".parse()?;
+    /// let parts = id.to_parts();
+    /// assert_eq!(parts.fragment.as_deref(), Some("top"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn to_parts(&self) -> IdParts {
+        IdParts {
+            scheme: self.scheme().into_owned(),
+            binding: self.binding().map(Into::into),
+            context: self.context().into_owned(),
+            path: self.path().into_owned(),
+            fragment: self.fragment().map(Into::into),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl TryFrom<IdParts> for Id {
+    type Error = Error;
+
+    /// Attempts to create an identifier from parts.
+    ///
+    /// Empty optional components are treated like absent ones.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if a component value contains a
+    /// backslash, [`Error::Component`], if a required component is empty, or
+    /// [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdParts;
+    ///
+    /// // Create identifier from parts
+    /// let parts = IdParts {
+    ///     scheme: "git".into(),
+    ///     binding: Some("v1:2".into()),
+    ///     context: "docs".into(),
+    ///     path: "caf\u{e9}.md".into(),
+    ///     fragment: None,
+    /// };
+    /// let id = Id::try_from(parts.clone())?;
+    /// assert_eq!(id.to_string(), "zri:git:v1%3A2:docs:caf%C3%A9.md:");
+    ///
+    /// // Ensure parts round trip, including encoded components
+    /// assert_eq!(id.clone().into_parts(), parts);
+    /// assert_eq!(Id::try_from(id.to_parts())?, id);
+    ///
+    /// // Create identifier from parts without path
+    /// let res = Id::try_from(IdParts { path: String::new(), ..parts });
+    /// assert!(res.is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_from(parts: IdParts) -> Result<Self> {
+        let view = IdView {
+            scheme: parts.scheme.into(),
+            binding: parts.binding.map(Into::into),
+            context: parts.context.into(),
+            path: parts.path.into(),
+            fragment: parts.fragment.map(Into::into),
+        };
+        view.to_id().map(Cow::into_owned)
+    }
+}
@@ -22,13 +22,56 @@
 
 //! Path utilities.
 
-use std::path::{Component, PathBuf};
+use percent_encoding::{percent_encode, AsciiSet, CONTROLS};
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
 
 use super::Id;
 
 mod error;
+mod mount;
+mod options;
+mod resolve;
+mod validation;
 
 pub use error::{Error, Result};
+pub use mount::MountMap;
+pub use options::Options;
+pub use resolve::Resolve;
+pub use validation::Validation;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Characters that are percent-encoded in fragments of URLs.
+const FRAGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'`')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// Characters that are percent-encoded in path segments of URLs.
+const SEGMENT: &AsciiSet = &FRAGMENT.add(b'/').add(b':').add(b'?');
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Portable display of a path, see [`display_portable`].
+struct Portable<'a>(&'a Path);
 
 // ----------------------------------------------------------------------------
 // Trait implementations
@@ -44,13 +87,17 @@ impl TryFrom<&Id> for PathBuf {
     /// i.e., it will use backslashes on Windows and forward slashes on Unix.
     /// Thus, paths can't be directly used as URLs.
     ///
+    /// The `binding` component is ignored, as it can only be interpreted by a
+    /// resolver, see [`resolve`] for creating a path that is joined onto the
+    /// root that the `scheme` and `binding` components resolve to.
+    ///
     /// # Examples
     ///
     /// ```
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
     /// use std::path::PathBuf;
-    /// use zrx_id::Id;
+    /// use zrx_id::prelude::*;
     ///
     /// // Create formatted string from string
     /// let id: Id = "zri:file::docs:index.md:".parse()?;
@@ -59,63 +106,794 @@ impl TryFrom<&Id> for PathBuf {
     /// # Ok(())
     /// # }
     /// ```
+    #[inline]
+    fn try_from(id: &Id) -> Result<Self> {
+        try_into_path_with(id, Options::default())
+    }
+}
+
+#[cfg(feature = "camino")]
+impl TryFrom<&Id> for camino::Utf8PathBuf {
+    type Error = Error;
+
+    /// Attempts to create a relative UTF-8 path from an identifier.
+    ///
+    /// This applies the same rules as the conversion into a [`PathBuf`], but
+    /// retains the guarantee that the path is valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use camino::{Utf8Path, Utf8PathBuf};
+    /// use zrx_id::{path, prelude::*};
+    ///
+    /// // Create UTF-8 path from identifier
+    /// let id: Id = "zri:file::docs:index.md:".parse()?;
+    /// let path = Utf8PathBuf::try_from(&id)?;
+    /// assert_eq!(path, Utf8Path::new("docs").join("index.md"));
+    ///
+    /// // Ensure path traversal is rejected
+    /// let id: Id = "zri:file::docs:../index.md:".parse()?;
+    /// let res = Utf8PathBuf::try_from(&id);
+    /// assert!(matches!(res, Err(path::Error::ParentDir)));
+    /// # Ok(())
+    /// # }
+    /// ```
     fn try_from(id: &Id) -> Result<Self> {
-        let mut stack = Vec::new();
-
-        // Normalize path and analyze its components - since Windows supports
-        // forward slashes and backslashes, we do not need to normalize it
-        let path =
-            PathBuf::from(id.context().as_ref()).join(id.path().as_ref());
-        for component in path.components() {
-            match component {
-                Component::Normal(part) => stack.push(part),
-                Component::CurDir => continue,
-
-                // Disallow path traversal for security reasons, which means
-                // `..` is not supported in paths, as it would allow to break
-                // out of the context. If we find a use case where we need `..`,
-                // we can lift this requirement in the future.
-                Component::ParentDir => {
+        let path = PathBuf::try_from(id)?;
+
+        // The path is guaranteed to be valid UTF-8, as it was created from the
+        // components of an identifier, so this can only fail in theory
+        camino::Utf8PathBuf::from_path_buf(path).map_err(Error::NonUtf8)
+    }
+}
+
+impl fmt::Display for Portable<'_> {
+    /// Formats the path for display, using forward slashes.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self.0.to_string_lossy();
+        for (index, part) in value.split('\\').enumerate() {
+            if index > 0 {
+                f.write_char('/')?;
+            }
+            f.write_str(part)?;
+        }
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Attempts to create a relative path from an identifier with options.
+///
+/// This is the variant of the conversion into a [`PathBuf`] that accepts
+/// [`Options`], which, e.g., allow to resolve `..` segments. With the default
+/// options, it behaves exactly like the conversion.
+///
+/// # Errors
+///
+/// If the relative path is absolute, [`Error::RootDir`] is returned, or
+/// [`Error::WindowsPrefix`], if it starts with a Windows prefix. If it
+/// contains `..`, and resolution is not allowed, or would break out of the
+/// root, [`Error::ParentDir`] is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::path::PathBuf;
+/// use zrx_id::path::{self, try_into_path_with, Options};
+/// use zrx_id::prelude::*;
+///
+/// // Create options allowing `..`
+/// let options = Options { allow_parent: true };
+///
+/// // Create identifiers and resolve `..` segments
+/// let id = Id::new("file", "", "a/../b")?;
+/// assert_eq!(try_into_path_with(&id, options)?, PathBuf::from("b"));
+/// let id = Id::new("file", "docs/sub", "../assets/logo.png")?;
+/// assert_eq!(
+///     try_into_path_with(&id, options)?,
+///     PathBuf::from("docs").join("assets").join("logo.png"),
+/// );
+///
+/// // Resolve interleaved `.` and `..` segments
+/// let id = Id::new("file", "docs", "./a/./../b/c/.././d.md")?;
+/// assert_eq!(
+///     try_into_path_with(&id, options)?,
+///     PathBuf::from("docs").join("b").join("d.md"),
+/// );
+///
+/// // Ensure traversal can't break out of the root
+/// let id = Id::new("file", "", "../a")?;
+/// let res = try_into_path_with(&id, options);
+/// assert!(matches!(res, Err(path::Error::ParentDir)));
+/// let id = Id::new("file", "docs", "a/../../../b")?;
+/// let res = try_into_path_with(&id, options);
+/// assert!(matches!(res, Err(path::Error::ParentDir)));
+///
+/// // Ensure `..` is rejected by default
+/// let id = Id::new("file", "", "a/../b")?;
+/// let res = try_into_path_with(&id, Options::default());
+/// assert!(matches!(res, Err(path::Error::ParentDir)));
+/// # Ok(())
+/// # }
+/// ```
+pub fn try_into_path_with(id: &Id, options: Options) -> Result<PathBuf> {
+    let mut stack = Vec::new();
+
+    // Normalize path and analyze its components - since Windows supports
+    // forward slashes and backslashes, we do not need to normalize it
+    let path = PathBuf::from(id.context().as_ref()).join(id.path().as_ref());
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => stack.push(part),
+            Component::CurDir => {}
+
+            // Disallow path traversal for security reasons by default, which
+            // means `..` is not supported in paths, as it would allow to break
+            // out of the context. If resolution is allowed, `..` removes the
+            // preceding segment, but must never break out of the root.
+            Component::ParentDir => {
+                if !options.allow_parent || stack.pop().is_none() {
                     return Err(Error::ParentDir);
                 }
+            }
 
-                // Disallow absolute paths, as we need to ensure that paths are
-                // always portable. Note that providers can use the binding to
-                // resolve paths relative to different mount points, e.g., to
-                // allow for plugins to ship with their own artifacts.
-                Component::RootDir | Component::Prefix(_) => {
-                    return Err(Error::RootDir);
+            // Disallow absolute paths, as we need to ensure that paths are
+            // always portable. Note that providers can use the binding to
+            // resolve paths relative to different mount points, e.g., to
+            // allow for plugins to ship with their own artifacts.
+            Component::RootDir => {
+                return Err(Error::RootDir);
+            }
+
+            // Disallow Windows prefixes for the same reason, but report them
+            // explicitly, as they are a common source of confusion
+            Component::Prefix(prefix) => {
+                let prefix = prefix.as_os_str().to_string_lossy();
+                return Err(Error::WindowsPrefix(prefix.into_owned()));
+            }
+        }
+    }
+
+    // Collect path components into a path
+    Ok(stack.iter().collect())
+}
+
+/// Returns a portable display of a path, which always uses forward slashes.
+///
+/// This is intended for paths derived from identifiers, e.g., through the
+/// conversion into a [`PathBuf`], which uses backslashes on Windows, so log
+/// messages render identically on all platforms. Since identifiers can never
+/// contain backslashes, all backslashes are rendered as forward slashes, which
+/// preserves roots and Windows prefixes, e.g., `C:\` is rendered as `C:/`.
+/// Paths that are not valid UTF-8 are rendered lossily.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use zrx_id::path::display_portable;
+///
+/// // Render Windows-style paths with forward slashes
+/// let path = Path::new(r"docs\guide\index.md");
+/// assert_eq!(display_portable(path).to_string(), "docs/guide/index.md");
+/// let path = Path::new(r"C:\docs\index.md");
+/// assert_eq!(display_portable(path).to_string(), "C:/docs/index.md");
+/// let path = Path::new(r"\\server\share\index.md");
+/// assert_eq!(display_portable(path).to_string(), "//server/share/index.md");
+///
+/// // Render Unix-style paths unchanged
+/// let path = Path::new("/docs/index.md");
+/// assert_eq!(display_portable(path).to_string(), "/docs/index.md");
+/// ```
+#[must_use]
+pub fn display_portable(path: &Path) -> impl fmt::Display + '_ {
+    Portable(path)
+}
+
+/// Attempts to create an absolute path from an identifier.
+///
+/// The `binding` component of the identifier is resolved through the given
+/// [`MountMap`], and the relative path, created from the `context` and `path`
+/// components, is joined onto the resulting mount point. Note that the same
+/// checks as for the relative path conversion apply, so the relative path can
+/// never break out of the mount point.
+///
+/// # Errors
+///
+/// If the binding isn't mounted, [`Error::Unmounted`] is returned. If the
+/// relative path is absolute or contains `..`, [`Error::RootDir`] or
+/// [`Error::ParentDir`] is returned, respectively.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::path::PathBuf;
+/// use zrx_id::path::{to_absolute, MountMap};
+/// use zrx_id::prelude::*;
+///
+/// // Create mount map and add mount point
+/// let mut mounts = MountMap::new();
+/// mounts.insert("c", r"C:\");
+///
+/// // Create identifier and resolve absolute path
+/// let mut id = Id::new("file", "docs", "index.md")?;
+/// id.set_binding("c")?;
+/// assert_eq!(
+///     to_absolute(&id, &mounts)?,
+///     PathBuf::from(r"C:\").join("docs").join("index.md"),
+/// );
+///
+/// // Unmounted bindings are an error
+/// id.set_binding("d")?;
+/// assert!(to_absolute(&id, &mounts).is_err());
+///
+/// // Identifiers without binding use the empty binding
+/// mounts.insert("", r"\\server\share");
+/// id.set_binding("")?;
+/// assert_eq!(
+///     to_absolute(&id, &mounts)?,
+///     PathBuf::from(r"\\server\share").join("docs").join("index.md"),
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_absolute(id: &Id, mounts: &MountMap) -> Result<PathBuf> {
+    let binding = id.binding().unwrap_or_default();
+    let Some(root) = mounts.get(&binding) else {
+        return Err(Error::Unmounted(binding.into_owned()));
+    };
+
+    // Create relative path and join it onto the mount point
+    PathBuf::try_from(id).map(|path| root.join(path))
+}
+
+/// Attempts to create a resolved path from an identifier.
+///
+/// The `scheme` and `binding` components of the identifier are resolved to a
+/// root path through the given [`Resolve`] implementation, and the relative
+/// path, created from the `context` and `path` components, is joined onto it.
+/// The checks for absolute paths and path traversal only apply to the relative
+/// path, not to the root, so the relative path can never break out of it.
+///
+/// # Errors
+///
+/// If the resolver returns no root, [`Error::Unresolved`] is returned. If the
+/// relative path is absolute or contains `..`, [`Error::RootDir`] or
+/// [`Error::ParentDir`] is returned, respectively.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::path::PathBuf;
+/// use zrx_id::path::{resolve, Resolve};
+/// use zrx_id::prelude::*;
+///
+/// // Create resolver mapping branches to checkout directories
+/// struct Checkouts;
+/// impl Resolve for Checkouts {
+///     fn root(&self, scheme: &str, binding: Option<&str>) -> Option<PathBuf> {
+///         match (scheme, binding) {
+///             ("git", Some(branch)) => {
+///                 Some(PathBuf::from("/checkouts").join(branch))
+///             }
+///             ("file", None) => Some(PathBuf::from("/project")),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// // Create identifiers and resolve paths
+/// let mut id = Id::new("git", "docs", "index.md")?;
+/// id.set_binding("main")?;
+/// assert_eq!(
+///     resolve(&id, &Checkouts)?,
+///     PathBuf::from("/checkouts/main").join("docs").join("index.md"),
+/// );
+/// id.set_binding("next")?;
+/// assert_eq!(
+///     resolve(&id, &Checkouts)?,
+///     PathBuf::from("/checkouts/next").join("docs").join("index.md"),
+/// );
+///
+/// // Identifiers without binding use their own root
+/// let id = Id::new("file", "docs", "index.md")?;
+/// assert_eq!(
+///     resolve(&id, &Checkouts)?,
+///     PathBuf::from("/project").join("docs").join("index.md"),
+/// );
+///
+/// // Unresolved roots and path traversal are errors
+/// let id = Id::new("git", "docs", "index.md")?;
+/// assert!(resolve(&id, &Checkouts).is_err());
+/// let mut id = Id::new("git", "docs", "../index.md")?;
+/// id.set_binding("main")?;
+/// assert!(resolve(&id, &Checkouts).is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn resolve<R>(id: &Id, resolver: &R) -> Result<PathBuf>
+where
+    R: Resolve,
+{
+    let scheme = id.scheme();
+    let binding = id.binding();
+    let Some(root) = resolver.root(&scheme, binding.as_deref()) else {
+        return Err(Error::Unresolved {
+            scheme: scheme.into_owned(),
+            binding: binding.map(Cow::into_owned),
+        });
+    };
+
+    // Create relative path and join it onto the root
+    PathBuf::try_from(id).map(|path| root.join(path))
+}
+
+/// Normalizes a file system path into the value of a `path` component.
+///
+/// Both `/` and `\` are treated as separators on all platforms, so paths are
+/// normalized consistently, regardless of where they originate. Empty and `.`
+/// segments are dropped, and if the path starts with the segments of the given
+/// context, those are stripped. The same rules as for the conversion into a
+/// [`PathBuf`] apply, so absolute paths, including Windows prefixes, and `..`
+/// are rejected. Windows prefixes are detected on all platforms.
+///
+/// # Errors
+///
+/// If the path starts with a Windows prefix, [`Error::WindowsPrefix`] is
+/// returned. If the path is absolute or contains `..`, [`Error::RootDir`] or
+/// [`Error::ParentDir`] is returned, respectively.
+pub(crate) fn normalize(value: &str, context: &str) -> Result<String> {
+    if let Some(prefix) = windows_prefix(value) {
+        return Err(Error::WindowsPrefix(prefix.to_owned()));
+    }
+
+    // Split path into segments, rejecting absolute paths, which start with a
+    // separator, and path traversal
+    let mut segments = Vec::new();
+    for (index, segment) in value.split(['/', '\\']).enumerate() {
+        if index == 0 && segment.is_empty() && !value.is_empty() {
+            return Err(Error::RootDir);
+        }
+        match segment {
+            "" | "." => {}
+            ".." => return Err(Error::ParentDir),
+            _ => segments.push(segment),
+        }
+    }
+
+    // Strip context, if the path starts with all of its segments
+    let prefix = context
+        .split('/')
+        .filter(|segment| !matches!(*segment, "" | "."))
+        .collect::<Vec<_>>();
+    if !prefix.is_empty() && segments.starts_with(&prefix) {
+        segments.drain(..prefix.len());
+    }
+
+    // Join segments with forward slashes
+    Ok(segments.join("/"))
+}
+
+/// Normalizes the value of a `context` or `path` component.
+///
+/// Duplicate slashes are collapsed, and empty and `.` segments are dropped,
+/// including trailing slashes, so that values referring to the same resource
+/// are identical. The same rules as for the conversion into a [`PathBuf`]
+/// apply, so absolute paths and `..` are rejected, instead of resolved.
+///
+/// # Errors
+///
+/// In addition to the errors returned by [`validate`], if the value is absolute
+/// or contains `..`, [`Error::RootDir`] or [`Error::ParentDir`] is returned,
+/// respectively.
+pub(crate) fn canonicalize(value: &[u8]) -> Result<Vec<u8>> {
+    let value = validate(value)?;
+    if value.starts_with(b"/") {
+        return Err(Error::RootDir);
+    }
+
+    // Join segments with single forward slashes, rejecting path traversal
+    let mut canonical = Vec::with_capacity(value.len());
+    for segment in value.split(|&byte| byte == b'/') {
+        match segment {
+            b"" | b"." => {}
+            b".." => return Err(Error::ParentDir),
+            _ => {
+                if !canonical.is_empty() {
+                    canonical.push(b'/');
                 }
+                canonical.extend_from_slice(segment);
             }
         }
+    }
+    Ok(canonical)
+}
+
+/// Strips the Windows prefix and root from a path, if any.
+///
+/// Drive letters like `C:`, UNC prefixes like `\\server\share`, as well as
+/// verbatim and device prefixes like `\\?\C:` or `\\.\COM1` are detected on
+/// all platforms, treating both `/` and `\` as separators. The prefix and all
+/// leading separators following it are stripped, so the remaining path can be
+/// passed to [`Id::from_path`] after deciding on a context root. Paths that
+/// are not valid UTF-8 are returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use zrx_id::path::strip_windows_prefix;
+///
+/// // Strip Windows prefixes from paths
+/// for value in [
+///     r"C:\docs\index.md",
+///     r"c:docs\index.md",
+///     r"\\server\share\docs\index.md",
+///     r"\\?\C:\docs\index.md",
+///     r"\\?\UNC\server\share\docs\index.md",
+///     r"\\.\device\docs\index.md",
+///     r"\docs\index.md",
+/// ] {
+///     let path = strip_windows_prefix(Path::new(value));
+///     assert_eq!(path, Path::new(r"docs\index.md"), "{value}");
+/// }
+///
+/// // Relative paths are returned unchanged
+/// let path = strip_windows_prefix(Path::new("docs/index.md"));
+/// assert_eq!(path, Path::new("docs/index.md"));
+/// ```
+#[must_use]
+pub fn strip_windows_prefix(path: &Path) -> &Path {
+    let Some(value) = path.to_str() else {
+        return path;
+    };
+
+    // Strip prefix, if any, and all separators following it
+    let len = windows_prefix(value).map_or(0, str::len);
+    Path::new(value[len..].trim_start_matches(['/', '\\']))
+}
 
-        // Collect path components into a path
-        Ok(stack.iter().collect())
+/// Returns the Windows prefix of a path, if any.
+///
+/// This mirrors the prefixes recognized by [`Path::components`] on Windows,
+/// i.e., drive letters, UNC prefixes, as well as verbatim and device prefixes,
+/// but treats both `/` and `\` as separators on all platforms.
+fn windows_prefix(value: &str) -> Option<&str> {
+    if is_drive(value) {
+        return Some(&value[..2]);
     }
+
+    // All other prefixes start with two separators, followed by a server and
+    // share for UNC prefixes, or a marker for verbatim and device prefixes
+    let rest = value.strip_prefix(['/', '\\'])?.strip_prefix(['/', '\\'])?;
+    let mut segments = rest.split(['/', '\\']);
+    let count = match segments.next()? {
+        "" => return None,
+        "?" | "." => match segments.next() {
+            Some(segment) if segment.eq_ignore_ascii_case("unc") => 4,
+            Some(_) => 2,
+            None => 1,
+        },
+        _ => 2,
+    };
+
+    // Compute length of prefix from the lengths of its segments
+    let len = rest
+        .split(['/', '\\'])
+        .take(count)
+        .map(|segment| segment.len() + 1)
+        .sum::<usize>();
+    Some(&value[..value.len().min(len + 1)])
 }
 
-// ----------------------------------------------------------------------------
-// Functions
-// ----------------------------------------------------------------------------
+/// Returns whether the given segment starts with a Windows drive letter.
+fn is_drive(segment: &str) -> bool {
+    match segment.as_bytes() {
+        [letter, b':', ..] => letter.is_ascii_alphabetic(),
+        _ => false,
+    }
+}
+
+/// Joins a segment onto a path, separated by exactly one `/`.
+///
+/// Trailing slashes of the path are collapsed into a single separator, while
+/// the segment is appended as is, so trailing slashes of the segment are kept.
+/// If the path is empty, the segment becomes the path, and if the segment is
+/// empty or `.`, the path is returned unchanged.
+///
+/// # Errors
+///
+/// If the segment contains a backslash, [`Error::Backslash`] is returned, and
+/// if it is absolute, i.e., starts with a `/`, [`Error::RootDir`].
+pub(crate) fn join(path: &str, segment: &str) -> Result<String> {
+    let segment = validate(segment)?;
+    if segment.starts_with('/') {
+        return Err(Error::RootDir);
+    }
+
+    // Join path and segment, unless one of them is empty
+    Ok(match (path.trim_end_matches('/'), segment) {
+        (_, "" | ".") => path.to_owned(),
+        ("", _) if path.is_empty() => segment.to_owned(),
+        (path, _) => format!("{path}/{segment}"),
+    })
+}
+
+/// Returns the parent of a path, if any.
+///
+/// Trailing slashes are ignored, so the parent of `a/b/` is `a`. If the path
+/// consists of a single segment, there's no parent, and [`None`] is returned.
+pub(crate) fn parent(path: &str) -> Option<&str> {
+    let path = path.trim_end_matches('/');
+    path.rfind('/')
+        .map(|index| path[..index].trim_end_matches('/'))
+        .filter(|parent| !parent.is_empty())
+}
+
+/// Returns the final segment of a path, if any.
+///
+/// This mirrors [`Path::file_name`], so trailing slashes and `.` segments are
+/// ignored, and [`None`] is returned if the final segment is `..`.
+pub(crate) fn file_name(path: &str) -> Option<&str> {
+    file_name_range(path).map(|range| &path[range])
+}
+
+/// Returns the range of the final segment of a path, if any.
+fn file_name_range(path: &str) -> Option<Range<usize>> {
+    let mut end = path.len();
+    for segment in path.rsplit('/') {
+        let start = end - segment.len();
+        if !matches!(segment, "" | ".") {
+            return (segment != "..").then_some(start..end);
+        }
+        end = start.saturating_sub(1);
+    }
+
+    // No final segment found
+    None
+}
+
+/// Replaces the extension of the final segment of a path, if any.
+///
+/// This mirrors [`PathBuf::set_extension`], so only the last extension is
+/// replaced or appended, if absent, and an empty extension removes it. Like
+/// in [`file_name`], trailing slashes and `.` segments are ignored, and thus
+/// dropped. If there's no final segment, [`None`] is returned.
+pub(crate) fn replace_extension(path: &str, extension: &str) -> Option<String> {
+    let range = file_name_range(path)?;
+    let (stem, _) = split_extension(&path[range.clone()]);
+
+    // Assemble path from everything up to the stem, and the extension
+    let mut value = String::with_capacity(range.end + extension.len() + 1);
+    value.push_str(&path[..range.start]);
+    value.push_str(stem);
+    if !extension.is_empty() {
+        value.push('.');
+        value.push_str(extension);
+    }
+    Some(value)
+}
+
+/// Splits a file name into stem and extension.
+///
+/// This mirrors [`Path::file_stem`] and [`Path::extension`], so only the last
+/// extension is split off, and dotfiles like `.gitignore` have no extension.
+pub(crate) fn split_extension(name: &str) -> (&str, Option<&str>) {
+    match name.rfind('.') {
+        Some(index) if index > 0 => (&name[..index], Some(&name[index + 1..])),
+        _ => (name, None),
+    }
+}
+
+/// Computes the relative path from a base path to a path.
+///
+/// If `dir` is set, the base path is treated as a directory, and otherwise as
+/// a file, which means that the path is resolved relative to its parent. Empty
+/// and `.` segments are ignored, and identical paths yield `.`.
+pub(crate) fn relative(path: &str, base: &str, dir: bool) -> String {
+    let path: Vec<_> = segments(path).collect();
+    let mut base: Vec<_> = segments(base).collect();
+    if !dir {
+        base.pop();
+    }
+
+    // Compute the common prefix, and traverse up from the base to it, and
+    // then down to the path, joining all segments with forward slashes
+    let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut parts = vec![".."; base.len() - common];
+    parts.extend(&path[common..]);
+    if parts.is_empty() {
+        String::from(".")
+    } else {
+        parts.join("/")
+    }
+}
+
+/// Checks whether a path starts with a prefix, comparing whole segments.
+///
+/// Empty and `.` segments are ignored, so `a/b` starts with `a/` and `./a`,
+/// but not with `a/b/c` or `a/bc`. Every path starts with an empty prefix.
+pub(crate) fn starts_with(path: &str, prefix: &str) -> bool {
+    let mut path = segments(path);
+    segments(prefix).all(|segment| path.next() == Some(segment))
+}
+
+/// Creates a relative URL path from a context, path, and fragment.
+///
+/// Empty and `.` segments are ignored, and the remaining segments are joined
+/// with `/` and percent-encoded as mandated by RFC 3986, which includes `:`,
+/// so the first segment can never be mistaken for a scheme. The fragment, if
+/// any, is percent-encoded and appended after a `#`.
+///
+/// # Errors
+///
+/// If the context or path is absolute or contains `..`, [`Error::RootDir`] or
+/// [`Error::ParentDir`] is returned, respectively.
+pub(crate) fn to_url(
+    context: Option<&str>, path: &str, fragment: Option<&str>,
+) -> Result<String> {
+    let mut value = String::with_capacity(path.len());
+    for part in context.into_iter().chain([path]) {
+        if part.starts_with('/') {
+            return Err(Error::RootDir);
+        }
+
+        // Encode and append each segment, rejecting path traversal
+        for segment in segments(part) {
+            if segment == ".." {
+                return Err(Error::ParentDir);
+            }
+            if !value.is_empty() {
+                value.push('/');
+            }
+            value.extend(percent_encode(segment.as_bytes(), SEGMENT));
+        }
+    }
+
+    // Append fragment, if any
+    if let Some(fragment) = fragment {
+        value.push('#');
+        value.extend(percent_encode(fragment.as_bytes(), FRAGMENT));
+    }
+    Ok(value)
+}
+
+/// Splits a decoded path into segments, ignoring empty segments.
+///
+/// If the path is borrowed, segments are borrowed from it, and otherwise, the
+/// path was decoded into an owned value, so segments are collected as owned
+/// values, which ties their lifetimes to the original value only.
+pub(crate) fn split_segments(
+    path: Cow<'_, str>,
+) -> impl Iterator<Item = Cow<'_, str>> {
+    let (borrowed, owned) = match path {
+        Cow::Borrowed(path) => (Some(path), Vec::new()),
+        Cow::Owned(path) => {
+            let iter = path.split('/').filter(|segment| !segment.is_empty());
+            (
+                None,
+                iter.map(|segment| Cow::Owned(segment.to_owned())).collect(),
+            )
+        }
+    };
+
+    // Borrow segments from the path, if it's borrowed
+    borrowed
+        .into_iter()
+        .flat_map(|path| path.split('/'))
+        .filter(|segment| !segment.is_empty())
+        .map(Cow::Borrowed)
+        .chain(owned)
+}
+
+/// Splits a path into segments, ignoring empty and `.` segments.
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter(|segment| !matches!(*segment, "" | "."))
+}
 
 /// Ensure that the given value is a valid path.
 ///
 /// We normalize paths to use forward slashes, which is the default on Unix
 /// systems and also supported on Windows. This way, we can ensure that paths
 /// are portable and can be used as URLs. Rust's file system API ensures that
-/// paths are correctly resolved for the current platform.
+/// paths are correctly resolved for the current platform. NUL bytes are never
+/// allowed, as they would truncate paths and strings passed to C functions.
 ///
 /// # Errors
 ///
-/// If a backslash is found, [`Error::Backslash`] is returned.
+/// If a backslash is found, [`Error::Backslash`] is returned, and if a NUL
+/// byte is found, [`Error::Nul`] is returned.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::path::{validate, Error};
+///
+/// // Ensure that NUL bytes are rejected with their offset
+/// let res = validate("a/b\0c.md");
+/// assert!(matches!(res, Err(Error::Nul { offset: 3 })));
+///
+/// // Ensure that other control characters are allowed
+/// assert!(validate("a/b\nc.md").is_ok());
+/// ```
 #[inline]
 pub fn validate<S>(value: S) -> Result<S>
 where
     S: AsRef<[u8]>,
 {
-    if value.as_ref().contains(&b'\\') {
-        Err(Error::Backslash)
-    } else {
-        Ok(value)
+    value
+        .as_ref()
+        .iter()
+        .enumerate()
+        .try_for_each(|(offset, &byte)| validate_byte(offset, byte))?;
+
+    // No errors occurred
+    Ok(value)
+}
+
+/// Ensure that the given value is a valid path without control characters.
+///
+/// This is the stricter variant of [`validate`], which additionally rejects
+/// all C0 control characters, e.g., newlines and tabs, which are valid in file
+/// names on most systems, but wreak havoc in log lines and terminals.
+///
+/// # Errors
+///
+/// In addition to the errors of [`validate`], [`Error::Control`] is returned
+/// if a control character is found.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::path::{validate_strict, Error};
+///
+/// // Ensure that control characters are rejected with their offset
+/// let res = validate_strict("a/b\nc.md");
+/// assert!(matches!(res, Err(Error::Control { byte: b'\n', offset: 3 })));
+/// assert!(validate_strict("a/b.md").is_ok());
+/// ```
+pub fn validate_strict<S>(value: S) -> Result<S>
+where
+    S: AsRef<[u8]>,
+{
+    let value = validate(value)?;
+    match value.as_ref().iter().position(|&byte| byte < 0x20) {
+        Some(offset) => {
+            let byte = value.as_ref()[offset];
+            Err(Error::Control { byte, offset })
+        }
+        None => Ok(value),
+    }
+}
+
+/// Ensure that the given byte is valid in a path.
+///
+/// This is the byte-wise equivalent of [`validate`], which allows to fold the
+/// validation into the scan when parsing identifiers and selectors. The given
+/// offset is only used for error reporting.
+///
+/// # Errors
+///
+/// If the byte is a backslash, [`Error::Backslash`] is returned, and if it's
+/// a NUL byte, [`Error::Nul`] is returned.
+#[inline]
+pub(crate) fn validate_byte(offset: usize, byte: u8) -> Result {
+    match byte {
+        b'\\' => Err(Error::Backslash),
+        0 => Err(Error::Nul { offset }),
+        _ => Ok(()),
     }
 }
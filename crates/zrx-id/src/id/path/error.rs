@@ -22,6 +22,7 @@
 
 //! Path error.
 
+use std::path::PathBuf;
 use std::result;
 use thiserror::Error;
 
@@ -36,6 +37,10 @@ pub enum Error {
     #[error("path must not start at '/'")]
     RootDir,
 
+    /// Path must not start with a Windows prefix.
+    #[error("path must not start with Windows prefix: {0:?}")]
+    WindowsPrefix(String),
+
     /// Path must not contain '..'.
     #[error("path must not contain '..'")]
     ParentDir,
@@ -43,6 +48,42 @@ pub enum Error {
     /// Path must not contain '\\'.
     #[error("path must not contain '\\'")]
     Backslash,
+
+    /// Path must not contain NUL bytes.
+    #[error("path must not contain NUL byte at offset {offset}")]
+    Nul {
+        /// Byte offset in the value.
+        offset: usize,
+    },
+
+    /// Path must not contain control characters.
+    #[error(
+        "path must not contain control character {byte:#04x} \
+         at offset {offset}"
+    )]
+    Control {
+        /// Offending byte.
+        byte: u8,
+        /// Byte offset in the value.
+        offset: usize,
+    },
+
+    /// Path is not valid UTF-8.
+    #[error("path is not valid UTF-8: {0:?}")]
+    NonUtf8(PathBuf),
+
+    /// Binding is not mounted.
+    #[error("binding is not mounted: {0:?}")]
+    Unmounted(String),
+
+    /// Scheme and binding can't be resolved.
+    #[error("no root for scheme {scheme:?} and binding {binding:?}")]
+    Unresolved {
+        /// Scheme of the identifier.
+        scheme: String,
+        /// Binding of the identifier, if any.
+        binding: Option<String>,
+    },
 }
 
 // ----------------------------------------------------------------------------
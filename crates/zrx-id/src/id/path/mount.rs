@@ -0,0 +1,123 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Mount map.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Mount map.
+///
+/// Mount maps associate the `binding` component of identifiers with absolute
+/// root paths, e.g., drive letters, UNC shares, or directories that plugins
+/// ship their own artifacts in. Identifiers without a binding are resolved
+/// through the mount point registered for the empty binding, if any.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use zrx_id::path::MountMap;
+///
+/// // Create mount map and add mount points
+/// let mut mounts = MountMap::new();
+/// mounts.insert("c", r"C:\");
+/// mounts.insert("share", r"\\server\share");
+///
+/// // Obtain mount point
+/// assert_eq!(mounts.get("c"), Some(Path::new(r"C:\")));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MountMap {
+    /// Mount points by binding.
+    mounts: HashMap<String, PathBuf>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl MountMap {
+    /// Creates a mount map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::path::MountMap;
+    ///
+    /// // Create mount map
+    /// let mounts = MountMap::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a mount point for the given binding.
+    ///
+    /// If a mount point was already registered for the binding, it's replaced.
+    /// Use the empty binding to register the mount point for identifiers that
+    /// don't have a binding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::path::MountMap;
+    ///
+    /// // Create mount map and add mount point
+    /// let mut mounts = MountMap::new();
+    /// mounts.insert("c", r"C:\");
+    /// ```
+    pub fn insert<B, P>(&mut self, binding: B, root: P) -> &mut Self
+    where
+        B: Into<String>,
+        P: Into<PathBuf>,
+    {
+        self.mounts.insert(binding.into(), root.into());
+        self
+    }
+
+    /// Returns the mount point for the given binding, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use zrx_id::path::MountMap;
+    ///
+    /// // Create mount map and add mount point
+    /// let mut mounts = MountMap::new();
+    /// mounts.insert("c", r"C:\");
+    ///
+    /// // Obtain mount point
+    /// assert_eq!(mounts.get("c"), Some(Path::new(r"C:\")));
+    /// assert_eq!(mounts.get("d"), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, binding: &str) -> Option<&Path> {
+        self.mounts.get(binding).map(PathBuf::as_path)
+    }
+}
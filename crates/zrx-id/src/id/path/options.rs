@@ -0,0 +1,56 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Path conversion options.
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Path conversion options.
+///
+/// Options control the conversion of identifiers into paths, see
+/// [`try_into_path_with`][super::try_into_path_with]. The default options
+/// apply the same rules as the conversion into a [`PathBuf`][].
+///
+/// [`PathBuf`]: std::path::PathBuf
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::path::Options;
+///
+/// // Create options allowing `..`
+/// let options = Options {
+///     allow_parent: true,
+///     ..Options::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Whether to resolve `..` instead of rejecting it.
+    ///
+    /// If enabled, `..` segments are resolved against the preceding segments
+    /// of the `context` and `path` components, but traversal must never break
+    /// out of the root, i.e., go above the first segment of the `context`.
+    pub allow_parent: bool,
+}
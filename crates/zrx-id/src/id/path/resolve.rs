@@ -0,0 +1,105 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Root resolver.
+
+use std::path::PathBuf;
+
+use super::MountMap;
+
+// ----------------------------------------------------------------------------
+// Traits
+// ----------------------------------------------------------------------------
+
+/// Root resolver.
+///
+/// Resolvers map the `scheme` and `binding` components of identifiers to root
+/// paths, onto which the relative path created from the `context` and `path`
+/// components is joined, see [`resolve`][super::resolve]. This allows, e.g.,
+/// to map the bindings of the `git` scheme to separate checkout directories.
+///
+/// Resolvers are implemented for [`MountMap`], which ignores the scheme, and
+/// for closures with a matching signature.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use zrx_id::path::Resolve;
+///
+/// // Create resolver for checkout directories
+/// let resolver = |scheme: &str, binding: Option<&str>| match scheme {
+///     "git" => binding.map(|branch| PathBuf::from("checkouts").join(branch)),
+///     _ => None,
+/// };
+///
+/// // Obtain root for scheme and binding
+/// assert_eq!(
+///     resolver.root("git", Some("main")),
+///     Some(PathBuf::from("checkouts").join("main")),
+/// );
+/// assert_eq!(resolver.root("file", None), None);
+/// ```
+pub trait Resolve {
+    /// Returns the root path for the given scheme and binding, if any.
+    fn root(&self, scheme: &str, binding: Option<&str>) -> Option<PathBuf>;
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<F> Resolve for F
+where
+    F: Fn(&str, Option<&str>) -> Option<PathBuf>,
+{
+    #[inline]
+    fn root(&self, scheme: &str, binding: Option<&str>) -> Option<PathBuf> {
+        self(scheme, binding)
+    }
+}
+
+impl Resolve for MountMap {
+    /// Returns the mount point for the given binding, if any.
+    ///
+    /// The scheme is ignored, and identifiers without a binding are resolved
+    /// through the mount point registered for the empty binding, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use zrx_id::path::{MountMap, Resolve};
+    ///
+    /// // Create mount map and add mount point
+    /// let mut mounts = MountMap::new();
+    /// mounts.insert("c", r"C:\");
+    ///
+    /// // Obtain root for scheme and binding
+    /// assert_eq!(mounts.root("file", Some("c")), Some(PathBuf::from(r"C:\")));
+    /// assert_eq!(mounts.root("file", None), None);
+    /// ```
+    #[inline]
+    fn root(&self, _scheme: &str, binding: Option<&str>) -> Option<PathBuf> {
+        self.get(binding.unwrap_or_default()).map(PathBuf::from)
+    }
+}
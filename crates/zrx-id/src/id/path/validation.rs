@@ -0,0 +1,95 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Validation policy.
+
+use std::borrow::Cow;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Validation policy.
+///
+/// Policies control how component values are treated before the usual checks
+/// of [`validate`][super::validate] are applied. The default policy is strict,
+/// which rejects backslashes, as they would break the portability of caches.
+/// Policies only ever apply to literal characters, so percent-encoded `%5C`
+/// sequences are never normalized.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::path::Validation;
+/// use zrx_id::prelude::*;
+///
+/// // Create identifier from Windows-style path
+/// let policy = Validation::NormalizeBackslashes;
+/// let id = Id::new_with(policy, "file", "docs", r"guide\index.md")?;
+/// assert_eq!(id.path(), "guide/index.md");
+///
+/// // Backslashes are rejected by default
+/// let policy = Validation::default();
+/// assert!(Id::new_with(policy, "file", "docs", r"guide\index.md").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum Validation {
+    /// Reject backslashes.
+    #[default]
+    Strict,
+    /// Rewrite backslashes to forward slashes.
+    NormalizeBackslashes,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Validation {
+    /// Applies the policy to the given string.
+    pub(crate) fn apply(self, value: &str) -> Cow<'_, str> {
+        match self {
+            Validation::NormalizeBackslashes if value.contains('\\') => {
+                Cow::Owned(value.replace('\\', "/"))
+            }
+            _ => Cow::Borrowed(value),
+        }
+    }
+
+    /// Applies the policy to the given bytes.
+    pub(crate) fn apply_bytes(self, value: &[u8]) -> Cow<'_, [u8]> {
+        match self {
+            Validation::NormalizeBackslashes if value.contains(&b'\\') => {
+                let iter = value.iter().map(|&byte| match byte {
+                    b'\\' => b'/',
+                    _ => byte,
+                });
+                Cow::Owned(iter.collect())
+            }
+            _ => Cow::Borrowed(value),
+        }
+    }
+}
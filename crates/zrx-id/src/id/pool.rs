@@ -0,0 +1,357 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Identifier pool.
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use super::format::hash::fnv1a_64;
+use super::{Id, Result};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Number of shards, which must be a power of two.
+const SHARDS: usize = 16;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Identifier pool.
+///
+/// Identifier pools intern identifiers, so each distinct identifier is stored
+/// exactly once, and handed out as an [`InternedId`], which is cheap to clone
+/// and compare. Pools are thread-safe, and split into shards, each guarded by
+/// a read-write lock, so concurrent lookups of different identifiers rarely
+/// contend. Entries are never removed implicitly - use [`IdPool::gc`] to drop
+/// identifiers that are no longer referenced outside of the pool.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::prelude::*;
+/// use zrx_id::IdPool;
+///
+/// // Create pool and intern identifier twice
+/// let pool = IdPool::new();
+/// let a = pool.intern(&Id::new("file", "docs", "index.md")?);
+/// let b = pool.intern(&Id::new("file", "docs", "index.md")?);
+/// assert_eq!(a, b);
+/// assert_eq!(pool.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct IdPool {
+    /// Shards of interned identifiers.
+    shards: [RwLock<HashSet<InternedId>>; SHARDS],
+}
+
+/// Interned identifier.
+///
+/// Interned identifiers are handles to identifiers stored in an [`IdPool`],
+/// which dereference to [`Id`]. Cloning only increments a reference count,
+/// and comparing two handles from the same pool is a pointer comparison.
+#[derive(Clone)]
+pub struct InternedId {
+    /// Shared identifier.
+    inner: Arc<Id>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl IdPool {
+    /// Creates an identifier pool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::IdPool;
+    ///
+    /// // Create pool
+    /// let pool = IdPool::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interns an identifier, returning the shared handle.
+    ///
+    /// If an equal identifier was interned before, its handle is returned,
+    /// and the given identifier is only cloned if it's new to the pool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdPool;
+    ///
+    /// // Create pool and intern identifier
+    /// let pool = IdPool::new();
+    /// let id = pool.intern(&Id::new("file", "docs", "index.md")?);
+    /// assert_eq!(id.path(), "index.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn intern(&self, id: &Id) -> InternedId {
+        self.get(id.as_str())
+            .unwrap_or_else(|| self.insert_with(id.as_str(), || id.clone()))
+    }
+
+    /// Interns an identifier from a string, returning the shared handle.
+    ///
+    /// If the string is the canonical representation of an identifier that
+    /// was interned before, its handle is returned without parsing.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the given string is not a valid
+    /// identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdPool;
+    ///
+    /// // Create pool and intern identifier from string
+    /// let pool = IdPool::new();
+    /// let a = pool.intern_str("zri:file::docs:index.md:")?;
+    /// let b = pool.intern(&Id::new("file", "docs", "index.md")?);
+    /// assert_eq!(a, b);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn intern_str(&self, value: &str) -> Result<InternedId> {
+        if let Some(id) = self.get(value) {
+            return Ok(id);
+        }
+
+        // Parse identifier, which might still be non-canonical, so we use its
+        // canonical representation for insertion, not the given string
+        let id: Id = value.parse()?;
+        Ok(self.insert_with(id.as_str(), || id.clone()))
+    }
+
+    /// Returns the handle of an interned identifier, if any.
+    ///
+    /// The given string must be in canonical form, as returned by
+    /// [`Id::as_str`], or the lookup will miss.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdPool;
+    ///
+    /// // Create pool and intern identifier
+    /// let pool = IdPool::new();
+    /// pool.intern(&Id::new("file", "docs", "index.md")?);
+    ///
+    /// // Obtain handle of interned identifier
+    /// assert!(pool.get("zri:file::docs:index.md:").is_some());
+    /// assert!(pool.get("zri:file::docs:about.md:").is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn get(&self, value: &str) -> Option<InternedId> {
+        let shard = self.shard(value).read();
+        let shard = shard.unwrap_or_else(PoisonError::into_inner);
+        shard.get(value).cloned()
+    }
+
+    /// Removes all identifiers that are only referenced by the pool.
+    ///
+    /// This method returns the number of removed identifiers. Handles that
+    /// are still held elsewhere keep their identifiers alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdPool;
+    ///
+    /// // Create pool and intern identifiers, dropping one handle
+    /// let pool = IdPool::new();
+    /// let id = pool.intern(&Id::new("file", "docs", "index.md")?);
+    /// pool.intern(&Id::new("file", "docs", "about.md")?);
+    ///
+    /// // Remove unreferenced identifiers
+    /// assert_eq!(pool.gc(), 1);
+    /// assert_eq!(pool.len(), 1);
+    /// assert!(pool.get(id.as_str()).is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn gc(&self) -> usize {
+        let mut removed = 0;
+        for shard in &self.shards {
+            let mut shard =
+                shard.write().unwrap_or_else(PoisonError::into_inner);
+            let len = shard.len();
+            shard.retain(|id| Arc::strong_count(&id.inner) > 1);
+            removed += len - shard.len();
+        }
+        removed
+    }
+
+    /// Inserts an identifier into its shard, unless it's already present.
+    ///
+    /// The shard is checked again after acquiring the write lock, since the
+    /// identifier might have been inserted by another thread in the meantime.
+    fn insert_with<F>(&self, value: &str, f: F) -> InternedId
+    where
+        F: FnOnce() -> Id,
+    {
+        let shard = self.shard(value).write();
+        let mut shard = shard.unwrap_or_else(PoisonError::into_inner);
+        if let Some(id) = shard.get(value) {
+            return id.clone();
+        }
+
+        // Create handle and insert it into the shard
+        let id = InternedId { inner: Arc::new(f()) };
+        shard.insert(id.clone());
+        id
+    }
+
+    /// Returns the shard for the given string.
+    #[inline]
+    fn shard(&self, value: &str) -> &RwLock<HashSet<InternedId>> {
+        #[allow(clippy::cast_possible_truncation)]
+        let index = fnv1a_64(value.as_bytes()) as usize & (SHARDS - 1);
+        &self.shards[index]
+    }
+}
+
+impl IdPool {
+    /// Returns the number of interned identifiers.
+    ///
+    /// Since other threads might intern identifiers concurrently, the number
+    /// is only a snapshot, and might be outdated once it's returned.
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| {
+                let shard = shard.read();
+                shard.unwrap_or_else(PoisonError::into_inner).len()
+            })
+            .sum()
+    }
+
+    /// Returns whether the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Deref for InternedId {
+    type Target = Id;
+
+    /// Dereferences to the identifier.
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl AsRef<Id> for InternedId {
+    /// Returns a reference to the identifier.
+    #[inline]
+    fn as_ref(&self) -> &Id {
+        &self.inner
+    }
+}
+
+impl Borrow<str> for InternedId {
+    /// Borrows the string representation.
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.inner.as_str()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Hash for InternedId {
+    /// Hashes the identifier.
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
+impl PartialEq for InternedId {
+    /// Compares two interned identifiers for equality.
+    ///
+    /// Handles from the same pool are compared by pointer, and only handles
+    /// from different pools need to compare the identifiers themselves.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner) || self.inner == other.inner
+    }
+}
+
+impl Eq for InternedId {}
+
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for InternedId {
+    /// Formats the interned identifier for display.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+impl fmt::Debug for InternedId {
+    /// Formats the interned identifier for debugging.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.inner, f)
+    }
+}
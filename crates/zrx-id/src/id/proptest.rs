@@ -0,0 +1,114 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Property testing strategies for identifiers.
+
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::vec;
+use proptest::option;
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+use super::Id;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Pattern for schemes.
+const SCHEME: &str = "[a-z][a-z0-9+.-]{0,7}";
+
+/// Pattern for values, which include unicode characters, as well as `:` and
+/// `%`, which need to be percent-encoded, but never start with a `.`, so they
+/// can't form `.` or `..` segments.
+pub(crate) const VALUE: &str =
+    "[a-zA-Z0-9_ :%äß€日🎉-][a-zA-Z0-9._ :%äß€日🎉-]{0,7}";
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Arbitrary for Id {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Creates a strategy for generating valid identifiers.
+    ///
+    /// Identifiers are generated with a valid scheme, an optional binding, a
+    /// context, a path of up to four segments, and an optional fragment. They
+    /// shrink towards absent optional components and shorter paths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use proptest::prelude::*;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Ensure identifiers round trip through their string representation
+    /// proptest!(|(id: Id)| {
+    ///     prop_assert_eq!(id.to_string().parse::<Id>().unwrap(), id.clone());
+    ///     prop_assert_eq!(Id::from_bytes(id.as_bytes()).unwrap(), id);
+    /// });
+    /// ```
+    ///
+    /// Components are retained as is:
+    ///
+    /// ```
+    /// use proptest::prelude::*;
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Ensure components round trip through the string representation
+    /// proptest!(|(id: Id)| {
+    ///     let parsed: Id = id.to_string().parse().unwrap();
+    ///     prop_assert_eq!(parsed.scheme(), id.scheme());
+    ///     prop_assert_eq!(parsed.binding(), id.binding());
+    ///     prop_assert_eq!(parsed.context(), id.context());
+    ///     prop_assert_eq!(parsed.path(), id.path());
+    ///     prop_assert_eq!(parsed.fragment(), id.fragment());
+    /// });
+    /// ```
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        (SCHEME, option::of(VALUE), VALUE, path(), option::of(VALUE))
+            .prop_filter_map(
+                "identifier must be valid",
+                |(scheme, binding, context, path, fragment)| {
+                    Id::new_full(
+                        scheme,
+                        binding.unwrap_or_default(),
+                        context,
+                        path,
+                        fragment.unwrap_or_default(),
+                    )
+                    .ok()
+                },
+            )
+            .boxed()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Creates a strategy for generating paths of one to four segments.
+fn path() -> impl Strategy<Value = String> {
+    vec(VALUE, 1..=4).prop_map(|segments| segments.join("/"))
+}
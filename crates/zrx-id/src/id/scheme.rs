@@ -0,0 +1,220 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Scheme registry.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::result;
+use std::sync::Arc;
+
+use super::{Error, Id, Result};
+
+mod builder;
+
+use builder::Builder;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Scheme registry.
+///
+/// Scheme registries are an opt-in mechanism to catch typos in schemes, e.g.,
+/// `fille` instead of `file`, which would otherwise go unnoticed, as nothing
+/// matches downstream. Applications register the set of allowed schemes, and
+/// optionally a validator for each scheme, which can impose further rules on
+/// the other components. Parsing with [`FromStr`][] is not affected.
+///
+/// Registries are immutable once built, and cheap to clone, since the schemes
+/// are shared, so they can be handed to as many threads as necessary.
+///
+/// [`FromStr`]: std::str::FromStr
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::SchemeRegistry;
+///
+/// // Create scheme registry
+/// let mut builder = SchemeRegistry::builder();
+/// builder.add("file")?;
+/// builder.add_with("git", |id| {
+///     id.binding().map(|_| ()).ok_or_else(|| String::from("missing branch"))
+/// })?;
+/// let registry = builder.build();
+///
+/// // Parse identifiers and validate them against the registry
+/// assert!(registry.parse("zri:file::docs:index.md:").is_ok());
+/// assert!(registry.parse("zri:git:main:docs:index.md:").is_ok());
+/// assert!(registry.parse("zri:git::docs:index.md:").is_err());
+/// assert!(registry.parse("zri:fille::docs:index.md:").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct SchemeRegistry {
+    /// Allowed schemes and their validators.
+    schemes: Arc<HashMap<String, Option<Validator>>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl SchemeRegistry {
+    /// Creates a scheme registry builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::SchemeRegistry;
+    ///
+    /// // Create scheme registry builder
+    /// let mut builder = SchemeRegistry::builder();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Validates an identifier against the registry.
+    ///
+    /// The scheme of the identifier must be registered, and if a validator was
+    /// registered for the scheme, the identifier must pass it.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Scheme`], if the scheme isn't registered,
+    /// or [`Error::Rejected`], if the validator of the scheme rejects the
+    /// identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::SchemeRegistry;
+    ///
+    /// // Create scheme registry
+    /// let mut builder = SchemeRegistry::builder();
+    /// builder.add("file")?;
+    /// let registry = builder.build();
+    ///
+    /// // Validate identifiers against the registry
+    /// let id = Id::new("file", "docs", "index.md")?;
+    /// assert!(registry.validate(&id).is_ok());
+    /// let id = Id::new("fille", "docs", "index.md")?;
+    /// assert_eq!(
+    ///     registry.validate(&id).unwrap_err().to_string(),
+    ///     "unknown scheme: fille"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate(&self, id: &Id) -> Result {
+        let scheme = id.scheme();
+        match self.schemes.get(scheme.as_ref()) {
+            None => Err(Error::Scheme { scheme: scheme.into_owned() }),
+            Some(None) => Ok(()),
+            Some(Some(validator)) => {
+                validator(id).map_err(|reason| Error::Rejected {
+                    scheme: scheme.into_owned(),
+                    reason,
+                })
+            }
+        }
+    }
+
+    /// Parses an identifier, and validates it against the registry.
+    ///
+    /// This combines [`FromStr`][] and [`SchemeRegistry::validate`].
+    ///
+    /// [`FromStr`]: std::str::FromStr
+    ///
+    /// # Errors
+    ///
+    /// This method returns the errors of [`FromStr`][], if the identifier is
+    /// invalid, and the errors of [`SchemeRegistry::validate`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::SchemeRegistry;
+    ///
+    /// // Create scheme registry
+    /// let mut builder = SchemeRegistry::builder();
+    /// builder.add("file")?;
+    /// let registry = builder.build();
+    ///
+    /// // Parse identifier and validate it against the registry
+    /// let id = registry.parse("zri:file::docs:index.md:")?;
+    /// assert_eq!(id.path(), "index.md");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse(&self, value: &str) -> Result<Id> {
+        let id = value.parse()?;
+        self.validate(&id).map(|()| id)
+    }
+
+    /// Returns whether the given scheme is registered.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, scheme: &str) -> bool {
+        self.schemes.contains_key(scheme)
+    }
+
+    /// Returns an iterator over all registered schemes.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.schemes.keys().map(String::as_str)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Debug for SchemeRegistry {
+    /// Formats the scheme registry for debugging.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut schemes: Vec<_> = self.iter().collect();
+        schemes.sort_unstable();
+        f.debug_struct("SchemeRegistry")
+            .field("schemes", &schemes)
+            .finish()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Type aliases
+// ----------------------------------------------------------------------------
+
+/// Validator for identifiers of a scheme.
+type Validator = Arc<dyn Fn(&Id) -> result::Result<(), String> + Send + Sync>;
@@ -0,0 +1,185 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Scheme registry builder.
+
+use std::collections::HashMap;
+use std::result;
+use std::sync::Arc;
+
+use crate::id::path::validate;
+use crate::id::{Error, Id, Result};
+
+use super::{SchemeRegistry, Validator};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Scheme registry builder.
+pub struct Builder {
+    /// Allowed schemes and their validators.
+    schemes: HashMap<String, Option<Validator>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Builder {
+    /// Creates a scheme registry builder.
+    ///
+    /// Note that the canonical way to create a [`SchemeRegistry`] is to invoke
+    /// the [`SchemeRegistry::builder`] method, which creates an instance of
+    /// [`Builder`], which is why we don't implement [`Default`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zrx_id::SchemeRegistry;
+    ///
+    /// // Create scheme registry builder
+    /// let mut builder = SchemeRegistry::builder();
+    /// ```
+    #[allow(clippy::new_without_default)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self { schemes: HashMap::new() }
+    }
+
+    /// Adds a scheme to the registry.
+    ///
+    /// If the scheme was added before, its validator is removed.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Component`], if the scheme is empty, or
+    /// [`Error::Path`], if it contains a backslash or NUL byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::SchemeRegistry;
+    ///
+    /// // Create scheme registry builder and add scheme
+    /// let mut builder = SchemeRegistry::builder();
+    /// builder.add("file")?;
+    /// assert!(builder.add("").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add<S>(&mut self, scheme: S) -> Result<&mut Self>
+    where
+        S: Into<String>,
+    {
+        self.insert(scheme.into(), None)
+    }
+
+    /// Adds a scheme with a validator to the registry.
+    ///
+    /// The validator is invoked with each identifier of the scheme, and must
+    /// return the reason for rejecting an identifier, if any. If the scheme
+    /// was added before, its validator is replaced.
+    ///
+    /// # Errors
+    ///
+    /// This method returns the same errors as [`Builder::add`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::SchemeRegistry;
+    ///
+    /// // Create scheme registry builder and add scheme with validator
+    /// let mut builder = SchemeRegistry::builder();
+    /// builder.add_with("file", |id| {
+    ///     if id.fragment().is_none() {
+    ///         Ok(())
+    ///     } else {
+    ///         Err(String::from("fragments are not supported"))
+    ///     }
+    /// })?;
+    ///
+    /// // Validate identifier against the registry
+    /// let registry = builder.build();
+    /// let id = Id::new("file", "docs", "index.md")?.with_fragment("top")?;
+    /// assert_eq!(
+    ///     registry.validate(&id).unwrap_err().to_string(),
+    ///     "rejected by scheme file: fragments are not supported"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_with<S, F>(&mut self, scheme: S, f: F) -> Result<&mut Self>
+    where
+        S: Into<String>,
+        F: Fn(&Id) -> result::Result<(), String> + Send + Sync + 'static,
+    {
+        self.insert(scheme.into(), Some(Arc::new(f)))
+    }
+
+    /// Builds the scheme registry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::SchemeRegistry;
+    ///
+    /// // Create scheme registry builder and add scheme
+    /// let mut builder = SchemeRegistry::builder();
+    /// builder.add("file")?;
+    ///
+    /// // Create scheme registry from builder
+    /// let registry = builder.build();
+    /// assert!(registry.contains("file"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn build(self) -> SchemeRegistry {
+        SchemeRegistry {
+            schemes: Arc::new(self.schemes),
+        }
+    }
+
+    /// Inserts a scheme with an optional validator.
+    fn insert(
+        &mut self, scheme: String, validator: Option<Validator>,
+    ) -> Result<&mut Self> {
+        if scheme.is_empty() {
+            let name = "scheme";
+            return Err(Error::Component { name, offset: None });
+        }
+
+        // Ensure scheme is valid, and insert it
+        let scheme = validate(scheme)?;
+        self.schemes.insert(scheme, validator);
+        Ok(self)
+    }
+}
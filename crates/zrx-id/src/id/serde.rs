@@ -0,0 +1,184 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Serialization and deserialization of identifiers.
+
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+use super::format::Format;
+use super::path::validate;
+use super::{Component, Error, Id};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Names of components in map form.
+const FIELDS: &[&str] = &["scheme", "binding", "context", "path", "fragment"];
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Visitor for identifiers.
+struct IdVisitor;
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Serialize for Id {
+    /// Serializes the identifier as its canonical string.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.format.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Id {
+    /// Deserializes an identifier from a string or a map.
+    ///
+    /// Identifiers are accepted in their canonical string form, as well as in
+    /// map form, with the keys `scheme`, `binding`, `context`, `path` and
+    /// `fragment`, of which `binding` and `fragment` are optional. Unknown
+    /// keys are rejected. For formats that are not self-describing, only the
+    /// string form is supported, which is what [`Serialize`] emits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Deserialize identifier from string
+    /// let id: Id = serde_json::from_str(r#""zri:file::docs:index.md:""#)?;
+    /// assert_eq!(id, Id::new("file", "docs", "index.md")?);
+    ///
+    /// // Deserialize identifier from map
+    /// let id: Id = serde_json::from_str(
+    ///     r#"{"scheme": "file", "context": "docs", "path": "index.md"}"#,
+    /// )?;
+    /// assert_eq!(id, Id::new("file", "docs", "index.md")?);
+    ///
+    /// // Deserialize identifier from map, missing path
+    /// let res = serde_json::from_str::<Id>(
+    ///     r#"{"scheme": "file", "context": "docs"}"#,
+    /// );
+    /// let err = res.unwrap_err().to_string();
+    /// assert!(err.starts_with("missing field `path`"));
+    ///
+    /// // Deserialize identifier from map, unknown key
+    /// let res = serde_json::from_str::<Id>(
+    ///     r#"{"scheme": "file", "context": "docs", "file": "a.md"}"#,
+    /// );
+    /// let err = res.unwrap_err().to_string();
+    /// assert!(err.starts_with("unknown field `file`"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(IdVisitor)
+        } else {
+            deserializer.deserialize_str(IdVisitor)
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<'de> Visitor<'de> for IdVisitor {
+    type Value = Id;
+
+    /// Formats the expected value.
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an identifier string or a map of components")
+    }
+
+    /// Visits a string, parsing it into an identifier.
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value.parse().map_err(E::custom)
+    }
+
+    /// Visits a map, creating an identifier from its components.
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut values: [Option<String>; 5] = Default::default();
+        while let Some(key) = map.next_key::<String>()? {
+            let Some(component) =
+                Component::ALL.into_iter().find(|c| c.name() == key)
+            else {
+                return Err(de::Error::unknown_field(&key, FIELDS));
+            };
+
+            // Ensure each component is only given once
+            let value = &mut values[component.index() - 1];
+            if value.is_some() {
+                return Err(de::Error::duplicate_field(component.name()));
+            }
+            *value = Some(map.next_value()?);
+        }
+
+        // Ensure scheme, context and path are present and set, and leave the
+        // binding and fragment empty, if they're not given
+        let [scheme, binding, context, path, fragment] = values;
+        let required = |component: Component, value: Option<String>| {
+            let value = value
+                .ok_or_else(|| de::Error::missing_field(component.name()))?;
+            if value.is_empty() {
+                let name = component.name();
+                Err(de::Error::custom(Error::Component { name, offset: None }))
+            } else {
+                validate(value).map_err(de::Error::custom)
+            }
+        };
+        let optional = |value: Option<String>| {
+            validate(value.unwrap_or_default()).map_err(de::Error::custom)
+        };
+        let parts = [
+            String::from("zri"),
+            required(Component::Scheme, scheme)?,
+            optional(binding)?,
+            required(Component::Context, context)?,
+            required(Component::Path, path)?,
+            optional(fragment)?,
+        ];
+
+        // Create identifier from components
+        Format::from_parts(parts)
+            .map(|format| Id { format })
+            .map_err(de::Error::custom)
+    }
+}
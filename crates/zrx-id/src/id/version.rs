@@ -0,0 +1,109 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Format versioning.
+
+use std::str::FromStr;
+
+use super::format::encoding::decode;
+use super::{Error, Id, Result};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Current format version.
+pub const FORMAT_VERSION: u32 = 1;
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Migrates an identifier from any known format version.
+///
+/// The version is taken from the prefix, which is either unversioned, e.g.
+/// `zri`, denoting the current version, or carries an explicit version, e.g.
+/// `zri1`. This is the hook point for upgraders, which convert identifiers
+/// from older versions to the current one, once the format evolves. Right
+/// now, only the current version exists, so this is equivalent to parsing.
+///
+/// Note that `%` characters in components are percent-encoded as `%25`, so
+/// components like `file%41.md` round trip. Identifiers that were emitted
+/// before this was the case and contain a literal `%` are still parsed, but
+/// a literal `%` followed by two hexadecimal digits is decoded, and all other
+/// literal `%` characters are not in canonical form, so such identifiers
+/// don't compare equal to ones created from their components. They should
+/// be re-created from their components in order to canonicalize them.
+///
+/// # Errors
+///
+/// This function returns [`Error::Version`], if the version is unknown, or
+/// [`Error::Prefix`], if the prefix is invalid. Otherwise, all errors of
+/// [`Id::from_str`] apply.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::{migrate, Id};
+///
+/// // Migrate identifier from versioned prefix
+/// let id = migrate("zri1:file::docs:index.md:")?;
+/// assert_eq!(id, Id::new("file", "docs", "index.md")?);
+///
+/// // Ensure unknown versions are rejected
+/// assert!(migrate("zri9:file::docs:index.md:").is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn migrate(value: &str) -> Result<Id> {
+    let prefix = value.split(':').next().unwrap_or_default();
+    let version = parse(&decode(prefix.as_bytes()), "zri")?;
+
+    // Dispatch to the upgrader for the given version - upgraders for future
+    // versions must be registered here, converting into the current version
+    match version {
+        FORMAT_VERSION => Id::from_str(value),
+        _ => Err(Error::Version(version)),
+    }
+}
+
+/// Parses the version from a prefix.
+///
+/// An unversioned prefix denotes the current version. Versions must not have
+/// leading zeroes, so each version has exactly one representation.
+pub(crate) fn parse(prefix: &str, base: &str) -> Result<u32> {
+    let rest = prefix.strip_prefix(base).ok_or(Error::Prefix)?;
+    if rest.is_empty() {
+        return Ok(FORMAT_VERSION);
+    }
+
+    // Ensure version consists of digits only, without leading zeroes
+    if rest.starts_with('0') || !rest.bytes().all(|byte| byte.is_ascii_digit())
+    {
+        return Err(Error::Prefix);
+    }
+
+    // Versions that don't fit into 32 bits are treated as unknown
+    Ok(rest.parse().unwrap_or(u32::MAX))
+}
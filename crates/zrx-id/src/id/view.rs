@@ -0,0 +1,172 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Identifier view.
+
+use std::borrow::Cow;
+
+use super::format::Format;
+use super::path::validate;
+use super::{Component, Error, Id, Result, ToId};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Identifier view.
+///
+/// Views borrow all components of an [`Id`] at once, which are extracted from
+/// the underlying formatted string in a single pass, so they can be passed to
+/// functions together without repeated extraction. Views can be converted back
+/// into identifiers with [`ToId::to_id`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::prelude::*;
+///
+/// // Create identifier and obtain view
+/// let id = Id::new("file", "docs", "index.md")?;
+/// let view = id.view();
+/// assert_eq!(view.scheme, "file");
+/// assert_eq!(view.binding, None);
+/// assert_eq!(view.path, "index.md");
+///
+/// // Convert view back into identifier
+/// assert_eq!(view.to_id()?.as_ref(), &id);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdView<'a> {
+    /// Scheme component.
+    pub scheme: Cow<'a, str>,
+    /// Binding component, if any.
+    pub binding: Option<Cow<'a, str>>,
+    /// Context component.
+    pub context: Cow<'a, str>,
+    /// Path component.
+    pub path: Cow<'a, str>,
+    /// Fragment component, if any.
+    pub fragment: Option<Cow<'a, str>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Id {
+    /// Returns a view of all components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    ///
+    /// // Create identifier and obtain view
+    /// let id: Id = "zri:file:master:docs:index.md:anchor".parse()?;
+    /// let view = id.view();
+    /// assert_eq!(view.binding.as_deref(), Some("master"));
+    /// assert_eq!(view.fragment.as_deref(), Some("anchor"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn view(&self) -> IdView<'_> {
+        IdView {
+            scheme: self.scheme(),
+            binding: self.binding(),
+            context: self.context(),
+            path: self.path(),
+            fragment: self.fragment(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl ToId for IdView<'_> {
+    /// Creates an identifier from a view.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Path`], if a component value contains a
+    /// backslash, [`Error::Component`], if a required component is empty, or
+    /// [`Error::Format`], if the format is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zrx_id::prelude::*;
+    /// use zrx_id::IdView;
+    ///
+    /// // Create identifier from view
+    /// let view = IdView {
+    ///     scheme: Cow::Borrowed("file"),
+    ///     binding: None,
+    ///     context: Cow::Borrowed("docs"),
+    ///     path: Cow::Borrowed("index.md"),
+    ///     fragment: Some(Cow::Borrowed("anchor")),
+    /// };
+    /// let id = view.to_id()?;
+    /// assert_eq!(id.to_string(), "zri:file::docs:index.md:anchor");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_id(&self) -> Result<Cow<'_, Id>> {
+        let required = [
+            (Component::Scheme, &self.scheme),
+            (Component::Context, &self.context),
+            (Component::Path, &self.path),
+        ];
+        for (component, value) in required {
+            if value.is_empty() {
+                let name = component.name();
+                return Err(Error::Component { name, offset: None });
+            }
+        }
+
+        // Create identifier by writing all components to the formatted string
+        // in a single pass, which encodes values containing `:` separators
+        let parts = [
+            "zri",
+            validate(self.scheme.as_ref())?,
+            validate(self.binding.as_deref().unwrap_or_default())?,
+            validate(self.context.as_ref())?,
+            validate(self.path.as_ref())?,
+            validate(self.fragment.as_deref().unwrap_or_default())?,
+        ];
+        Ok(Cow::Owned(Id {
+            format: Format::from_parts(parts)?,
+        }))
+    }
+}
@@ -23,8 +23,22 @@
 //! Identifier abstractions and utilities.
 
 mod id;
+pub mod prelude;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 
 pub use id::format;
-pub use id::matcher::{self, Matcher, Selector, ToSelector};
+pub use id::fragment;
+#[cfg(feature = "dfa")]
+pub use id::matcher::DfaMatcher;
+#[cfg(feature = "lite-matcher")]
+pub use id::matcher::LiteMatcher;
+#[cfg(feature = "globset")]
+pub use id::matcher::Matcher;
+pub use id::matcher::{self, Selector, SelectorBuilder, ToSelector};
 pub use id::path;
-pub use id::{Error, Id, Result, ToId};
+pub use id::{
+    migrate, ChangeSet, Component, ComponentSet, Error, Id, IdBuilder, IdGraph,
+    IdMap, IdParts, IdPool, IdSet, IdView, InternedId, Result, SchemeRegistry,
+    ToId,
+};
@@ -0,0 +1,75 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Prelude.
+//!
+//! This module re-exports the core types, their builders and conversion
+//! traits, so they can be imported with a single glob import, and methods
+//! provided by traits like [`ToId`] and [`ToSelector`] are always in scope.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use zrx_id::prelude::*;
+//!
+//! // Create identifier from string
+//! let id = "zri:file::docs:index.md:".to_id()?;
+//!
+//! // Create selector from string
+//! let selector = "zrs::::**/*.md:".to_selector()?;
+//! assert_eq!(id.path(), "index.md");
+//! assert_eq!(selector.path().as_deref(), Some("**/*.md"));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The prelude doesn't depend on the standard prelude being in scope:
+//!
+//! ```
+//! #[no_implicit_prelude]
+//! mod hygiene {
+//!     use ::zrx_id::prelude::*;
+//!
+//!     pub fn check() -> ::std::result::Result<(), ::zrx_id::Error> {
+//!         let id = Id::new("file", "docs", "index.md")?;
+//!         let _ = (&id).to_id()?;
+//!         let _ = "zrs::::**/*.md:".to_selector();
+//!         let _ = Component::Path;
+//!         let _: IdBuilder = Id::builder();
+//!         let _: SelectorBuilder = Selector::builder();
+//!         ::std::result::Result::Ok(())
+//!     }
+//! }
+//! # hygiene::check().unwrap();
+//! ```
+
+#[cfg(feature = "dfa")]
+pub use crate::DfaMatcher;
+#[cfg(feature = "lite-matcher")]
+pub use crate::LiteMatcher;
+#[cfg(feature = "globset")]
+pub use crate::Matcher;
+pub use crate::{
+    Component, Id, IdBuilder, Selector, SelectorBuilder, ToId, ToSelector,
+};
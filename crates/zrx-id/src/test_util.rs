@@ -0,0 +1,238 @@
+// Copyright (c) 2024 Zensical <contributors@zensical.org>
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Test utilities.
+//!
+//! This module provides fixtures and assertion helpers for crates that write
+//! tests against identifiers, selectors and matchers, and is only available
+//! when the `test-util` feature is enabled.
+
+#[cfg(feature = "globset")]
+use crate::id::ToId;
+#[cfg(feature = "globset")]
+use crate::Matcher;
+use crate::{Id, Selector};
+
+// ----------------------------------------------------------------------------
+// Macros
+// ----------------------------------------------------------------------------
+
+/// Creates a vector of identifiers from strings.
+///
+/// Each string is parsed with [`parse_id`], which panics with the offending
+/// value and the error if it isn't a valid identifier.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::ids;
+///
+/// // Create identifiers from strings
+/// let ids = ids!["zri:file::docs:index.md:", "zri:git:main:src:lib.rs:"];
+/// assert_eq!(ids.len(), 2);
+/// assert_eq!(ids[1].binding().as_deref(), Some("main"));
+/// ```
+///
+/// Invalid identifiers panic:
+///
+/// ``` should_panic
+/// use zrx_id::ids;
+///
+/// // Create identifiers from strings
+/// let ids = ids!["zri:file::docs::"];
+/// ```
+#[macro_export]
+macro_rules! ids {
+    ($($value:expr),* $(,)?) => {
+        ::std::vec![$($crate::test_util::parse_id($value)),*]
+    };
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Parses an identifier, or panics.
+///
+/// # Panics
+///
+/// Panics if the given value isn't a valid identifier, reporting the value
+/// and the error at the location of the caller.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::test_util::parse_id;
+///
+/// // Parse identifier
+/// let id = parse_id("zri:file::docs:index.md:");
+/// assert_eq!(id.path(), "index.md");
+/// ```
+#[must_use]
+#[track_caller]
+pub fn parse_id(value: &str) -> Id {
+    match value.parse() {
+        Ok(id) => id,
+        Err(err) => panic!("invalid identifier {value:?}: {err}"),
+    }
+}
+
+/// Parses a selector, or panics.
+///
+/// # Panics
+///
+/// Panics if the given value isn't a valid selector, reporting the value and
+/// the error at the location of the caller.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::test_util::parse_selector;
+///
+/// // Parse selector
+/// let selector = parse_selector("zrs::::**/*.md:");
+/// assert_eq!(selector.path().as_deref(), Some("**/*.md"));
+/// ```
+#[must_use]
+#[track_caller]
+pub fn parse_selector(value: &str) -> Selector {
+    match value.parse() {
+        Ok(selector) => selector,
+        Err(err) => panic!("invalid selector {value:?}: {err}"),
+    }
+}
+
+/// Returns a set of representative identifiers.
+///
+/// The identifiers cover the shapes that commonly trip up consumers, i.e., an
+/// empty and a non-empty binding, a deep path, a path with a `:` that needs to
+/// be percent-encoded, a unicode path, and a fragment.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::test_util::sample_ids;
+///
+/// // Obtain representative identifiers
+/// let ids = sample_ids();
+/// assert!(ids.iter().any(|id| id.binding().is_some()));
+/// assert!(ids.iter().any(|id| id.path().contains(':')));
+/// ```
+#[must_use]
+pub fn sample_ids() -> Vec<Id> {
+    [
+        "zri:file::docs:index.md:",
+        "zri:file::docs:guide/getting-started/installation/index.md:",
+        "zri:git:main:src:lib.rs:",
+        "zri:file::docs:c%3A/windows.md:",
+        "zri:file::docs:caf%C3%A9/men%C3%BC.md:",
+        "zri:file::docs:index.md:anchor",
+        "zri:file::assets:images/logo.png:",
+    ]
+    .into_iter()
+    .map(parse_id)
+    .collect()
+}
+
+/// Returns a set of selectors covering each wildcard shape.
+///
+/// The selectors cover literals, `*`, `?`, recursive `**` as prefix, suffix
+/// and in between segments, character classes, alternations, as well as
+/// empty components, which are implicit wildcards.
+///
+/// # Examples
+///
+/// ```
+/// use zrx_id::test_util::sample_selectors;
+///
+/// // Obtain representative selectors
+/// let selectors = sample_selectors();
+/// assert!(selectors.iter().all(|selector| selector.scheme().is_none()));
+/// ```
+#[must_use]
+pub fn sample_selectors() -> Vec<Selector> {
+    [
+        "zrs:::::",
+        "zrs::::index.md:",
+        "zrs::::*.md:",
+        "zrs::::index.m?:",
+        "zrs::::**/*.md:",
+        "zrs::::guide/**:",
+        "zrs::::guide/**/index.md:",
+        "zrs::::[a-z]*.rs:",
+        "zrs::::*.{md,png}:",
+        "zrs::main:src::",
+        "zrs:::docs::anchor",
+    ]
+    .into_iter()
+    .map(parse_selector)
+    .collect()
+}
+
+/// Asserts that a matcher matches exactly the given selectors.
+///
+/// # Panics
+///
+/// Panics if the given identifier is invalid, or if the match set differs
+/// from the expected indexes of the selectors.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zrx_id::prelude::*;
+/// use zrx_id::test_util::assert_matches;
+///
+/// // Create matcher builder and add selectors
+/// let mut builder = Matcher::builder();
+/// builder.add("zrs::::**/*.md:")?;
+/// builder.add("zrs::::**/*.rs:")?;
+///
+/// // Create matcher from builder
+/// let matcher = builder.build()?;
+///
+/// // Ensure identifier matches exactly the first selector
+/// assert_matches(&matcher, "zri:file::docs:index.md:", &[0]);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "globset")]
+#[allow(clippy::needless_pass_by_value)]
+#[track_caller]
+pub fn assert_matches<I>(matcher: &Matcher, id: I, expected: &[usize])
+where
+    I: ToId,
+{
+    let id = match id.to_id() {
+        Ok(id) => id.into_owned(),
+        Err(err) => panic!("invalid identifier: {err}"),
+    };
+    match matcher.matches(&id) {
+        Ok(actual) => assert_eq!(
+            actual, expected,
+            "identifier {id} matched selectors {actual:?}, \
+             expected {expected:?}"
+        ),
+        Err(err) => panic!("matching {id} failed: {err}"),
+    }
+}